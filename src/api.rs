@@ -1,22 +1,24 @@
 mod convert;
 
 use crate::models::database::{
-    AbilityRow, EvolutionRow, GameRow, InsertRow, MoveChangeRow, MoveRow, MoveRowGroup,
-    PokemonAbilityRow, PokemonMoveRow, PokemonRow, PokemonRowGroup, PokemonTypeChangeRow,
-    SelectRow, SpeciesRow, TypeChangeRow, TypeRow, TypeRowGroup,
+    AbilityRow, EncounterRow, EvolutionRow, GameRow, InsertRow, MachineRow, MoveChangeRow,
+    MoveMetaRow, MoveRow, MoveRowGroup, PokemonAbilityRow, PokemonMoveRow, PokemonRow,
+    PokemonRowGroup, PokemonTypeChangeRow, SelectRow, SpeciesRow, TypeChangeRow, TypeRow,
+    TypeRowGroup,
 };
 use crate::models::EvolutionStep;
 use convert::{capture_url_id, FromChange};
 
 use std::collections::HashSet;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use rusqlite::Connection;
 
 use rustemon::evolution::evolution_chain as rustemon_evolution;
 use rustemon::games::version_group as rustemon_version;
+use rustemon::machines::machine as rustemon_machine;
 use rustemon::moves::move_ as rustemon_move;
 use rustemon::pokemon::ability as rustemon_ability;
 use rustemon::pokemon::pokemon as rustemon_pokemon;
@@ -26,8 +28,9 @@ use rustemon::pokemon::type_ as rustemon_type;
 use rustemon::client::{CacheMode, RustemonClient, RustemonClientBuilder};
 use rustemon::model::evolution::EvolutionChain;
 use rustemon::model::games::VersionGroup;
+use rustemon::model::machines::Machine;
 use rustemon::model::moves::Move;
-use rustemon::model::pokemon::{Ability, Pokemon, PokemonSpecies, Type};
+use rustemon::model::pokemon::{Ability, LocationAreaEncounter, Pokemon, PokemonSpecies, Type};
 
 pub fn api_client() -> RustemonClient {
     RustemonClientBuilder::default()
@@ -41,9 +44,30 @@ pub fn game_to_gen(game: &str, db: &Connection) -> u8 {
     game.generation
 }
 
+// Raw rustemon errors do not say whether PokéAPI was unreachable or returned unexpected data
+pub fn fetch_error(error: anyhow::Error) -> anyhow::Error {
+    use rustemon::error::Error;
+
+    let (is_network, is_decode) = match error.downcast_ref::<Error>() {
+        Some(Error::Reqwest(e)) => (e.is_connect() || e.is_timeout(), e.is_decode()),
+        Some(Error::ReqwestMiddleware(e)) => (e.is_connect() || e.is_timeout(), e.is_decode()),
+        _ => (false, false),
+    };
+
+    if is_network {
+        anyhow!("Could not reach PokéAPI and no cached data is available; check your connection.")
+    } else if is_decode {
+        anyhow!("Could not parse the data received from PokéAPI: {error}")
+    } else {
+        error
+    }
+}
+
 #[allow(async_fn_in_trait)]
 pub trait FetchIdentifiers {
     type Identifier;
+    // Set when the identifiers can only be gathered from another resource's entries
+    const DERIVED_FROM: Option<&'static str> = None;
 
     async fn fetch_all_identifiers(client: &RustemonClient) -> Result<Vec<Self::Identifier>>;
 }
@@ -68,7 +92,7 @@ pub trait FetchEntries: FetchIdentifiers {
                 .collect();
             let entry_results: Vec<_> = entry_futures.collect().await;
             for entry in entry_results {
-                entries.push(entry?);
+                entries.push(entry.map_err(fetch_error)?);
             }
         }
 
@@ -89,7 +113,9 @@ pub trait ConvertEntries: FetchEntries {
 #[allow(async_fn_in_trait)]
 pub trait FetchResource: FetchIdentifiers + FetchEntries + ConvertEntries {
     async fn fetch_resource(client: &RustemonClient, db: &Connection) -> Result<Vec<Self::Row>> {
-        let names = Self::fetch_all_identifiers(client).await?;
+        let names = Self::fetch_all_identifiers(client)
+            .await
+            .map_err(fetch_error)?;
         let entries = Self::fetch_all_entries(names, client).await?;
         Ok(Self::convert_to_rows(entries, db))
     }
@@ -157,8 +183,12 @@ impl ConvertEntries for MoveFetcher {
                 move_data.push(MoveRowGroup::MoveChangeRow(change_move));
             }
 
+            let meta = MoveMetaRow::from_move(&move_);
             let move_ = MoveRow::from(move_);
             move_data.push(MoveRowGroup::MoveRow(move_));
+            if let Some(meta) = meta {
+                move_data.push(MoveRowGroup::MoveMetaRow(meta));
+            }
         }
 
         move_data
@@ -269,11 +299,14 @@ impl FetchResource for SpeciesFetcher {}
 pub struct EvolutionFetcher;
 impl FetchIdentifiers for EvolutionFetcher {
     type Identifier = i64;
+    const DERIVED_FROM: Option<&'static str> = Some("species");
 
     async fn fetch_all_identifiers(client: &RustemonClient) -> Result<Vec<i64>> {
         // rustemon::evolution::evolution_chain::get_all_entries() is broken.
         // Retrieve them instead via species resource instead.
-        let names = SpeciesFetcher::fetch_all_identifiers(client).await?;
+        let names = SpeciesFetcher::fetch_all_identifiers(client)
+            .await
+            .map_err(fetch_error)?;
         let species = SpeciesFetcher::fetch_all_entries(names, client).await?;
         let mut evolution_ids = HashSet::new();
 
@@ -364,3 +397,109 @@ impl ConvertEntries for PokemonFetcher {
     }
 }
 impl FetchResource for PokemonFetcher {}
+
+pub struct EncounterFetcher;
+impl FetchIdentifiers for EncounterFetcher {
+    type Identifier = i64;
+
+    async fn fetch_all_identifiers(client: &RustemonClient) -> Result<Vec<i64>> {
+        Ok(rustemon_pokemon::get_all_entries(client)
+            .await?
+            .into_iter()
+            .map(|p| capture_url_id(&p.url).unwrap())
+            .collect::<Vec<i64>>())
+    }
+}
+impl FetchEntries for EncounterFetcher {
+    type Entry = (i64, Vec<LocationAreaEncounter>);
+
+    async fn fetch_entry(
+        identifier: &i64,
+        client: &RustemonClient,
+    ) -> Result<(i64, Vec<LocationAreaEncounter>)> {
+        let encounters = rustemon_pokemon::encounters::get_by_id(*identifier, client).await?;
+        Ok((*identifier, encounters))
+    }
+}
+impl ConvertEntries for EncounterFetcher {
+    type Row = EncounterRow;
+
+    fn convert_to_rows(
+        entries: Vec<(i64, Vec<LocationAreaEncounter>)>,
+        db: &Connection,
+    ) -> Vec<EncounterRow> {
+        let mut encounter_data = vec![];
+        for (pokemon_id, encounters) in entries {
+            for encounter in encounters.iter() {
+                let mut encounter_rows =
+                    Vec::<EncounterRow>::from_change(encounter, pokemon_id, db);
+                encounter_data.append(&mut encounter_rows);
+            }
+        }
+        encounter_data
+    }
+}
+impl FetchResource for EncounterFetcher {}
+
+pub struct MachineFetcher;
+impl FetchIdentifiers for MachineFetcher {
+    type Identifier = i64;
+    const DERIVED_FROM: Option<&'static str> = Some("moves");
+
+    async fn fetch_all_identifiers(client: &RustemonClient) -> Result<Vec<i64>> {
+        // PokéAPI lists machines without names, which rustemon cannot parse.
+        // Retrieve them instead via the move resource.
+        let names = MoveFetcher::fetch_all_identifiers(client)
+            .await
+            .map_err(fetch_error)?;
+        let moves = MoveFetcher::fetch_all_entries(names, client).await?;
+
+        Ok(moves
+            .into_iter()
+            .flat_map(|move_| move_.machines)
+            .map(|detail| capture_url_id(&detail.machine.url).unwrap())
+            .collect())
+    }
+}
+impl FetchEntries for MachineFetcher {
+    type Entry = Machine;
+
+    async fn fetch_entry(identifier: &i64, client: &RustemonClient) -> Result<Machine> {
+        Ok(rustemon_machine::get_by_id(*identifier, client).await?)
+    }
+}
+impl ConvertEntries for MachineFetcher {
+    type Row = MachineRow;
+
+    fn convert_to_rows(entries: Vec<Machine>, _db: &Connection) -> Vec<MachineRow> {
+        entries
+            .into_iter()
+            .map(MachineRow::from)
+            .collect::<Vec<MachineRow>>()
+    }
+}
+impl FetchResource for MachineFetcher {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustemon::client::Environment;
+
+    #[tokio::test]
+    async fn fetch_unreachable_api() {
+        // Nothing listens on port 1, so the request fails without leaving the machine
+        let client = RustemonClientBuilder::default()
+            .with_mode(CacheMode::NoStore)
+            .with_environment(Environment::Custom(String::from("http://127.0.0.1:1/")))
+            .try_build()
+            .unwrap();
+
+        let err = GameFetcher::fetch_all_entries(vec![String::from("red-blue")], &client)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            "Could not reach PokéAPI and no cached data is available; check your connection.",
+            err.to_string()
+        );
+    }
+}
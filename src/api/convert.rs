@@ -1,10 +1,12 @@
 use super::game_to_gen;
 use crate::models::database::{
-    AbilityRow, GameRow, MoveChangeRow, MoveRow, PokemonAbilityRow, PokemonMoveRow, PokemonRow,
-    PokemonTypeChangeRow, SpeciesRow, TypeChangeRow, TypeRow,
+    AbilityRow, EncounterRow, GameRow, MachineRow, MoveChangeRow, MoveMetaRow, MoveRow,
+    PokemonAbilityRow, PokemonMoveRow, PokemonRow, PokemonTypeChangeRow, SpeciesRow, TypeChangeRow,
+    TypeRow,
 };
 use crate::models::{EvolutionMethod, EvolutionStep};
 
+use std::collections::BTreeMap;
 use std::sync::OnceLock;
 
 use anyhow::{anyhow, Result};
@@ -13,15 +15,17 @@ use rusqlite::Connection;
 
 use rustemon::model::evolution::{ChainLink, EvolutionDetail};
 use rustemon::model::games::VersionGroup;
+use rustemon::model::machines::Machine;
 use rustemon::model::moves::{Move, PastMoveStatValues};
 use rustemon::model::pokemon::{
-    Ability, Pokemon, PokemonAbility, PokemonMove, PokemonSpecies, PokemonStat, PokemonType,
-    PokemonTypePast, Type, TypeRelations, TypeRelationsPast,
+    Ability, LocationAreaEncounter, Pokemon, PokemonAbility, PokemonMove, PokemonSpecies,
+    PokemonStat, PokemonType, PokemonTypePast, Type, TypeRelations, TypeRelationsPast,
 };
 use rustemon::model::resource::{NamedApiResource, VerboseEffect};
 
 trait GetEffectEntry {
     fn get_effect(&self) -> Option<String>;
+    fn get_short_effect(&self) -> Option<String>;
 }
 
 impl GetEffectEntry for Vec<VerboseEffect> {
@@ -30,6 +34,12 @@ impl GetEffectEntry for Vec<VerboseEffect> {
             .find(|e| e.language.name == "en")
             .map(|ve| ve.effect.clone())
     }
+
+    fn get_short_effect(&self) -> Option<String> {
+        self.iter()
+            .find(|e| e.language.name == "en")
+            .map(|ve| ve.short_effect.clone())
+    }
 }
 
 impl From<VersionGroup> for GameRow {
@@ -39,15 +49,22 @@ impl From<VersionGroup> for GameRow {
             name,
             order,
             generation,
+            versions,
             ..
         } = value;
         let generation = capture_url_gen(&generation.url).unwrap();
+        let versions = versions
+            .into_iter()
+            .map(|v| v.name)
+            .collect::<Vec<String>>()
+            .join(",");
 
         Self {
             id,
             name,
             order: order as u8,
             generation,
+            versions,
         }
     }
 }
@@ -69,6 +86,7 @@ impl From<Move> for MoveRow {
         } = value;
 
         let effect = effect_entries.get_effect().unwrap_or_default();
+        let short_effect = effect_entries.get_short_effect().unwrap_or_default();
 
         Self {
             id,
@@ -79,11 +97,40 @@ impl From<Move> for MoveRow {
             damage_class: damage_class.name,
             type_: type_.name,
             effect,
+            short_effect,
             effect_chance,
             generation: capture_url_gen(&generation.url).unwrap(),
         }
     }
 }
+// Moves without PokéAPI meta data, e.g. some max moves, get no row
+impl MoveMetaRow {
+    pub fn from_move(value: &Move) -> Option<Self> {
+        let meta = value.meta.as_ref()?;
+        let stat_changes = value
+            .stat_changes
+            .iter()
+            .map(|change| (change.stat.name.clone(), change.change))
+            .collect::<Vec<(String, i64)>>();
+        // e.g. swords-dance targets the user; close-combat hits the target but lowers the user's stats
+        let stat_changes_user =
+            value.target.name.starts_with("user") || meta.category.name == "damage+raise";
+
+        Some(Self {
+            id: value.id,
+            drain: meta.drain,
+            healing: meta.healing,
+            crit_rate: meta.crit_rate,
+            flinch_chance: meta.flinch_chance,
+            stat_chance: meta.stat_chance,
+            min_hits: meta.min_hits,
+            max_hits: meta.max_hits,
+            stat_changes: serde_json::to_string(&stat_changes).unwrap(),
+            stat_changes_user,
+        })
+    }
+}
+
 pub trait FromChange<T> {
     fn from_change(value: T, id: i64, db: &Connection) -> Self;
 }
@@ -201,6 +248,25 @@ impl FromChange<&TypeRelationsPast> for TypeChangeRow {
     }
 }
 
+impl From<Machine> for MachineRow {
+    fn from(value: Machine) -> Self {
+        let Machine {
+            id,
+            item,
+            move_,
+            version_group,
+        } = value;
+        let move_id = capture_url_id(&move_.url).unwrap();
+
+        Self {
+            id,
+            name: item.name,
+            game: version_group.name,
+            move_id,
+        }
+    }
+}
+
 impl From<Ability> for AbilityRow {
     fn from(value: Ability) -> Self {
         let Ability {
@@ -212,11 +278,13 @@ impl From<Ability> for AbilityRow {
         } = value;
         let generation = capture_url_gen(&generation.url).unwrap();
         let effect = effect_entries.get_effect().unwrap_or_default();
+        let short_effect = effect_entries.get_short_effect().unwrap_or_default();
 
         Self {
             id,
             name,
             effect,
+            short_effect,
             generation,
         }
     }
@@ -230,6 +298,8 @@ impl From<PokemonSpecies> for SpeciesRow {
             is_baby,
             is_legendary,
             is_mythical,
+            capture_rate,
+            growth_rate,
             evolution_chain,
             ..
         } = value;
@@ -241,6 +311,8 @@ impl From<PokemonSpecies> for SpeciesRow {
             is_baby,
             is_legendary,
             is_mythical,
+            capture_rate,
+            growth_rate: growth_rate.name,
             evolution_id,
         }
     }
@@ -368,6 +440,49 @@ impl FromChange<&PokemonMove> for Vec<PokemonMoveRow> {
     }
 }
 
+impl FromChange<&LocationAreaEncounter> for Vec<EncounterRow> {
+    fn from_change(value: &LocationAreaEncounter, id: i64, db: &Connection) -> Self {
+        let LocationAreaEncounter {
+            location_area,
+            version_details,
+        } = value;
+
+        let mut encounter_rows = vec![];
+        for vd in version_details {
+            let game = match GameRow::select_by_version(&vd.version.name, db) {
+                Ok(Some(game)) => game.name,
+                _ => continue,
+            };
+
+            // PokéAPI lists an encounter per condition (time, season, etc.);
+            // collapse them into one level range per method.
+            let mut methods: BTreeMap<&str, (i64, i64)> = BTreeMap::new();
+            for encounter in &vd.encounter_details {
+                let levels = methods
+                    .entry(&encounter.method.name)
+                    .or_insert((encounter.min_level, encounter.max_level));
+                levels.0 = levels.0.min(encounter.min_level);
+                levels.1 = levels.1.max(encounter.max_level);
+            }
+
+            for (method, (min_level, max_level)) in methods {
+                encounter_rows.push(EncounterRow {
+                    id: None,
+                    location: location_area.name.clone(),
+                    method: String::from(method),
+                    version: vd.version.name.clone(),
+                    game: game.clone(),
+                    min_level,
+                    max_level,
+                    pokemon_id: id,
+                })
+            }
+        }
+
+        encounter_rows
+    }
+}
+
 impl FromChange<&PokemonTypePast> for PokemonTypeChangeRow {
     fn from_change(value: &PokemonTypePast, id: i64, _db: &Connection) -> Self {
         let PokemonTypePast { generation, types } = value;
@@ -394,6 +509,10 @@ impl From<Pokemon> for PokemonRow {
             species,
             stats,
             types,
+            base_experience,
+            height,
+            weight,
+            sprites,
             ..
         } = value;
 
@@ -419,6 +538,10 @@ impl From<Pokemon> for PokemonRow {
             special_attack,
             special_defense,
             speed,
+            base_experience,
+            height,
+            weight,
+            sprite: sprites.front_default,
             species_id,
         }
     }
@@ -468,3 +591,132 @@ fn capture_url_gen(url: &str) -> Result<u8> {
         Err(anyhow!("Generation not found in resource url"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustemon::model::moves::{MoveMetaData, MoveStatChange};
+    use rustemon::model::pokemon::PokemonSprites;
+
+    fn named<T: Default>(name: &str, url: &str) -> NamedApiResource<T> {
+        let mut resource = NamedApiResource::default();
+        resource.name = String::from(name);
+        resource.url = String::from(url);
+        resource
+    }
+
+    #[test]
+    fn convert_machine() {
+        let machine = Machine {
+            id: 1234,
+            item: named("tm35", "https://pokeapi.co/api/v2/item/339/"),
+            move_: named("flamethrower", "https://pokeapi.co/api/v2/move/53/"),
+            version_group: named("x-y", "https://pokeapi.co/api/v2/version-group/15/"),
+        };
+        let machine_row = MachineRow::from(machine);
+        assert_eq!(1234, machine_row.id);
+        assert_eq!("tm35", machine_row.name);
+        assert_eq!("x-y", machine_row.game);
+        assert_eq!(53, machine_row.move_id);
+    }
+
+    #[test]
+    fn convert_playthrough_details() {
+        let species = PokemonSpecies {
+            id: 25,
+            name: String::from("pikachu"),
+            capture_rate: 190,
+            growth_rate: named("medium", "https://pokeapi.co/api/v2/growth-rate/2/"),
+            evolution_chain: None,
+            ..Default::default()
+        };
+        let species_row = SpeciesRow::from(species);
+        assert_eq!(190, species_row.capture_rate);
+        assert_eq!("medium", species_row.growth_rate);
+
+        let stats = [
+            "hp",
+            "attack",
+            "defense",
+            "special-attack",
+            "special-defense",
+            "speed",
+        ]
+        .into_iter()
+        .map(|stat| PokemonStat {
+            stat: named(stat, ""),
+            effort: 0,
+            base_stat: 50,
+        })
+        .collect();
+        let pokemon = Pokemon {
+            id: 25,
+            name: String::from("pikachu"),
+            base_experience: Some(112),
+            height: 4,
+            weight: 60,
+            species: named("pikachu", "https://pokeapi.co/api/v2/pokemon-species/25/"),
+            types: vec![PokemonType {
+                slot: 1,
+                type_: named("electric", ""),
+            }],
+            stats,
+            sprites: PokemonSprites {
+                front_default: Some(String::from(
+                    "https://raw.githubusercontent.com/PokeAPI/sprites/master/sprites/pokemon/25.png",
+                )),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let pokemon_row = PokemonRow::from(pokemon);
+        assert_eq!(Some(112), pokemon_row.base_experience);
+        assert_eq!((4, 60), (pokemon_row.height, pokemon_row.weight));
+        assert!(pokemon_row.sprite.unwrap().ends_with("/pokemon/25.png"));
+        assert_eq!(25, pokemon_row.species_id);
+    }
+
+    #[test]
+    fn convert_move_meta() {
+        let stat_change = |stat: &str, change: i64| MoveStatChange {
+            change,
+            stat: named(stat, ""),
+        };
+        let close_combat = Move {
+            id: 370,
+            name: String::from("close-combat"),
+            target: named("selected-pokemon", ""),
+            stat_changes: vec![
+                stat_change("defense", -1),
+                stat_change("special-defense", -1),
+            ],
+            meta: Some(MoveMetaData {
+                category: named("damage+raise", ""),
+                stat_chance: 100,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let meta_row = MoveMetaRow::from_move(&close_combat).unwrap();
+        assert_eq!(370, meta_row.id);
+        assert_eq!(
+            r#"[["defense",-1],["special-defense",-1]]"#,
+            meta_row.stat_changes
+        );
+        assert!(meta_row.stat_changes_user);
+
+        let growl = Move {
+            target: named("all-opponents", ""),
+            stat_changes: vec![stat_change("attack", -1)],
+            meta: Some(MoveMetaData {
+                category: named("net-good-stats", ""),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(!MoveMetaRow::from_move(&growl).unwrap().stat_changes_user);
+
+        let no_meta = Move::default();
+        assert!(MoveMetaRow::from_move(&no_meta).is_none());
+    }
+}
@@ -2,25 +2,42 @@ mod commands;
 mod display;
 pub mod utils;
 
+use crate::models::{
+    BattleConditions, FormModifier, Region, Role, Stat, StatBound, Terrain, Weather,
+};
+use crate::resource::config::ConfigKey;
+use crate::resource::database::DatabaseFile;
 use crate::resource::{Config, ConfigBuilder};
 use crate::VERSION;
 use commands::{
-    AbilityCommand, Command, ConfigCommand, CoverageCommand, MatchCommand, MoveCommand,
-    PokemonCommand, ResourceCommand, SetupCommand, TypeCommand,
+    AbilityCommand, Command, ConfigCommand, CoverageCommand, CustomAddCommand, CustomListCommand,
+    CustomRemoveCommand, DataVersionCommand, MatchCommand, MatchFileCommand, MoveCommand,
+    MoveSearchCommand, PokemonAvailabilityCommand, PokemonCommand, PokemonMatrixCommand,
+    PokemonTypeCommand, RandomCommand, ResourceCommand, SearchCommand, SetupCommand, TypeCommand,
+    TypeCoverageCommand, WhereCommand,
 };
+use display::OutputFormat;
+use utils::{color_override, env_var, flag_override, to_ascii, AsciiWriter};
 
-use std::io::stdout;
+use std::io::{stdout, Write};
 use std::path::PathBuf;
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use anyhow::{anyhow, bail, Result};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(author, version = VERSION, about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
+    /// Print when the database was built, by which version, and the games it covers
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    data_version: bool,
     /// Sets the mainline Pokémon game the output will be based on
+    ///
+    /// Custom Pokémon always use the generation set in custom.yaml instead,
+    /// so they can be mixed with Pokémon from this game, e.g. in coverage
     #[clap(long, global = true)]
     game: Option<String>,
     /// Force output to include colors
@@ -29,6 +46,15 @@ struct Cli {
     /// Force output to exclude colors
     #[clap(long, action = clap::ArgAction::SetTrue, global = true)]
     no_color: bool,
+    /// Print minimal output without section headers, e.g. for scripting; pokemon and move only
+    #[clap(short, long, action = clap::ArgAction::SetTrue, global = true)]
+    quiet: bool,
+    /// Replace non-ASCII characters such as the é in Pokémon, for terminals that mangle them
+    #[clap(long, action = clap::ArgAction::SetTrue, global = true)]
+    ascii: bool,
+    /// Applies a named profile from the config file over the base config
+    #[clap(long, global = true)]
+    profile: Option<String>,
     /// Sets a specific file as the program's config path
     #[clap(long, global = true)]
     config: Option<PathBuf>,
@@ -43,54 +69,346 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Retrieve and set up program data. Run this before using the program
-    Setup,
+    Setup {
+        /// Also retrieve where Pokémon can be encountered; this takes considerably longer
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        with_encounters: bool,
+        /// Also retrieve which TM, HM, or TR teaches each move in every game
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        with_machines: bool,
+        /// Only list how many entries each resource would retrieve, without building the database
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+    },
     /// Prints general data about a Pokémon
     Pokemon {
         /// Name of the Pokémon
-        pokemon: String,
+        #[arg(required_unless_present = "all_of_type")]
+        pokemon: Option<String>,
         /// Display all move data the Pokémon is capable of learning
-        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        #[arg(short, long, action = clap::ArgAction::SetTrue, overrides_with = "no_moves")]
         moves: bool,
+        /// Hide moves even when the pokemon.moves config is set
+        #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "moves")]
+        no_moves: bool,
         /// Display the Pokémon evolutionary line
-        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        #[arg(short, long, action = clap::ArgAction::SetTrue, overrides_with = "no_evolution")]
         evolution: bool,
+        /// Hide the evolutionary line even when the pokemon.evolution config is set
+        #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "evolution")]
+        no_evolution: bool,
+        /// Only display the species names of the evolutionary line
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        no_evolution_methods: bool,
+        /// Label each species of the evolutionary line with its stage, e.g. basic or stage 1
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        stages: bool,
+        /// Display other forms of the Pokémon's species
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        forms: bool,
+        /// Only display level-up moves learned by this level
+        #[arg(long, value_name = "LEVEL")]
+        up_to_level: Option<i64>,
+        /// Include TM moves alongside the level cap
+        #[arg(long, requires = "up_to_level", action = clap::ArgAction::SetTrue)]
+        include_tm: bool,
+        /// Output format; markdown renders stats and moves as tables, dot renders the evolution line as a Graphviz graph, json renders the asset URLs
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Show this stat first and the others from highest to lowest: hp, atk, def, satk, sdef, spd, or total
+        #[arg(long, value_name = "STAT")]
+        sort: Option<Stat>,
+        /// Display the catch rate, base experience, growth rate, height, and weight
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        details: bool,
+        /// Rate each move's effectiveness against this defending Pokémon
+        #[arg(long, value_name = "POKEMON")]
+        vs: Option<String>,
+        /// Show the TM, HM, or TR number of machine moves; requires `dunspars setup --with-machines`
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        tm: bool,
+        /// Explain whether the Pokémon learns this move, and in which generations when it does not
+        #[arg(long, value_name = "MOVE")]
+        explain: Option<String>,
+        /// Suggest a 252/252/4 EV spread and nature for a physical, special, or bulky role
+        #[arg(long, value_enum, value_name = "ROLE")]
+        ev_suggest: Option<Role>,
+        /// Display the Pokémon's mega form; X or Y picks one when it has two
+        #[arg(
+            long,
+            num_args = 0..=1,
+            value_name = "X|Y",
+            value_parser = ["x", "y"],
+            ignore_case = true,
+            conflicts_with_all = ["availability", "all_of_type"]
+        )]
+        mega: Option<Option<String>>,
+        /// Display the Pokémon's gigantamax form
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["mega", "availability", "all_of_type"]
+        )]
+        gmax: bool,
+        /// Display the Pokémon's regional form, e.g. meowth with galar for meowth-galar
+        #[arg(
+            long,
+            value_enum,
+            conflicts_with_all = ["mega", "gmax", "availability", "all_of_type"]
+        )]
+        region: Option<Region>,
+        /// Print a single line of the name, types, base stats, and base stat total
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["availability", "all_of_type", "format"]
+        )]
+        compact: bool,
+        /// Prints the PokéAPI sprite URL of the Pokémon for wikis and bots
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = [
+                "moves", "evolution", "forms", "details", "compact", "availability", "matrix",
+                "all_of_type"
+            ]
+        )]
+        assets: bool,
+        /// Draw the defensive type chart as a bar per type instead of groups
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        bars: bool,
+        /// Lists the generations and games in which the Pokémon is present
+        #[arg(
+            short,
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["moves", "evolution", "forms", "sort", "details"]
+        )]
+        availability: bool,
+        /// Prints the Pokémon's typing and base stat total in every generation it is present
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = [
+                "moves", "evolution", "forms", "sort", "details", "mega", "gmax", "region",
+                "availability", "compact", "all_of_type"
+            ]
+        )]
+        matrix: bool,
+        /// Lists every Pokémon of this type in the game, sorted by base stat total
+        #[arg(
+            long,
+            value_name = "TYPE",
+            conflicts_with_all = ["pokemon", "moves", "evolution", "forms", "sort", "details"]
+        )]
+        all_of_type: Option<String>,
+        /// Prints only this page of Pokémon, starting from 1
+        #[arg(short, long, requires = "all_of_type")]
+        page: Option<usize>,
+        /// Number of Pokémon per page
+        #[arg(long, default_value_t = 50, requires = "page")]
+        page_size: usize,
+    },
+    /// Prints a random Pokémon available in the game
+    Random {
+        /// Only pick Pokémon of this type
+        #[arg(short, long = "type", value_name = "TYPE")]
+        type_: Option<String>,
+        /// Whether legendary and mythical Pokémon may be picked
+        #[arg(long, default_value_t = true, action = clap::ArgAction::Set)]
+        legendary: bool,
+        /// Seeds the pick so that it can be reproduced
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Lists the Pokémon in the configured game whose base stats are within bounds
+    Search {
+        /// Minimum base stat, e.g. spd=100 or total=500; may be repeated
+        #[arg(long, value_name = "STAT=N")]
+        min: Vec<StatBound>,
+        /// Maximum base stat, e.g. hp=50; may be repeated
+        #[arg(long, value_name = "STAT=N")]
+        max: Vec<StatBound>,
+        /// Print each Pokémon's types and base stats on its line
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        compact: bool,
+        /// Prints only this page of Pokémon, starting from 1
+        #[arg(short, long)]
+        page: Option<usize>,
+        /// Number of Pokémon per page
+        #[arg(long, default_value_t = 50, requires = "page")]
+        page_size: usize,
+    },
+    /// Prints where a Pokémon can be encountered in the game
+    Where {
+        /// Name of the Pokémon
+        pokemon: String,
     },
     /// Prints matchup data between Pokémon
     Match {
-        /// Names of the defending Pokémon; max 6
-        #[arg(required = true, num_args = 1..=6)]
-        defenders: Vec<String>,
-        /// Name of the attacking Pokémon
-        attacker: String,
+        /// Names of the defending Pokémon; max 6. The last Pokémon is the attacker unless --attackers is set
+        #[arg(required_unless_present = "from", num_args = 1..=7, value_name = "POKEMON")]
+        pokemon: Vec<String>,
+        /// Comma-separated attacking Pokémon; every positional Pokémon is then a defender
+        #[arg(short, long, value_delimiter = ',', conflicts_with = "from")]
+        attackers: Vec<String>,
+        /// Comma-separated types of a hypothetical attacker, e.g. fire,flying; its STAB types are rated instead of moves
+        #[arg(
+            long,
+            value_delimiter = ',',
+            value_name = "TYPE",
+            conflicts_with_all = ["attackers", "from"]
+        )]
+        attacker_type: Vec<String>,
+        /// Reads matchups from a file, one per line
+        ///
+        /// Each line is formatted as `defender1,defender2 attacker` with up to 6 defenders.
+        /// Blank lines and lines starting with `#` are ignored.
+        #[arg(short, long, conflicts_with = "pokemon")]
+        from: Option<PathBuf>,
         /// Display only moves that match the user's type
-        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        #[arg(short, long, action = clap::ArgAction::SetTrue, overrides_with = "no_stab_only")]
         stab_only: bool,
+        /// Display every move even when the match.stab_only config is set
+        #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "stab_only")]
+        no_stab_only: bool,
         /// Display verbose output
-        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        #[arg(short, long, action = clap::ArgAction::SetTrue, overrides_with = "no_verbose")]
         verbose: bool,
+        /// Display regular output even when the match.verbose config is set
+        #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "verbose")]
+        no_verbose: bool,
+        /// Order defenders by how threatened the attacker is, most threatening first
+        ///
+        /// Each of the defender's super effective moves against the attacker scores 1,
+        /// and each quadruple effective move scores 2.
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        sort: bool,
+        /// Comma-separated moves to leave out, e.g. ones a Pokémon will not realistically run
+        #[arg(long, value_delimiter = ',', value_name = "MOVE")]
+        exclude: Vec<String>,
+        /// Active weather; rain and sun scale water and fire moves, sand and snow bolster rock and ice defenders
+        #[arg(long, value_enum)]
+        weather: Option<Weather>,
+        /// Active terrain; boosts matching moves by 1.3, except misty which halves dragon moves
+        #[arg(long, value_enum)]
+        terrain: Option<Terrain>,
     },
     /// Prints type coverage based on the provided Pokémon
     Coverage {
         /// Names of Pokémon; max 6
         #[arg(required = true, num_args = 1..=6)]
         pokemon: Vec<String>,
+        /// Sort types by the number of Pokémon covering them, fewest first
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        sort: bool,
+        /// Display only types covered by fewer than this many Pokémon
+        #[arg(short, long)]
+        min: Option<usize>,
+        /// Also list neutral coverage, dimmed after the advantageous coverage
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        include_overlap: bool,
+        /// Condense offense coverage to the number of Pokémon covering each type and whether any has STAB
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "include_overlap")]
+        summary: bool,
+        /// Comma-separated moves to leave out of custom Pokémon's and --top-moves offense coverage
+        #[arg(long, value_delimiter = ',', value_name = "MOVE")]
+        exclude: Vec<String>,
+        /// Approximate a moveset for non-custom Pokémon from their strongest learnable move of each type
+        #[arg(long, value_name = "COUNT")]
+        top_moves: Option<usize>,
+        /// Check whether two Pokémon resist each other's weaknesses and hit what resists each other's STAB
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["sort", "min", "include_overlap", "summary", "exclude", "top_moves"]
+        )]
+        synergy: bool,
     },
     /// Prints type weakness and coverage about a Pokémon type or a combination of two
     Type {
         /// Name of a type
-        primary_type: String,
+        #[arg(required_unless_present = "coverage")]
+        primary_type: Option<String>,
         /// Name of a secondary type. Optional
         secondary_type: Option<String>,
+        /// Only print the multipliers against these types, e.g. --vs water or --vs fire,flying
+        #[arg(long, value_delimiter = ',', num_args = 1)]
+        vs: Vec<String>,
+        /// Lists the single and dual typings that none of these attacking types hit super effectively
+        #[arg(
+            long,
+            value_delimiter = ',',
+            num_args = 1..,
+            value_name = "TYPE",
+            conflicts_with_all = ["primary_type", "vs"]
+        )]
+        coverage: Vec<String>,
+        /// Only print the types hit super effectively, on one line
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with_all = ["vs", "coverage"])]
+        se_only: bool,
+        /// Draw a bar per type whose length and color follow its multiplier
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with_all = ["vs", "coverage", "se_only"])]
+        bars: bool,
     },
     /// Prints data about a Pokémon move
     Move {
         /// Name of the move
-        move_: String,
+        #[arg(required_unless_present = "search")]
+        move_: Option<String>,
+        /// Display the full effect description
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        verbose: bool,
+        /// List the Pokémon that can learn the move in the configured generation
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        learners: bool,
+        /// Only list learners by this method, e.g. level-up, machine, egg, tutor
+        #[arg(long, requires = "learners")]
+        method: Option<String>,
+        /// Print only the effect description
+        #[arg(short, long, action = clap::ArgAction::SetTrue, conflicts_with = "learners")]
+        effect_only: bool,
+        /// Display the move's power, accuracy, pp, and type across generations
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "effect_only")]
+        history: bool,
+        /// Limit the history to a range of generations, e.g. 3-6, 3-, or -6
+        #[arg(long, requires = "history")]
+        gens: Option<String>,
+        /// Use this effect chance instead of the stored one, e.g. 20 for a move boosted by serene grace
+        #[arg(
+            long,
+            value_name = "PERCENT",
+            value_parser = clap::value_parser!(i64).range(0..=100),
+            conflicts_with = "search"
+        )]
+        effect_chance: Option<i64>,
+        /// List the moves whose effect mentions a keyword instead, e.g. paralyze or priority
+        #[arg(
+            short,
+            long,
+            conflicts_with_all = ["move_", "learners", "effect_only", "history"]
+        )]
+        search: Option<String>,
+        /// Prints only this page of moves, starting from 1
+        #[arg(short, long, requires = "search")]
+        page: Option<usize>,
+        /// Number of moves per page
+        #[arg(long, default_value_t = 50, requires = "page")]
+        page_size: usize,
     },
     /// Prints data about a Pokémon ability
     Ability {
         /// Name of the ability
         ability: String,
+        /// Display the full effect description
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        verbose: bool,
+        /// Display the Pokémon that can have the ability; (h) marks it as hidden
+        #[arg(short, long, visible_alias = "dex", action = clap::ArgAction::SetTrue)]
+        pokemon: bool,
+        /// Only display Pokémon that have the ability as a hidden ability
+        #[arg(long, requires = "pokemon", action = clap::ArgAction::SetTrue)]
+        hidden: bool,
     },
     /// Prints all possible names from a Resource such as Pokémon, Moves, etc
     Resource {
@@ -100,6 +418,21 @@ enum Commands {
         /// Value to be printed in between values. Defaults to newline
         #[arg(short, long)]
         delimiter: Option<String>,
+        /// Prints only this page of values, starting from 1
+        #[arg(short, long)]
+        page: Option<usize>,
+        /// Number of values per page
+        #[arg(long, default_value_t = 50, requires = "page")]
+        page_size: usize,
+        /// Prints values in aligned columns fitted to the terminal width
+        #[arg(short, long, action = clap::ArgAction::SetTrue, conflicts_with = "delimiter")]
+        columns: bool,
+        /// Orders Pokémon by national dex, name, or base stat total. Defaults to database order
+        #[arg(short, long, value_enum)]
+        order: Option<ResourceOrder>,
+        /// Only lists values present in the configured game's generation
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        current_gen: bool,
     },
     /// Dunspars configuration
     Config {
@@ -110,9 +443,123 @@ enum Commands {
         /// Deletes the target configuration
         #[arg(short, long, action = clap::ArgAction::SetTrue)]
         unset: bool,
+        /// Prints every recognized configuration with its current and default values
+        #[arg(short, long, action = clap::ArgAction::SetTrue, conflicts_with = "key")]
+        all: bool,
+    },
+    /// Adds, removes, or lists the custom Pokémon of the custom resources file
+    Custom {
+        #[command(subcommand)]
+        action: CustomAction,
     },
 }
 
+impl Commands {
+    fn requires_database(&self) -> bool {
+        !matches!(
+            self,
+            Commands::Setup { .. }
+                | Commands::Config { .. }
+                | Commands::Custom {
+                    action: CustomAction::Remove { .. } | CustomAction::List
+                }
+        )
+    }
+
+    // Commands with a minimal output for --quiet; the others reject it rather than ignore it
+    fn supports_quiet(&self) -> bool {
+        matches!(
+            self,
+            Commands::Pokemon {
+                availability: false,
+                matrix: false,
+                all_of_type: None,
+                ..
+            } | Commands::Move { search: None, .. }
+        )
+    }
+
+    // First option whose output --quiet would leave out, so it can be rejected; options that
+    // require another one, such as --method, are covered by it
+    fn quiet_conflict(&self) -> Option<&'static str> {
+        let conflicts = match self {
+            Commands::Pokemon {
+                moves,
+                evolution,
+                stages,
+                forms,
+                format,
+                sort,
+                details,
+                explain,
+                ev_suggest,
+                bars,
+                ..
+            } => vec![
+                ("--moves", *moves),
+                ("--evolution", *evolution),
+                ("--stages", *stages),
+                ("--forms", *forms),
+                ("--format", *format != OutputFormat::Text),
+                ("--sort", sort.is_some()),
+                ("--details", *details),
+                ("--explain", explain.is_some()),
+                ("--ev-suggest", ev_suggest.is_some()),
+                ("--bars", *bars),
+            ],
+            Commands::Move {
+                verbose,
+                learners,
+                history,
+                effect_chance,
+                ..
+            } => vec![
+                ("--verbose", *verbose),
+                ("--learners", *learners),
+                ("--history", *history),
+                ("--effect-chance", effect_chance.is_some()),
+            ],
+            _ => vec![],
+        };
+        conflicts
+            .into_iter()
+            .find_map(|(flag, used)| used.then_some(flag))
+    }
+}
+
+#[derive(Subcommand)]
+enum CustomAction {
+    /// Adds a custom Pokémon after checking its base Pokémon, moves, and types
+    Add {
+        /// Name to look the custom Pokémon up by
+        nickname: String,
+        /// Name of the Pokémon it is based on
+        #[arg(long)]
+        base: String,
+        /// A move the custom Pokémon knows; repeat for each move
+        #[arg(long = "move", value_name = "MOVE")]
+        moves: Vec<String>,
+        /// Types replacing the base Pokémon's, e.g. fire,flying
+        #[arg(
+            long = "type",
+            value_delimiter = ',',
+            num_args = 1,
+            value_name = "TYPE"
+        )]
+        types: Vec<String>,
+        /// Generation of the custom Pokémon. Defaults to the configured game's
+        #[arg(long)]
+        generation: Option<u8>,
+    },
+    /// Removes a custom Pokémon
+    Remove {
+        /// Nickname of the custom Pokémon
+        nickname: String,
+    },
+    /// Lists the custom Pokémon with their base Pokémon and generation
+    List,
+}
+
 #[derive(Clone, clap::ValueEnum)]
 enum ResourceArgs {
     Pokemon,
@@ -120,22 +567,54 @@ enum ResourceArgs {
     Abilities,
     Games,
     Types,
+    /// The base species of each evolution chain
+    Evolutions,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ResourceOrder {
+    /// By species, with forms after their base Pokémon
+    Dex,
+    Name,
+    /// By base stat total, highest first
+    Bst,
 }
 
 pub async fn run() -> Result<i32> {
     let cli = Cli::parse();
-    let mut builder = ConfigBuilder::from_file(cli.config)?;
+    if !cli.ascii {
+        return run_cli(cli, &mut stdout().lock()).await;
+    }
+
+    let mut output = AsciiWriter::new(stdout().lock());
+    let status_code = run_cli(cli, &mut output)
+        .await
+        .map_err(|e| anyhow!(to_ascii(&e.to_string())));
+    output.flush()?;
+    status_code
+}
+
+async fn run_cli(cli: Cli, output: &mut impl Write) -> Result<i32> {
+    let mut builder = ConfigBuilder::from_file(cli.config, cli.profile.as_deref(), env_var)?;
 
     if let Some(game) = &cli.game {
         builder = builder.game(game.to_owned());
     }
 
-    if cli.color {
-        builder = builder.color_enabled(true);
+    let color_flag = if cli.color {
+        Some(true)
     } else if cli.no_color {
-        builder = builder.color_enabled(false);
+        Some(false)
+    } else {
+        None
+    };
+    if let Some(color) = color_override(color_flag, builder.get_color_enabled(), env_var) {
+        builder = builder.color_enabled(color);
     }
 
+    // Quiet only trims the output; colors are still governed by --color and --no-color
+    builder = builder.quiet(cli.quiet);
+
     if let Some(path) = cli.database {
         builder = builder.db_path(path);
     }
@@ -144,13 +623,33 @@ pub async fn run() -> Result<i32> {
     }
 
     let config = builder.build()?;
-    let status_code = run_command(cli.command, config).await?;
+    let status_code = match cli.command {
+        Some(_) if cli.data_version => bail!("--data-version cannot be used with a subcommand"),
+        Some(command) if cli.quiet && !command.supports_quiet() => {
+            bail!("--quiet is only supported by pokemon and move, without listing options")
+        }
+        Some(command) if cli.quiet && command.quiet_conflict().is_some() => {
+            bail!(
+                "--quiet cannot be used with {}",
+                command.quiet_conflict().unwrap()
+            )
+        }
+        Some(command) => {
+            // Fail before any output rather than partway through a command
+            if command.requires_database() {
+                DatabaseFile::from_config(&config).connect()?;
+            }
+            run_command(command, config, output).await?
+        }
+        None if cli.data_version => DataVersionCommand.run(config, output).await?,
+        None => Cli::command()
+            .error(ErrorKind::MissingSubcommand, "a subcommand is required")
+            .exit(),
+    };
     Ok(status_code)
 }
 
-async fn run_command(commands: Commands, config: Config) -> Result<i32> {
-    let mut output = stdout().lock();
-
+async fn run_command(commands: Commands, config: Config, output: &mut impl Write) -> Result<i32> {
     // Performing dynamic dispatch with async functions may produce
     // obscure error messages which make it hard to debug.
     // Related issues:
@@ -158,71 +657,537 @@ async fn run_command(commands: Commands, config: Config) -> Result<i32> {
     // https://github.com/rust-lang/rust/issues/78649
     // https://github.com/rust-lang/rust/issues/119727
     match commands {
-        Commands::Setup => {
-            let cmd = SetupCommand;
-            cmd.run(config, &mut output).await
+        Commands::Setup {
+            with_encounters,
+            with_machines,
+            dry_run,
+        } => {
+            let cmd = SetupCommand {
+                with_encounters,
+                with_machines,
+                dry_run,
+            };
+            cmd.run(config, output).await
         }
         Commands::Pokemon {
             pokemon,
             moves,
+            no_moves,
             evolution,
+            no_evolution,
+            no_evolution_methods,
+            stages,
+            forms,
+            up_to_level,
+            include_tm,
+            format,
+            sort,
+            details,
+            vs,
+            tm,
+            explain,
+            ev_suggest,
+            mega,
+            gmax,
+            region,
+            compact,
+            assets,
+            bars,
+            availability,
+            matrix,
+            all_of_type,
+            page,
+            page_size,
         } => {
+            if let Some(type_) = all_of_type {
+                if format != OutputFormat::Text {
+                    bail!("--all-of-type only supports --format text");
+                }
+                let cmd = PokemonTypeCommand {
+                    type_,
+                    page,
+                    page_size,
+                };
+                return cmd.run(config, output).await;
+            }
+
+            let name = pokemon.expect("clap requires a name without --all-of-type");
+            let moves = flag_override(
+                moves,
+                no_moves,
+                config.flag_default(ConfigKey::PokemonMoves),
+            );
+            let evolution = flag_override(
+                evolution,
+                no_evolution,
+                config.flag_default(ConfigKey::PokemonEvolution),
+            );
+            // Checked here rather than by clap so that the config defaults count
+            let needs_moves = [
+                ("--up-to-level", up_to_level.is_some()),
+                ("--vs", vs.is_some()),
+                ("--tm", tm),
+                ("--explain", explain.is_some()),
+            ];
+            if let Some((flag, _)) = needs_moves.iter().find(|(_, set)| *set && !moves) {
+                bail!("{flag} requires --moves");
+            }
+            let needs_evolution = [
+                ("--no-evolution-methods", no_evolution_methods),
+                ("--stages", stages),
+            ];
+            if let Some((flag, _)) = needs_evolution.iter().find(|(_, set)| *set && !evolution) {
+                bail!("{flag} requires --evolution");
+            }
+            if availability {
+                let cmd = PokemonAvailabilityCommand { name };
+                return cmd.run(config, output).await;
+            }
+            if matrix {
+                let cmd = PokemonMatrixCommand { name };
+                return cmd.run(config, output).await;
+            }
+
+            match format {
+                OutputFormat::Markdown if evolution || forms => {
+                    bail!("--format markdown does not support --evolution or --forms")
+                }
+                OutputFormat::Dot if !evolution => bail!("--format dot requires --evolution"),
+                OutputFormat::Json if !assets => bail!("--format json requires --assets"),
+                OutputFormat::Markdown | OutputFormat::Dot if assets => {
+                    bail!("--assets only supports --format text or json")
+                }
+                _ => {}
+            }
+            if ev_suggest.is_some() && format != OutputFormat::Text {
+                bail!("--ev-suggest only supports --format text");
+            }
+
             let cmd = PokemonCommand {
-                name: pokemon,
+                name,
                 moves,
                 evolution,
+                evolution_methods: !no_evolution_methods,
+                evolution_stages: stages,
+                forms,
+                up_to_level,
+                include_tm,
+                format,
+                sort,
+                details,
+                vs,
+                tm,
+                ev_suggest,
+                form: match (mega, gmax, region) {
+                    (Some(variant), _, _) => Some(FormModifier::Mega(variant)),
+                    (None, true, _) => Some(FormModifier::Gmax),
+                    (None, false, Some(region)) => Some(FormModifier::Region(region)),
+                    (None, false, None) => None,
+                },
+                compact,
+                assets,
+                bars,
+                explain,
             };
-            cmd.run(config, &mut output).await
+            cmd.run(config, output).await
         }
         Commands::Type {
             primary_type,
             secondary_type,
+            vs,
+            coverage,
+            se_only,
+            bars,
         } => {
+            if !coverage.is_empty() {
+                let cmd = TypeCoverageCommand { types: coverage };
+                return cmd.run(config, output).await;
+            }
+
             let cmd = TypeCommand {
-                primary_type,
+                primary_type: primary_type.expect("clap requires a type without --coverage"),
                 secondary_type,
+                vs,
+                se_only,
+                bars,
             };
-            cmd.run(config, &mut output).await
+            cmd.run(config, output).await
         }
-        Commands::Move { move_ } => {
-            let cmd = MoveCommand { name: move_ };
-            cmd.run(config, &mut output).await
+        Commands::Move {
+            move_,
+            verbose,
+            learners,
+            method,
+            effect_only,
+            history,
+            gens,
+            effect_chance,
+            search,
+            page,
+            page_size,
+        } => {
+            if let Some(keyword) = search {
+                let cmd = MoveSearchCommand {
+                    keyword,
+                    page,
+                    page_size,
+                };
+                return cmd.run(config, output).await;
+            }
+
+            let cmd = MoveCommand {
+                name: move_.expect("clap requires a name without --search"),
+                verbose,
+                learners,
+                learn_method: method,
+                effect_only,
+                history,
+                generations: gens,
+                effect_chance,
+            };
+            cmd.run(config, output).await
         }
-        Commands::Ability { ability } => {
-            let cmd = AbilityCommand { name: ability };
-            cmd.run(config, &mut output).await
+        Commands::Ability {
+            ability,
+            verbose,
+            pokemon,
+            hidden,
+        } => {
+            let cmd = AbilityCommand {
+                name: ability,
+                verbose,
+                pokemon,
+                hidden_only: hidden,
+            };
+            cmd.run(config, output).await
+        }
+        Commands::Search {
+            min,
+            max,
+            compact,
+            page,
+            page_size,
+        } => {
+            if min.is_empty() && max.is_empty() {
+                bail!("At least one --min or --max stat is required");
+            }
+
+            let cmd = SearchCommand {
+                min,
+                max,
+                compact,
+                page,
+                page_size,
+            };
+            cmd.run(config, output).await
+        }
+        Commands::Random {
+            type_,
+            legendary,
+            seed,
+        } => {
+            let cmd = RandomCommand {
+                type_,
+                legendary,
+                seed,
+            };
+            cmd.run(config, output).await
+        }
+        Commands::Where { pokemon } => {
+            let cmd = WhereCommand { name: pokemon };
+            cmd.run(config, output).await
         }
         Commands::Match {
-            defenders,
-            attacker,
+            mut pokemon,
+            attackers,
+            attacker_type,
+            from,
             stab_only,
+            no_stab_only,
             verbose,
+            no_verbose,
+            sort,
+            exclude,
+            weather,
+            terrain,
         } => {
-            let cmd = MatchCommand {
-                defender_names: defenders,
-                attacker_name: attacker,
+            let conditions = BattleConditions { weather, terrain };
+            let stab_only = flag_override(
                 stab_only,
+                no_stab_only,
+                config.flag_default(ConfigKey::MatchStabOnly),
+            );
+            let verbose = flag_override(
                 verbose,
-            };
-            cmd.run(config, &mut output).await
+                no_verbose,
+                config.flag_default(ConfigKey::MatchVerbose),
+            );
+            if let Some(path) = from {
+                let cmd = MatchFileCommand {
+                    path,
+                    stab_only,
+                    verbose,
+                    sort,
+                    conditions,
+                    exclude,
+                };
+                cmd.run(config, output).await
+            } else {
+                let attackers = if !attacker_type.is_empty() {
+                    if pokemon.len() > 6 {
+                        bail!("At most 6 defenders may be provided");
+                    }
+                    vec![]
+                } else if attackers.is_empty() {
+                    if pokemon.len() < 2 {
+                        bail!("At least one defender and an attacker are required");
+                    }
+                    vec![pokemon.pop().unwrap()]
+                } else if pokemon.len() > 6 {
+                    bail!("At most 6 defenders may be provided");
+                } else {
+                    attackers
+                };
+
+                let cmd = MatchCommand {
+                    defender_names: pokemon,
+                    attacker_names: attackers,
+                    attacker_types: attacker_type,
+                    stab_only,
+                    verbose,
+                    sort,
+                    conditions,
+                    exclude,
+                };
+                cmd.run(config, output).await
+            }
         }
-        Commands::Coverage { pokemon } => {
-            let cmd = CoverageCommand { names: pokemon };
-            cmd.run(config, &mut output).await
+        Commands::Coverage {
+            pokemon,
+            sort,
+            min,
+            include_overlap,
+            summary,
+            exclude,
+            top_moves,
+            synergy,
+        } => {
+            let cmd = CoverageCommand {
+                names: pokemon,
+                sort,
+                min,
+                include_overlap,
+                summary,
+                exclude,
+                synergy,
+                top_moves,
+            };
+            cmd.run(config, output).await
         }
         Commands::Resource {
             resource,
             delimiter,
+            page,
+            page_size,
+            columns,
+            order,
+            current_gen,
         } => {
+            if order.is_some() && !matches!(resource, ResourceArgs::Pokemon) {
+                bail!("--order is only supported for pokemon");
+            }
+
             let cmd = ResourceCommand {
                 resource,
                 delimiter,
+                page,
+                page_size,
+                columns,
+                order,
+                current_gen,
             };
-            cmd.run(config, &mut output).await
+            cmd.run(config, output).await
         }
-        Commands::Config { key, value, unset } => {
-            let cmd = ConfigCommand { key, value, unset };
-            cmd.run(config, &mut output).await
+        Commands::Config {
+            key,
+            value,
+            unset,
+            all,
+        } => {
+            let cmd = ConfigCommand {
+                key,
+                value,
+                unset,
+                all,
+            };
+            cmd.run(config, output).await
+        }
+        Commands::Custom { action } => match action {
+            CustomAction::Add {
+                nickname,
+                base,
+                moves,
+                types,
+                generation,
+            } => {
+                let cmd = CustomAddCommand {
+                    nickname,
+                    base,
+                    moves,
+                    types,
+                    generation,
+                };
+                cmd.run(config, output).await
+            }
+            CustomAction::Remove { nickname } => {
+                let cmd = CustomRemoveCommand { nickname };
+                cmd.run(config, output).await
+            }
+            CustomAction::List => CustomListCommand.run(config, output).await,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env::current_dir;
+
+    fn config(moves: bool) -> Config {
+        let mut custom_path = current_dir().unwrap();
+        custom_path.push("configs/custom.yaml");
+        let mut type_chart_path = std::env::temp_dir();
+        type_chart_path.push("dunspars_missing_type_chart.yaml");
+
+        ConfigBuilder::default()
+            .game(String::from("scarlet-violet"))
+            .color_enabled(false)
+            .custom_path(custom_path)
+            .type_chart_path(type_chart_path)
+            .flag_default(ConfigKey::PokemonMoves, moves)
+            .build()
+            .unwrap()
+    }
+
+    fn parse(args: &[&str]) -> Commands {
+        let cli = Cli::try_parse_from([&["dunspars"], args].concat()).unwrap();
+        cli.command.unwrap()
+    }
+
+    #[test]
+    fn quiet_support() {
+        assert!(parse(&["pokemon", "pikachu"]).supports_quiet());
+        assert!(parse(&["move", "surf"]).supports_quiet());
+        assert!(!parse(&["move", "--search", "burn"]).supports_quiet());
+        assert!(!parse(&["pokemon", "--all-of-type", "fire"]).supports_quiet());
+        assert!(!parse(&["match", "golem", "lapras"]).supports_quiet());
+        assert!(!parse(&["type", "fire"]).supports_quiet());
+    }
+
+    #[test]
+    fn quiet_conflicts() {
+        assert_eq!(None, parse(&["pokemon", "pikachu"]).quiet_conflict());
+        assert_eq!(None, parse(&["move", "surf"]).quiet_conflict());
+
+        let pokemon = [
+            (vec!["--moves"], "--moves"),
+            (vec!["--evolution"], "--evolution"),
+            (vec!["--evolution", "--stages"], "--evolution"),
+            (vec!["--forms"], "--forms"),
+            (vec!["--format", "markdown"], "--format"),
+            (vec!["--format", "json"], "--format"),
+            (vec!["--sort", "atk"], "--sort"),
+            (vec!["--details"], "--details"),
+            (vec!["--explain", "thunderbolt"], "--explain"),
+            (vec!["--ev-suggest", "special"], "--ev-suggest"),
+            (vec!["--bars"], "--bars"),
+        ];
+        for (args, flag) in pokemon {
+            let command = parse(&[&["pokemon", "pikachu"], args.as_slice()].concat());
+            assert_eq!(Some(flag), command.quiet_conflict());
+        }
+
+        let move_ = [
+            (vec!["--verbose"], "--verbose"),
+            (vec!["--learners"], "--learners"),
+            (vec!["--learners", "--method", "machine"], "--learners"),
+            (vec!["--history"], "--history"),
+            (vec!["--history", "--gens", "1-4"], "--history"),
+            (vec!["--effect-chance", "50"], "--effect-chance"),
+        ];
+        for (args, flag) in move_ {
+            let command = parse(&[&["move", "surf"], args.as_slice()].concat());
+            assert_eq!(Some(flag), command.quiet_conflict());
+        }
+    }
+
+    #[tokio::test]
+    async fn run_cli_rejects_quiet_conflicts() {
+        // A missing config file reads as empty, so a user's own config does not matter
+        let mut config_path = std::env::temp_dir();
+        config_path.push("dunspars_missing_config.yaml");
+        let config_path = config_path.display().to_string();
+
+        let commands = [
+            (vec!["pokemon", "pikachu", "--moves"], "--moves"),
+            (vec!["pokemon", "pikachu", "--format", "dot"], "--format"),
+            (vec!["move", "surf", "--learners"], "--learners"),
+            (
+                vec!["move", "surf", "--effect-chance", "20"],
+                "--effect-chance",
+            ),
+        ];
+        for (args, flag) in commands {
+            let args = [
+                &["dunspars", "--quiet", "--config", &config_path],
+                args.as_slice(),
+            ]
+            .concat();
+            let cli = Cli::try_parse_from(args).unwrap();
+            let mut output = vec![];
+            let err = run_cli(cli, &mut output).await.unwrap_err();
+            assert_eq!(
+                format!("--quiet cannot be used with {flag}"),
+                err.to_string()
+            );
+            assert!(output.is_empty());
         }
     }
+
+    #[tokio::test]
+    async fn move_flags_need_moves() {
+        let mut output = vec![];
+        let err = run_command(
+            parse(&["pokemon", "pikachu", "--up-to-level", "10"]),
+            config(false),
+            &mut output,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!("--up-to-level requires --moves", err.to_string());
+
+        let err = run_command(
+            parse(&["pokemon", "eevee", "--stages"]),
+            config(true),
+            &mut output,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!("--stages requires --evolution", err.to_string());
+        assert!(output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_moves_config_default() {
+        // pokemon.moves stands in for --moves
+        let mut output = vec![];
+        let command = parse(&["pokemon", "pikachu", "--up-to-level", "10"]);
+        run_command(command, config(true), &mut output)
+            .await
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("thunder-shock"));
+        assert!(!output.contains("thunderbolt"));
+    }
 }
@@ -1,60 +1,169 @@
 use super::display::*;
-use super::ResourceArgs;
+use super::utils::terminal_width;
+use super::{ResourceArgs, ResourceOrder};
 use crate::api::game_to_gen;
+use crate::error::DunsparsError;
 use crate::models::database::{
-    AbilityRow, GameRow, MoveRow, PokemonRow, SelectAllNames, TypeRow, Validate,
+    AbilityRow, GameRow, MetaRow, MoveRow, PokemonMoveRow, PokemonRow, SelectAllNames,
+    SelectGenerationNames, SelectRow, TypeRow, Validate, ValidateGeneration,
 };
-use crate::models::{Ability, FromName, FromNameCustom, Move, Pokemon, Type};
-use crate::resource::config::ConfigFile;
-use crate::resource::custom::{CustomCollection, CustomFile};
+use crate::models::db::Db;
+use crate::models::{
+    normalize_name, resolve_type_alias, uncovered_typings, validate_custom, Ability,
+    BattleConditions, DefenseTypeChart, EvolutionStep, FormModifier, FromName, FromNameCustom,
+    GenerationRange, Machines, Move, OffenseTypeChart, Pokemon, Role, Stat, StatBound, StatTotals,
+    Synergy, Type, TypeChart, TypeScore, Units, TYPES,
+};
+use crate::resource::config::{ConfigFile, ConfigKey};
+use crate::resource::custom::{CustomCollection, CustomFile, CustomPokemon, CustomTypes};
 use crate::resource::database::DatabaseFile;
-use crate::resource::{Config, YamlFile};
+use crate::resource::type_chart::{TypeChartCollection, TypeChartFile};
+use crate::resource::{AppFile, Config, YamlFile};
 
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use indoc::writedoc;
 use rusqlite::Connection;
 
 struct AppContext {
-    db: Connection,
+    db: Db,
     custom: CustomCollection,
+    type_charts: TypeChartCollection,
     config: Config,
+    // Name lists keyed by table; fetched at most once per process
+    names: RefCell<HashMap<&'static str, Vec<String>>>,
+    generation_names: RefCell<HashMap<(&'static str, u8), Vec<String>>>,
+    stat_totals: RefCell<HashMap<u8, StatTotals>>,
 }
 impl AppContext {
     fn try_new(config: Config) -> Result<Self> {
-        let db_file = if let Some(path) = &config.db_path {
-            DatabaseFile::new(path.clone())
-        } else {
-            DatabaseFile::default()
-        };
-        let db = db_file.connect()?;
+        let db = DatabaseFile::from_config(&config).connect()?;
+
+        let custom = CustomFile::from_config(&config).read()?;
 
-        let custom_file = if let Some(path) = &config.custom_path {
-            CustomFile::new(path.clone())
+        let type_chart_file = if let Some(path) = &config.type_chart_path {
+            TypeChartFile::new(path.clone())
         } else {
-            CustomFile::default()
+            TypeChartFile::default()
         };
-        let custom = custom_file.read()?;
+        let type_charts = type_chart_file.read()?;
+
+        let app = Self {
+            db: Db::new(db),
+            config,
+            custom,
+            type_charts,
+            names: RefCell::new(HashMap::new()),
+            generation_names: RefCell::new(HashMap::new()),
+            stat_totals: RefCell::new(HashMap::new()),
+        };
+        for name in app.type_charts.type_names() {
+            Validate::<TypeRow>::validate(&app, name)
+                .map_err(|e| anyhow!("Invalid type chart file: {e}"))?;
+        }
+        app.type_charts
+            .check_conflicts()
+            .map_err(|e| anyhow!("Invalid type chart file: {e}"))?;
 
-        Ok(Self { db, config, custom })
+        Ok(app)
     }
 
-    fn get_generation(&self) -> Result<u8> {
-        let game = match &self.config.game {
-            Some(game) => Validate::<GameRow>::validate(&self.db, game)?,
+    // Types and Pokémon are built through these so that custom type charts apply to them
+    fn get_type(&self, name: &str, generation: u8) -> Result<Type> {
+        let type_ = Type::from_name(name, generation, &self.db, self)?;
+        Ok(type_.with_custom_chart(&self.type_charts))
+    }
+
+    fn get_pokemon(&self, name: &str, generation: u8) -> Result<Pokemon> {
+        let pokemon = Pokemon::from_name(name, generation, &self.db, self, &self.custom)?;
+        pokemon.get_custom_defense_chart(&self.type_charts, &self.db)?;
+        Ok(pokemon)
+    }
+
+    // For names already taken from the database, e.g. listings, which skip validation
+    fn load_pokemon(&self, name: &str, generation: u8) -> Result<Pokemon> {
+        let pokemon = self.db.pokemon(name, generation)?;
+        pokemon.get_custom_defense_chart(&self.type_charts, &self.db)?;
+        Ok(pokemon)
+    }
+
+    fn get_game(&self) -> Result<String> {
+        match &self.config.game {
+            Some(game) => Validate::<GameRow>::validate(self, game),
             None => self
-                .get_latest_game()
-                .ok_or(anyhow!("Cannot find the latest game"))?,
-        };
+                .get_latest_game()?
+                .ok_or(anyhow!("Cannot find the latest game")),
+        }
+    }
+
+    fn get_generation(&self) -> Result<u8> {
+        let game = self.get_game()?;
         Ok(game_to_gen(&game, &self.db))
     }
 
-    fn get_latest_game(&self) -> Option<String> {
-        GameRow::select_all_names(&self.db)
-            .unwrap()
-            .last()
-            .map(|g| g.to_string())
+    fn get_latest_game(&self) -> Result<Option<String>> {
+        Ok(GameRow::select_latest(&self.db)?)
+    }
+
+    fn select_all_names<T: SelectAllNames>(&self) -> Result<Vec<String>> {
+        let mut names = self.names.borrow_mut();
+        if let Some(names) = names.get(T::table()) {
+            return Ok(names.clone());
+        }
+
+        let table_names = T::select_all_names(&self.db)?;
+        names.insert(T::table(), table_names.clone());
+        Ok(table_names)
+    }
+
+    fn select_generation_names<T: SelectAllNames>(&self, generation: u8) -> Result<Vec<String>>
+    where
+        Connection: ValidateGeneration<T>,
+    {
+        let key = (T::table(), generation);
+        let mut names = self.generation_names.borrow_mut();
+        if let Some(names) = names.get(&key) {
+            return Ok(names.clone());
+        }
+
+        let generation_names =
+            ValidateGeneration::<T>::get_generation_resource(&*self.db, generation)?;
+        names.insert(key, generation_names.clone());
+        Ok(generation_names)
+    }
+
+    fn get_stat_totals(&self, generation: u8) -> Result<StatTotals> {
+        let mut stat_totals = self.stat_totals.borrow_mut();
+        if let Some(totals) = stat_totals.get(&generation) {
+            return Ok(totals.clone());
+        }
+
+        let totals = StatTotals::try_new(generation, &self.db)?;
+        stat_totals.insert(generation, totals.clone());
+        Ok(totals)
+    }
+}
+impl<T: SelectAllNames> Validate<T> for AppContext {
+    fn get_resource(&self) -> Result<Vec<String>> {
+        self.select_all_names::<T>()
+    }
+
+    fn label() -> &'static str {
+        T::label()
+    }
+}
+impl<T: SelectAllNames> ValidateGeneration<T> for AppContext
+where
+    Connection: ValidateGeneration<T>,
+{
+    fn get_generation_resource(&self, generation: u8) -> Result<Vec<String>> {
+        self.select_generation_names::<T>(generation)
     }
 }
 
@@ -62,11 +171,58 @@ pub trait Command {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32>;
 }
 
-pub struct SetupCommand;
+pub struct SetupCommand {
+    pub with_encounters: bool,
+    pub with_machines: bool,
+    pub dry_run: bool,
+}
 impl Command for SetupCommand {
     async fn run(&self, _config: Config, writer: &mut impl Write) -> Result<i32> {
         let file = DatabaseFile::default();
-        file.build_db(writer).await?;
+        if self.dry_run {
+            file.dry_run(writer, self.with_encounters, self.with_machines)
+                .await?;
+            return Ok(0);
+        }
+
+        file.build_db(writer, self.with_encounters, self.with_machines)
+            .await?;
+        Ok(0)
+    }
+}
+
+pub struct DataVersionCommand;
+impl Command for DataVersionCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let db_file = DatabaseFile::from_config(&config);
+        let db = db_file.open()?;
+
+        // Databases built by older versions may lack some entries
+        let meta = |name| MetaRow::select_by_name(name, &db).map(|row| row.value).ok();
+        let unknown = || String::from("unknown");
+        let (games, first_gen, last_gen) = GameRow::select_coverage(&db)?;
+        let generations = match (first_gen, last_gen) {
+            (Some(first), Some(last)) => format!("gen-{first} to gen-{last}"),
+            _ => String::from("no generations"),
+        };
+
+        writedoc! {
+            writer,
+            "
+            database: {path}
+            built by: dunspars {version}
+            built at: {built_at}
+            encounters: {encounters}
+            machines: {machines}
+            games: {games} ({generations})
+            ",
+            path = db_file.path().display(),
+            version = meta("version").unwrap_or_else(unknown),
+            built_at = meta("built_at").map_or_else(unknown, |time| format!("{time} UTC")),
+            encounters = meta("encounters").unwrap_or_else(unknown),
+            machines = meta("machines").unwrap_or_else(unknown),
+        }?;
+
         Ok(0)
     }
 }
@@ -75,34 +231,114 @@ pub struct PokemonCommand {
     pub name: String,
     pub moves: bool,
     pub evolution: bool,
+    pub evolution_methods: bool,
+    pub evolution_stages: bool,
+    pub forms: bool,
+    pub up_to_level: Option<i64>,
+    pub include_tm: bool,
+    pub format: OutputFormat,
+    pub sort: Option<Stat>,
+    pub details: bool,
+    pub vs: Option<String>,
+    pub tm: bool,
+    pub ev_suggest: Option<Role>,
+    pub form: Option<FormModifier>,
+    pub compact: bool,
+    pub assets: bool,
+    pub bars: bool,
+    pub explain: Option<String>,
 }
 impl Command for PokemonCommand {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
         let app = AppContext::try_new(config)?;
         let generation = app.get_generation()?;
 
-        let pokemon = Pokemon::from_name(&self.name, generation, &app.db, &app.custom)?;
-        let pokemon_display = DisplayComponent::new(&pokemon, app.config.color_enabled);
+        let mut pokemon = app.get_pokemon(&self.name, generation)?;
+        if let Some(form) = &self.form {
+            let name = form.resolve(&pokemon.name, &pokemon.get_forms(&app.db)?)?;
+            pokemon = app.get_pokemon(&name, generation)?;
+        }
+        // EVs as they work today and natures arrived in generation 3
+        if self.ev_suggest.is_some() && pokemon.generation < 3 {
+            bail!("EV spreads and natures are not available before generation 3");
+        }
+        if self.compact {
+            let pokemon_display = DisplayComponent::new(&pokemon, app.config.color_enabled);
+            writeln!(writer, "{}", pokemon_display.compact())?;
+            return Ok(0);
+        }
+        if self.assets {
+            return self.run_assets(&pokemon, writer);
+        }
+        if app.config.quiet {
+            let pokemon_display = DisplayComponent::new(&pokemon, app.config.color_enabled);
+            writeln!(writer, "{}", pokemon_display.summary())?;
+            return Ok(0);
+        }
+
+        match self.format {
+            OutputFormat::Markdown => return self.run_markdown(&app, &pokemon, writer),
+            OutputFormat::Dot => {
+                let evolution_step = pokemon.get_evolution_steps(&app.db)?;
+                let evolution_step_display = DisplayComponent::new(&evolution_step, Some(false));
+                writeln!(writer, "{}", evolution_step_display.dot())?;
+                return Ok(0);
+            }
+            OutputFormat::Text | OutputFormat::Json => {}
+        }
+
+        // Custom Pokémon are ranked against their own generation
+        let stat_totals = if self.details {
+            Some(app.get_stat_totals(pokemon.generation)?)
+        } else {
+            None
+        };
+        let pokemon_context = PokemonComponent {
+            pokemon: &pokemon,
+            sort: self.sort,
+            details: self.details,
+            stat_totals: stat_totals.as_ref(),
+            units: app.config.units.unwrap_or_default(),
+        };
+        let pokemon_display = DisplayComponent::new(pokemon_context, app.config.color_enabled);
 
         let defense_chart = pokemon.get_defense_chart(&app.db)?;
         let defense_chart_ctx = TypeChartComponent {
             type_chart: &defense_chart,
+            bars: self.bars,
         };
         let type_chart_display = DisplayComponent::new(defense_chart_ctx, app.config.color_enabled);
+        let immunities = type_chart_display
+            .immunities()
+            .map_or(String::from(""), |immunities| format!("\n\n{immunities}"));
 
         writedoc! {
             writer,
             "
             {pokemon_display}
 
-            {type_chart_display}
+            {type_chart_display}{immunities}
             "
         }?;
 
+        if let Some(role) = self.ev_suggest {
+            let spread = pokemon.stats.suggest_spread(role);
+            let spread_display = DisplayComponent::new(&spread, app.config.color_enabled);
+            writedoc! {
+                writer,
+                "
+
+                {spread_display}
+                "
+            }?;
+        }
+
         if self.evolution {
             let evolution_step = pokemon.get_evolution_steps(&app.db)?;
             let evolution_step_display =
                 DisplayComponent::new(&evolution_step, app.config.color_enabled);
+            let evolution_step_display =
+                evolution_step_display.tree(self.evolution_methods, self.evolution_stages);
             writedoc! {
                 writer,
                 "
@@ -112,11 +348,33 @@ impl Command for PokemonCommand {
             }?;
         }
 
+        if self.forms {
+            let forms = pokemon.get_forms(&app.db)?;
+            let forms_context = FormsComponent { forms: &forms };
+            let forms_display = DisplayComponent::new(forms_context, app.config.color_enabled);
+            writedoc! {
+                writer,
+                "
+
+                {forms_display}
+                "
+            }?;
+        }
+
         if self.moves {
             let moves = pokemon.get_learnable_move_list(&app.db)?;
+            let defender = self.get_defender(&app, generation)?;
+            let machines = self.get_machines(&app)?;
             let move_list_context = MoveListComponent {
                 move_list: &moves,
                 pokemon: &pokemon,
+                up_to_level: self.up_to_level,
+                include_tm: self.include_tm,
+                vs: defender
+                    .as_ref()
+                    .map(|(d, chart)| (d.nickname.as_str(), chart)),
+                machines: machines.as_ref(),
+                width: terminal_width(),
             };
             let move_list_display =
                 DisplayComponent::new(move_list_context, app.config.color_enabled);
@@ -128,24 +386,402 @@ impl Command for PokemonCommand {
                 {move_list_display}
                 "
             }?;
+
+            if let Some(move_name) = &self.explain {
+                let explanation = self.explain_move(&app, &pokemon, move_name)?;
+                writeln!(writer, "\n{explanation}")?;
+            }
+        }
+
+        Ok(0)
+    }
+}
+
+impl PokemonCommand {
+    // Points to the generations that do teach a move missing from this one's learnset
+    fn explain_move(&self, app: &AppContext, pokemon: &Pokemon, move_name: &str) -> Result<String> {
+        let move_name = Validate::<MoveRow>::validate(app, &normalize_name(move_name))?;
+        let Pokemon {
+            name, generation, ..
+        } = pokemon;
+
+        let methods = learn_methods(&pokemon.learnable_moves, &move_name);
+        if !methods.is_empty() {
+            return Ok(format!(
+                "{name} learns {move_name} in generation {generation} by {}",
+                methods.join(" and ")
+            ));
+        }
+
+        let generations = pokemon.get_move_generations(&move_name, &app.db)?;
+        if generations.is_empty() {
+            return Ok(format!(
+                "{name} does not learn {move_name} in any generation"
+            ));
+        }
+        let generations = generations
+            .iter()
+            .map(|generation| generation.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        Ok(format!(
+            "{name} does not learn {move_name} in generation {generation}; it does in generations {generations}"
+        ))
+    }
+
+    fn get_defender(
+        &self,
+        app: &AppContext,
+        generation: u8,
+    ) -> Result<Option<(Pokemon, DefenseTypeChart)>> {
+        let Some(name) = &self.vs else {
+            return Ok(None);
+        };
+
+        let defender = app.get_pokemon(name, generation)?;
+        let chart = defender.get_defense_chart(&app.db)?;
+        Ok(Some((defender, chart)))
+    }
+
+    fn get_machines(&self, app: &AppContext) -> Result<Option<Machines>> {
+        if !self.tm {
+            return Ok(None);
+        }
+
+        let game = app.get_game()?;
+        Ok(Some(Machines::try_new(&game, &app.db)?))
+    }
+
+    fn run_assets(&self, pokemon: &Pokemon, writer: &mut impl Write) -> Result<i32> {
+        let sprite = &pokemon.details.sprite;
+        if self.format == OutputFormat::Json {
+            let assets = serde_json::json!({ "name": pokemon.name, "sprite": sprite });
+            writeln!(writer, "{assets}")?;
+        } else {
+            let sprite = sprite.as_deref().unwrap_or("N/A");
+            writeln!(writer, "sprite: {sprite}")?;
+        }
+
+        Ok(0)
+    }
+
+    fn run_markdown(
+        &self,
+        app: &AppContext,
+        pokemon: &Pokemon,
+        writer: &mut impl Write,
+    ) -> Result<i32> {
+        let types = pokemon
+            .get_types()
+            .into_iter()
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" ");
+        let stats_context = StatsComponent {
+            stats: &pokemon.stats,
+            sort: self.sort,
+        };
+        let stats_display = DisplayComponent::new(stats_context, Some(false));
+
+        writedoc! {
+            writer,
+            "
+            ## {name}
+            {types}
+
+            {stats}",
+            name = pokemon.nickname,
+            stats = stats_display.markdown(),
+        }?;
+
+        if self.moves {
+            let moves = pokemon.get_learnable_move_list(&app.db)?;
+            let defender = self.get_defender(app, pokemon.generation)?;
+            let machines = self.get_machines(app)?;
+            let move_list_context = MoveListComponent {
+                move_list: &moves,
+                pokemon,
+                up_to_level: self.up_to_level,
+                include_tm: self.include_tm,
+                vs: defender
+                    .as_ref()
+                    .map(|(d, chart)| (d.nickname.as_str(), chart)),
+                machines: machines.as_ref(),
+                width: terminal_width(),
+            };
+            let move_list_display = DisplayComponent::new(move_list_context, Some(false));
+
+            writedoc! {
+                writer,
+                "
+
+                {moves}",
+                moves = move_list_display.markdown(),
+            }?;
         }
 
         Ok(0)
     }
 }
 
+pub struct RandomCommand {
+    pub type_: Option<String>,
+    pub legendary: bool,
+    pub seed: Option<u64>,
+}
+impl Command for RandomCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let generation = app.get_generation()?;
+        let type_ = self
+            .type_
+            .as_ref()
+            .map(|t| Validate::<TypeRow>::validate(&app, &resolve_type_alias(t)))
+            .transpose()?;
+
+        let mut candidates =
+            PokemonRow::select_names_by_generation(generation, self.legendary, &app.db)?;
+        let mut rng = match self.seed {
+            Some(seed) => fastrand::Rng::with_seed(seed),
+            None => fastrand::Rng::new(),
+        };
+        rng.shuffle(&mut candidates);
+
+        // Types are checked after loading so that past type changes are respected
+        let mut chosen = None;
+        for name in candidates {
+            let pokemon = app.load_pokemon(&name, generation)?;
+            if type_
+                .as_ref()
+                .is_none_or(|t| pokemon.get_types().contains(&t))
+            {
+                chosen = Some(pokemon.name);
+                break;
+            }
+        }
+        let Some(name) = chosen else {
+            bail!("No Pokémon in generation {generation} match the given filters");
+        };
+
+        let pokemon = PokemonCommand {
+            name,
+            moves: false,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        pokemon.run(app.config, writer).await
+    }
+}
+
+pub struct PokemonTypeCommand {
+    pub type_: String,
+    pub page: Option<usize>,
+    pub page_size: usize,
+}
+impl Command for PokemonTypeCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let generation = app.get_generation()?;
+        let type_ = Validate::<TypeRow>::validate(&app, &resolve_type_alias(&self.type_))?;
+
+        // Types are checked after loading so that past type changes are respected
+        let mut pokemon = vec![];
+        for name in PokemonRow::select_names_by_generation(generation, true, &app.db)? {
+            let candidate = app.load_pokemon(&name, generation)?;
+            if candidate.get_types().contains(&&type_) {
+                pokemon.push(candidate);
+            }
+        }
+        if pokemon.is_empty() {
+            bail!("No {type_} Pokémon are available in generation {generation}");
+        }
+
+        pokemon.sort_by(|a, b| {
+            b.stats
+                .get(Stat::Total)
+                .cmp(&a.stats.get(Stat::Total))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let pokemon = paginate(pokemon, self.page, self.page_size)?;
+
+        let list_context = PokemonListComponent { pokemon: &pokemon };
+        let list = DisplayComponent::new(list_context, app.config.color_enabled);
+        writeln!(writer, "{list}")?;
+
+        Ok(0)
+    }
+}
+
+pub struct SearchCommand {
+    pub min: Vec<StatBound>,
+    pub max: Vec<StatBound>,
+    pub compact: bool,
+    pub page: Option<usize>,
+    pub page_size: usize,
+}
+impl Command for SearchCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let generation = app.get_generation()?;
+
+        let ranges = StatBound::ranges(&self.min, &self.max)?
+            .into_iter()
+            .map(|(stat, range)| (stat.column(generation), range))
+            .collect::<Vec<_>>();
+        let names = PokemonRow::select_names_by_stats(generation, &ranges, &app.db)?;
+        if names.is_empty() {
+            bail!("No Pokémon in generation {generation} match the given stats");
+        }
+        let names = paginate(names, self.page, self.page_size)?;
+
+        for name in names {
+            if self.compact {
+                let pokemon = app.load_pokemon(&name, generation)?;
+                let pokemon_display = DisplayComponent::new(&pokemon, app.config.color_enabled);
+                writeln!(writer, "{}", pokemon_display.compact())?;
+            } else {
+                writeln!(writer, "{name}")?;
+            }
+        }
+
+        Ok(0)
+    }
+}
+
+pub struct PokemonAvailabilityCommand {
+    pub name: String,
+}
+impl Command for PokemonAvailabilityCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+
+        // Loading the Pokémon would fail when it is absent from the configured game,
+        // so only its name is resolved; custom Pokémon report their base
+        let name = match app.custom.find_pokemon(&self.name) {
+            Some(custom) => Validate::<PokemonRow>::validate(&app, &custom.base)?,
+            None => Validate::<PokemonRow>::validate(&app, &normalize_name(&self.name))?,
+        };
+
+        let mut availability: Vec<(u8, Vec<String>)> = vec![];
+        for (generation, game) in PokemonRow::select_availability(&name, &app.db)? {
+            match availability.last_mut() {
+                Some((last_generation, games)) if *last_generation == generation => {
+                    games.push(game)
+                }
+                _ => availability.push((generation, vec![game])),
+            }
+        }
+
+        let availability_context = AvailabilityComponent {
+            name: &name,
+            availability: &availability,
+        };
+        let availability_display =
+            DisplayComponent::new(availability_context, app.config.color_enabled);
+        writeln!(writer, "{availability_display}")?;
+
+        Ok(0)
+    }
+}
+
+pub struct PokemonMatrixCommand {
+    pub name: String,
+}
+impl Command for PokemonMatrixCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let name = match app.custom.find_pokemon(&self.name) {
+            Some(custom) => Validate::<PokemonRow>::validate(&app, &custom.base)?,
+            None => Validate::<PokemonRow>::validate(&app, &normalize_name(&self.name))?,
+        };
+
+        let (_, first, last) = GameRow::select_coverage(&app.db)?;
+        let (Some(first), Some(last)) = (first, last) else {
+            bail!(DunsparsError::DatabaseMalformed);
+        };
+
+        let mut matrix = vec![];
+        for generation in first..=last {
+            // Absent generations are expected; any other error is not
+            let entry = match app.load_pokemon(&name, generation) {
+                Ok(pokemon) => Some((
+                    pokemon.get_types().into_iter().cloned().collect(),
+                    pokemon.stats.get(Stat::Total),
+                )),
+                Err(err) => match err.downcast_ref::<DunsparsError>() {
+                    Some(DunsparsError::NotInGeneration { .. }) => None,
+                    _ => return Err(err),
+                },
+            };
+            matrix.push((generation, entry));
+        }
+
+        let matrix_context = AvailabilityMatrixComponent {
+            name: &name,
+            matrix: &matrix,
+        };
+        let matrix_display = DisplayComponent::new(matrix_context, app.config.color_enabled);
+        writeln!(writer, "{matrix_display}")?;
+
+        Ok(0)
+    }
+}
+
 pub struct TypeCommand {
     pub primary_type: String,
     pub secondary_type: Option<String>,
+    pub vs: Vec<String>,
+    pub se_only: bool,
+    pub bars: bool,
 }
 impl Command for TypeCommand {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
         let app = AppContext::try_new(config)?;
         let generation = app.get_generation()?;
 
-        let primary_type = Type::from_name(&self.primary_type, generation, &app.db)?;
+        if !self.vs.is_empty() {
+            return self.run_vs(&app, generation, writer);
+        }
+
+        let primary_type = app.get_type(&self.primary_type, generation)?;
+        if self.se_only {
+            let secondary_type = self
+                .secondary_type
+                .as_ref()
+                .map(|t| app.get_type(t, generation))
+                .transpose()?;
+            let mut type_charts: Vec<&dyn TypeChart> = vec![&primary_type.offense_chart];
+            if let Some(secondary_type) = &secondary_type {
+                type_charts.push(&secondary_type.offense_chart);
+            }
+
+            let super_effective_ctx = SuperEffectiveComponent {
+                type_charts: &type_charts,
+            };
+            let super_effective_display =
+                DisplayComponent::new(super_effective_ctx, app.config.color_enabled);
+            writeln!(writer, "{super_effective_display}")?;
+            return Ok(0);
+        }
+
         let primary_offense_ctx = TypeChartComponent {
             type_chart: &primary_type.offense_chart,
+            bars: self.bars,
         };
         let primary_offense_display =
             DisplayComponent::new(primary_offense_ctx, app.config.color_enabled);
@@ -153,13 +789,14 @@ impl Command for TypeCommand {
         let secondary_type = self
             .secondary_type
             .as_ref()
-            .map(|t| Type::from_name(t, generation, &app.db));
+            .map(|t| app.get_type(t, generation));
 
         match secondary_type {
             Some(secondary_type) => {
                 let secondary_type = secondary_type?;
                 let secondary_offense_ctx = TypeChartComponent {
                     type_chart: &secondary_type.offense_chart,
+                    bars: self.bars,
                 };
                 let secondary_offense_display =
                     DisplayComponent::new(secondary_offense_ctx, app.config.color_enabled);
@@ -167,9 +804,16 @@ impl Command for TypeCommand {
                 let combined_defense = primary_type.defense_chart + secondary_type.defense_chart;
                 let defense_ctx = TypeChartComponent {
                     type_chart: &combined_defense,
+                    bars: self.bars,
                 };
                 let defense_display = DisplayComponent::new(defense_ctx, app.config.color_enabled);
 
+                let score = TypeScore::new(
+                    &combined_defense,
+                    &[&primary_type.offense_chart, &secondary_type.offense_chart],
+                );
+                let score_display = DisplayComponent::new(&score, app.config.color_enabled);
+
                 writedoc! {
                     writer,
                     "
@@ -178,21 +822,30 @@ impl Command for TypeCommand {
                     {secondary_offense_display}
 
                     {defense_display}
+
+                    {score_display}
                     "
                 }?;
             }
             None => {
                 let defense_ctx = TypeChartComponent {
                     type_chart: &primary_type.defense_chart,
+                    bars: self.bars,
                 };
                 let defense_display = DisplayComponent::new(defense_ctx, app.config.color_enabled);
 
+                let score =
+                    TypeScore::new(&primary_type.defense_chart, &[&primary_type.offense_chart]);
+                let score_display = DisplayComponent::new(&score, app.config.color_enabled);
+
                 writedoc! {
                     writer,
                     "
                     {primary_offense_display}
 
                     {defense_display}
+
+                    {score_display}
                     "
                 }?;
             }
@@ -202,98 +855,553 @@ impl Command for TypeCommand {
     }
 }
 
-pub struct MoveCommand {
-    pub name: String,
+pub struct TypeCoverageCommand {
+    pub types: Vec<String>,
 }
-impl Command for MoveCommand {
+impl Command for TypeCoverageCommand {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
         let app = AppContext::try_new(config)?;
         let generation = app.get_generation()?;
 
-        let move_ = Move::from_name(&self.name, generation, &app.db)?;
-        let move_display = DisplayComponent::new(&move_, app.config.color_enabled);
+        let types = self
+            .types
+            .iter()
+            .map(|name| app.get_type(name, generation))
+            .collect::<Result<Vec<Type>>>()?;
+        let offense = types.iter().map(|t| &t.offense_chart).collect::<Vec<_>>();
+
+        // Only types present in the generation can defend, e.g. no fairy before generation 6
+        let defending_types = TypeRow::select_names_up_to_generation(generation, &app.db)?
+            .into_iter()
+            .filter(|name| TYPES.contains(&name.as_str()))
+            .collect::<Vec<String>>();
+        let uncovered = uncovered_typings(&offense, &defending_types);
+
+        let type_names = types.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+        let coverage_context = TypeCoverageComponent {
+            types: &type_names,
+            uncovered: &uncovered,
+        };
+        let coverage_display = DisplayComponent::new(coverage_context, app.config.color_enabled);
+        writeln!(writer, "{coverage_display}")?;
+
+        Ok(0)
+    }
+}
 
-        writedoc! {
-            writer,
-            "
-            {move_display}
-            "
-        }?;
+impl TypeCommand {
+    fn run_vs(&self, app: &AppContext, generation: u8, writer: &mut impl Write) -> Result<i32> {
+        if self.vs.len() > 2 {
+            bail!("--vs accepts at most two types");
+        }
+
+        let load = |names: Vec<&String>| -> Result<Vec<Type>> {
+            names
+                .into_iter()
+                .map(|name| app.get_type(name, generation))
+                .collect()
+        };
+        let types = load(
+            [Some(&self.primary_type), self.secondary_type.as_ref()]
+                .into_iter()
+                .flatten()
+                .collect(),
+        )?;
+        let vs = load(self.vs.iter().collect())?;
+
+        let combined_defense = |types: &[Type]| {
+            DefenseTypeChart::fold(types.iter().map(|t| t.defense_chart.clone())).unwrap()
+        };
+        let types_defense = combined_defense(&types);
+        let vs_defense = combined_defense(&vs);
+
+        let offense = types
+            .iter()
+            .map(|t| vs_defense.get_multiplier(&t.name))
+            .collect::<Vec<f32>>();
+        let defense = vs
+            .iter()
+            .map(|t| types_defense.get_multiplier(&t.name))
+            .collect::<Vec<f32>>();
+
+        let names = |types: &[Type]| types.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+        let (type_names, vs_names) = (names(&types), names(&vs));
+        let matchup_context = TypeMatchupComponent {
+            types: &type_names,
+            vs: &vs_names,
+            offense: &offense,
+            defense: &defense,
+        };
+        let matchup_display = DisplayComponent::new(matchup_context, app.config.color_enabled);
+        writeln!(writer, "{matchup_display}")?;
 
         Ok(0)
     }
 }
 
-pub struct AbilityCommand {
+pub struct MoveCommand {
     pub name: String,
+    pub verbose: bool,
+    pub learners: bool,
+    pub learn_method: Option<String>,
+    pub effect_only: bool,
+    pub history: bool,
+    pub generations: Option<String>,
+    pub effect_chance: Option<i64>,
 }
-impl Command for AbilityCommand {
+impl Command for MoveCommand {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
         let app = AppContext::try_new(config)?;
         let generation = app.get_generation()?;
+        let learn_method = self
+            .learn_method
+            .as_deref()
+            .map(|method| Validate::<PokemonMoveRow>::validate(&*app.db, &normalize_name(method)))
+            .transpose()?;
+
+        let mut move_ = Move::from_name(&self.name, generation, &app.db, &app)?;
+        if let Some(chance) = self.effect_chance {
+            if move_.effect_chance.is_none() {
+                bail!("{} has no effect chance to override", move_.name);
+            }
+            move_.effect_chance = Some(chance);
+        }
+        let move_context = MoveComponent {
+            move_: &move_,
+            verbose: self.verbose,
+        };
+        let move_display = DisplayComponent::new(move_context, app.config.color_enabled);
+
+        if self.effect_only {
+            writeln!(writer, "{}", move_display.effect())?;
+            return Ok(0);
+        }
 
-        let ability = Ability::from_name(&self.name, generation, &app.db)?;
-        let ability_display = DisplayComponent::new(&ability, app.config.color_enabled);
+        if app.config.quiet {
+            writeln!(writer, "{}", move_display.stats())?;
+            return Ok(0);
+        }
 
         writedoc! {
             writer,
             "
-            {ability_display}
+            {move_display}
             "
         }?;
 
-        Ok(0)
-    }
-}
-
+        if let Some(mut meta) = move_.get_meta(&app.db)?.filter(|meta| !meta.is_empty()) {
+            // The stat change chance is the effect chance of moves like crunch
+            if let Some(chance) = self.effect_chance.filter(|_| meta.stat_chance > 0) {
+                meta.stat_chance = chance;
+            }
+            let meta_context = MoveMetaComponent { meta: &meta };
+            let meta_display = DisplayComponent::new(meta_context, app.config.color_enabled);
+            writedoc! {
+                writer,
+                "
+
+                {meta_display}
+                "
+            }?;
+        }
+
+        if self.learners {
+            let learners = move_.get_learners(generation, learn_method.as_deref(), &app.db)?;
+            let learners_context = MoveLearnersComponent { pokemon: &learners };
+            let learners_display =
+                DisplayComponent::new(learners_context, app.config.color_enabled);
+            writedoc! {
+                writer,
+                "
+
+                {learners_display}
+                "
+            }?;
+        }
+
+        if self.history {
+            let generations = match &self.generations {
+                Some(range) => {
+                    let range = GenerationRange::from_str(range)?;
+                    let (_, first, last) = GameRow::select_coverage(&app.db)?;
+                    Some(range.resolve(first.unwrap_or(1), last.unwrap_or(generation))?)
+                }
+                None => None,
+            };
+            let history = move_.get_history(generations.as_ref(), &app.db)?;
+            let history_context = MoveHistoryComponent { history: &history };
+            let history_display = DisplayComponent::new(history_context, app.config.color_enabled);
+            writedoc! {
+                writer,
+                "
+
+                {history_display}
+                "
+            }?;
+        }
+
+        Ok(0)
+    }
+}
+
+pub struct MoveSearchCommand {
+    pub keyword: String,
+    pub page: Option<usize>,
+    pub page_size: usize,
+}
+impl Command for MoveSearchCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let generation = app.get_generation()?;
+
+        let names = MoveRow::select_names_by_effect(&self.keyword, generation, &app.db)?;
+        if names.is_empty() {
+            bail!(
+                "No moves mention '{}' in their effect in generation {generation}",
+                self.keyword
+            );
+        }
+        let names = paginate(names, self.page, self.page_size)?;
+        writeln!(writer, "{}", names.join("\n"))?;
+
+        Ok(0)
+    }
+}
+
+pub struct AbilityCommand {
+    pub name: String,
+    pub verbose: bool,
+    pub pokemon: bool,
+    pub hidden_only: bool,
+}
+impl Command for AbilityCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let generation = app.get_generation()?;
+
+        let ability = Ability::from_name(&self.name, generation, &app.db, &app)?;
+        let ability_context = AbilityComponent {
+            ability: &ability,
+            verbose: self.verbose,
+        };
+        let ability_display = DisplayComponent::new(ability_context, app.config.color_enabled);
+
+        writedoc! {
+            writer,
+            "
+            {ability_display}
+            "
+        }?;
+
+        if self.pokemon {
+            let pokemon = ability.get_pokemon(self.hidden_only, &app.db)?;
+            let pokemon_context = AbilityPokemonComponent { pokemon: &pokemon };
+            let pokemon_display = DisplayComponent::new(pokemon_context, app.config.color_enabled);
+            writedoc! {
+                writer,
+                "
+
+                {pokemon_display}
+                "
+            }?;
+        }
+
+        Ok(0)
+    }
+}
+
+pub struct WhereCommand {
+    pub name: String,
+}
+impl Command for WhereCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let game = app.get_game()?;
+        let generation = game_to_gen(&game, &app.db);
+
+        let pokemon = app.get_pokemon(&self.name, generation)?;
+        let encounters = pokemon.get_encounters(&game, &app.db)?;
+        let encounter_list_context = EncounterListComponent {
+            encounters: &encounters,
+            game: &game,
+        };
+        let encounter_list_display =
+            DisplayComponent::new(encounter_list_context, app.config.color_enabled);
+
+        writedoc! {
+            writer,
+            "
+            {encounter_list_display}
+            "
+        }?;
+
+        Ok(0)
+    }
+}
+
 #[derive(Clone)]
 pub struct MatchCommand {
     pub defender_names: Vec<String>,
-    pub attacker_name: String,
+    pub attacker_names: Vec<String>,
+    // Builds a single typing-only attacker instead of attacker_names
+    pub attacker_types: Vec<String>,
     pub verbose: bool,
     pub stab_only: bool,
+    pub sort: bool,
+    pub conditions: BattleConditions,
+    pub exclude: Vec<String>,
 }
 impl Command for MatchCommand {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
         let app = AppContext::try_new(config)?;
         let generation = app.get_generation()?;
 
-        let attacker = Pokemon::from_name(&self.attacker_name, generation, &app.db, &app.custom)?;
+        let mut defenders = resolve_pokemon(&app, generation, &self.defender_names)?;
+        // Repeated defenders would only repeat their blocks
+        let mut seen = HashSet::new();
+        let mut duplicates = vec![];
+        defenders.retain(|defender| {
+            let first = seen.insert(defender.nickname.clone());
+            if !first && !duplicates.contains(&defender.nickname) {
+                duplicates.push(defender.nickname.clone());
+            }
+            first
+        });
+        if !duplicates.is_empty() {
+            writeln!(
+                writer,
+                "Duplicate defenders are listed once: {}\n",
+                duplicates.join(", ")
+            )?;
+        }
 
-        let mut defenders = vec![];
+        let attackers = if self.attacker_types.is_empty() {
+            resolve_pokemon(&app, generation, &self.attacker_names)?
+        } else {
+            let types = self
+                .attacker_types
+                .iter()
+                .map(|name| app.get_type(name, generation))
+                .collect::<Result<Vec<Type>>>()?;
+            vec![Pokemon::from_types(&types, generation)?]
+        };
+        let exclude = validate_moves(&app, &self.exclude)?;
+        let options = MatchOptions {
+            verbose: self.verbose,
+            stab_only: self.stab_only,
+            sort: self.sort,
+            conditions: self.conditions,
+            exclude: &exclude,
+        };
 
-        for defender_name in self.defender_names.iter() {
-            let defender = Pokemon::from_name(defender_name, generation, &app.db, &app.custom)?;
+        for attacker in attackers.iter() {
+            // A single attacker keeps the original output; several are separated into blocks
+            if attackers.len() > 1 {
+                writeln!(writer, "[{attacker}]\n", attacker = attacker.nickname)?;
+            }
 
-            defenders.push(defender);
+            write_matchup(&app, &defenders, attacker, &options, writer)?;
         }
 
-        for defender in defenders {
-            let match_context = MatchComponent {
-                defender: &defender,
-                attacker: &attacker,
-                db: &app.db,
-                verbose: self.verbose,
-                stab_only: self.stab_only,
-            };
-            let match_display = DisplayComponent::new(match_context, app.config.color_enabled);
+        Ok(0)
+    }
+}
 
-            writedoc! {
-                writer,
-                "
-                {match_display}
+pub struct MatchFileCommand {
+    pub path: PathBuf,
+    pub verbose: bool,
+    pub stab_only: bool,
+    pub sort: bool,
+    pub conditions: BattleConditions,
+    pub exclude: Vec<String>,
+}
+impl Command for MatchFileCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let generation = app.get_generation()?;
+        let contents = fs::read_to_string(&self.path)?;
+        let exclude = validate_moves(&app, &self.exclude)?;
+        let options = MatchOptions {
+            verbose: self.verbose,
+            stab_only: self.stab_only,
+            sort: self.sort,
+            conditions: self.conditions,
+            exclude: &exclude,
+        };
 
+        let mut status_code = 0;
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
 
-                "
-            }?;
+            writeln!(writer, "[{line_number}] {line}\n", line_number = index + 1)?;
+
+            // Errors are reported per line so that one bad matchup does not abort the whole batch
+            let matchup = parse_matchup_line(line).and_then(|(defender_names, attacker_name)| {
+                resolve_matchup(&app, generation, &defender_names, &attacker_name)
+            });
+            match matchup {
+                Ok((defenders, attacker)) => {
+                    write_matchup(&app, &defenders, &attacker, &options, writer)?
+                }
+                Err(e) => {
+                    status_code = 1;
+                    writeln!(writer, "{e}\n\n")?;
+                }
+            }
         }
 
-        Ok(0)
+        Ok(status_code)
+    }
+}
+
+fn parse_matchup_line(line: &str) -> Result<(Vec<String>, String)> {
+    let parts = line.split_whitespace().collect::<Vec<&str>>();
+    let [defenders, attacker] = parts.as_slice() else {
+        bail!("Matchup '{line}' must be formatted as `defender1,defender2 attacker`");
+    };
+
+    let defender_names = defenders
+        .split(',')
+        .filter(|d| !d.is_empty())
+        .map(String::from)
+        .collect::<Vec<String>>();
+    if defender_names.is_empty() || defender_names.len() > 6 {
+        bail!("Matchup '{line}' must have between 1 and 6 defenders");
+    }
+
+    Ok((defender_names, attacker.to_string()))
+}
+
+fn resolve_matchup(
+    app: &AppContext,
+    generation: u8,
+    defender_names: &[String],
+    attacker_name: &str,
+) -> Result<(Vec<Pokemon>, Pokemon)> {
+    let attacker = app.get_pokemon(attacker_name, generation)?;
+    let defenders = resolve_pokemon(app, generation, defender_names)?;
+
+    Ok((defenders, attacker))
+}
+
+fn resolve_pokemon(app: &AppContext, generation: u8, names: &[String]) -> Result<Vec<Pokemon>> {
+    let mut pokemon = vec![];
+
+    for name in names.iter() {
+        let mon = app.get_pokemon(name, generation)?;
+
+        pokemon.push(mon);
+    }
+
+    Ok(pokemon)
+}
+
+fn validate_moves(app: &AppContext, names: &[String]) -> Result<HashSet<String>> {
+    names
+        .iter()
+        .map(|name| Validate::<MoveRow>::validate(app, &normalize_name(name)))
+        .collect()
+}
+
+// Each method a move is learned by, once, even when it is learned at several levels
+fn learn_methods<'a>(
+    learnable_moves: &'a [(String, String, i64)],
+    move_name: &str,
+) -> Vec<&'a str> {
+    let mut methods = vec![];
+    for (learned, method, _) in learnable_moves {
+        if learned == move_name && !methods.contains(&method.as_str()) {
+            methods.push(method.as_str());
+        }
+    }
+    methods
+}
+
+// Settings shared by every matchup of a match command
+struct MatchOptions<'a> {
+    verbose: bool,
+    stab_only: bool,
+    sort: bool,
+    conditions: BattleConditions,
+    exclude: &'a HashSet<String>,
+}
+
+// Defenders whose moves threaten the attacker the most come first. The key is only the
+// threat score and `sort_by_cached_key` is stable, so ties keep their input order
+fn sort_by_threat(
+    app: &AppContext,
+    defenders: &mut [&Pokemon],
+    attacker: &Pokemon,
+    options: &MatchOptions,
+) {
+    defenders.sort_by_cached_key(|defender| {
+        let threat = MoveWeaknessComponent {
+            defender: attacker,
+            attacker: defender,
+            db: &app.db,
+            verbose: false,
+            stab_only: options.stab_only,
+            conditions: options.conditions,
+            exclude: options.exclude,
+        };
+        std::cmp::Reverse(threat.threat_score())
+    });
+}
+
+fn write_matchup(
+    app: &AppContext,
+    defenders: &[Pokemon],
+    attacker: &Pokemon,
+    options: &MatchOptions,
+    writer: &mut impl Write,
+) -> Result<()> {
+    // Each attacker sorts from the input order, not from the previous attacker's order
+    let mut defenders = defenders.iter().collect::<Vec<&Pokemon>>();
+    if options.sort {
+        sort_by_threat(app, &mut defenders, attacker, options);
+    }
+
+    for defender in defenders {
+        if defender.nickname == attacker.nickname {
+            writeln!(writer, "mirror matchup: {} vs itself\n", attacker.nickname)?;
+        }
+
+        let match_context = MatchComponent {
+            defender,
+            attacker,
+            db: &app.db,
+            verbose: options.verbose,
+            stab_only: options.stab_only,
+            conditions: options.conditions,
+            exclude: options.exclude,
+        };
+        let match_display = DisplayComponent::new(match_context, app.config.color_enabled);
+
+        writedoc! {
+            writer,
+            "
+            {match_display}
+
+
+            "
+        }?;
     }
+
+    Ok(())
 }
 
 pub struct CoverageCommand {
     pub names: Vec<String>,
+    pub sort: bool,
+    pub min: Option<usize>,
+    pub include_overlap: bool,
+    pub summary: bool,
+    pub exclude: Vec<String>,
+    pub synergy: bool,
+    pub top_moves: Option<usize>,
 }
 impl Command for CoverageCommand {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
@@ -302,13 +1410,27 @@ impl Command for CoverageCommand {
 
         let mut pokemon = vec![];
         for name in self.names.iter() {
-            let mon = Pokemon::from_name(name, generation, &app.db, &app.custom)?;
+            let mon = app.get_pokemon(name, generation)?;
             pokemon.push(mon);
         }
+        if self.synergy {
+            return Self::run_synergy(&app, &pokemon, writer);
+        }
+        if self.top_moves == Some(0) {
+            bail!("--top-moves must be at least 1");
+        }
+        let exclude = validate_moves(&app, &self.exclude)?;
 
         let coverage_ctx = CoverageComponent {
             pokemon: &pokemon,
             db: &app.db,
+            sort: self.sort,
+            min: self.min,
+            include_overlap: self.include_overlap,
+            summary: self.summary,
+            exclude: &exclude,
+            type_charts: &app.type_charts,
+            top_moves: self.top_moves,
         };
         let coverage_display = DisplayComponent::new(coverage_ctx, app.config.color_enabled);
 
@@ -323,21 +1445,139 @@ impl Command for CoverageCommand {
     }
 }
 
+impl CoverageCommand {
+    fn run_synergy(app: &AppContext, pokemon: &[Pokemon], writer: &mut impl Write) -> Result<i32> {
+        let [first, second] = pokemon else {
+            bail!("--synergy compares exactly two Pokémon");
+        };
+
+        let charts = |mon: &Pokemon| -> Result<(DefenseTypeChart, Vec<OffenseTypeChart>)> {
+            let offense = mon
+                .get_types()
+                .into_iter()
+                .map(|type_| Ok(app.get_type(type_, mon.generation)?.offense_chart))
+                .collect::<Result<Vec<OffenseTypeChart>>>()?;
+            Ok((mon.get_defense_chart(&app.db)?, offense))
+        };
+        let (first_defense, first_offense) = charts(first)?;
+        let (second_defense, second_offense) = charts(second)?;
+        let first_offense = first_offense.iter().collect::<Vec<&OffenseTypeChart>>();
+        let second_offense = second_offense.iter().collect::<Vec<&OffenseTypeChart>>();
+
+        let first_synergy = Synergy::new(
+            &first_defense,
+            &first_offense,
+            &second_defense,
+            &second_offense,
+        );
+        let second_synergy = Synergy::new(
+            &second_defense,
+            &second_offense,
+            &first_defense,
+            &first_offense,
+        );
+        let first_ctx = SynergyComponent {
+            pokemon: &first.name,
+            partner: &second.name,
+            synergy: &first_synergy,
+        };
+        let first_display = DisplayComponent::new(first_ctx, app.config.color_enabled);
+        let second_ctx = SynergyComponent {
+            pokemon: &second.name,
+            partner: &first.name,
+            synergy: &second_synergy,
+        };
+        let second_display = DisplayComponent::new(second_ctx, app.config.color_enabled);
+
+        writedoc! {
+            writer,
+            "
+            {first_display}
+
+            {second_display}
+            "
+        }?;
+
+        Ok(0)
+    }
+}
+
 pub struct ResourceCommand {
     pub resource: ResourceArgs,
     pub delimiter: Option<String>,
+    pub page: Option<usize>,
+    pub page_size: usize,
+    pub columns: bool,
+    pub order: Option<ResourceOrder>,
+    pub current_gen: bool,
+}
+impl ResourceCommand {
+    fn paginate(&self, names: Vec<String>) -> Result<Vec<String>> {
+        paginate(names, self.page, self.page_size)
+    }
+
+    fn select_pokemon_names(&self, app: &AppContext) -> Result<Vec<String>> {
+        let Some(order) = self.order else {
+            return app.select_all_names::<PokemonRow>();
+        };
+
+        let mut pokemon = PokemonRow::select_all(&app.db)?;
+        match order {
+            // Form ids come after every default Pokémon, so they sort after their species' default
+            ResourceOrder::Dex => pokemon.sort_by_key(|p| (p.species_id, p.id)),
+            ResourceOrder::Name => pokemon.sort_by(|a, b| a.name.cmp(&b.name)),
+            ResourceOrder::Bst => pokemon.sort_by_key(|p| std::cmp::Reverse(p.base_stat_total())),
+        }
+        Ok(pokemon.into_iter().map(|p| p.name).collect())
+    }
+
+    // Pokémon, and chains by their base species, are present when they have moves in the generation;
+    // the rest when introduced by then
+    fn select_generation_names(&self, app: &AppContext) -> Result<HashSet<String>> {
+        let generation = app.get_generation()?;
+        let db = &app.db;
+        let names = match self.resource {
+            ResourceArgs::Pokemon => PokemonRow::select_names_by_generation(generation, true, db)?,
+            ResourceArgs::Moves => MoveRow::select_names_up_to_generation(generation, db)?,
+            ResourceArgs::Abilities => AbilityRow::select_names_up_to_generation(generation, db)?,
+            ResourceArgs::Types => TypeRow::select_names_up_to_generation(generation, db)?,
+            ResourceArgs::Games => GameRow::select_names_up_to_generation(generation, db)?,
+            ResourceArgs::Evolutions => {
+                PokemonRow::select_names_by_generation(generation, true, db)?
+            }
+        };
+        Ok(names.into_iter().collect())
+    }
 }
 impl Command for ResourceCommand {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
         let app = AppContext::try_new(config)?;
-        let delimiter = self.delimiter.clone().unwrap_or("\n".to_string());
 
-        let resource = match self.resource {
-            ResourceArgs::Pokemon => PokemonRow::select_all_names(&app.db)?.join(&delimiter),
-            ResourceArgs::Moves => MoveRow::select_all_names(&app.db)?.join(&delimiter),
-            ResourceArgs::Abilities => AbilityRow::select_all_names(&app.db)?.join(&delimiter),
-            ResourceArgs::Types => TypeRow::select_all_names(&app.db)?.join(&delimiter),
-            ResourceArgs::Games => GameRow::select_all_names(&app.db)?.join(&delimiter),
+        let names = match self.resource {
+            ResourceArgs::Pokemon => self.select_pokemon_names(&app)?,
+            ResourceArgs::Moves => app.select_all_names::<MoveRow>()?,
+            ResourceArgs::Abilities => app.select_all_names::<AbilityRow>()?,
+            ResourceArgs::Types => app.select_all_names::<TypeRow>()?,
+            ResourceArgs::Games => app.select_all_names::<GameRow>()?,
+            ResourceArgs::Evolutions => EvolutionStep::select_base_names(&app.db)?,
+        };
+        let names = if self.current_gen {
+            let present = self.select_generation_names(&app)?;
+            names.into_iter().filter(|n| present.contains(n)).collect()
+        } else {
+            names
+        };
+        let names = self.paginate(names)?;
+
+        let resource = if self.columns {
+            let columns_context = ColumnsComponent {
+                names: &names,
+                width: terminal_width(),
+            };
+            DisplayComponent::new(columns_context, app.config.color_enabled).to_string()
+        } else {
+            let delimiter = self.delimiter.clone().unwrap_or("\n".to_string());
+            names.join(&delimiter)
         };
 
         writedoc! {
@@ -351,13 +1591,142 @@ impl Command for ResourceCommand {
     }
 }
 
+fn paginate<T>(items: Vec<T>, page: Option<usize>, page_size: usize) -> Result<Vec<T>> {
+    let Some(page) = page else {
+        return Ok(items);
+    };
+
+    if page_size == 0 {
+        bail!("Page size must be greater than 0.");
+    }
+
+    let pages = items.len().div_ceil(page_size).max(1);
+    if page == 0 || page > pages {
+        bail!("Page {page} is out of range; there are {pages} pages.");
+    }
+
+    Ok(items
+        .into_iter()
+        .skip((page - 1) * page_size)
+        .take(page_size)
+        .collect())
+}
+
+pub struct CustomAddCommand {
+    pub nickname: String,
+    pub base: String,
+    pub moves: Vec<String>,
+    pub types: Vec<String>,
+    pub generation: Option<u8>,
+}
+impl Command for CustomAddCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let app = AppContext::try_new(config)?;
+        let custom_file = CustomFile::from_config(&app.config);
+        let mut custom = custom_file.read()?;
+        if custom.find_pokemon(&self.nickname).is_some() {
+            bail!("Custom Pokémon '{}' already exists", self.nickname);
+        }
+
+        let generation = match self.generation {
+            Some(generation) => generation,
+            None => app.get_generation()?,
+        };
+        let base = ValidateGeneration::<PokemonRow>::validate_in_generation(
+            &app,
+            &normalize_name(&self.base),
+            generation,
+        )?;
+        let types = self
+            .types
+            .iter()
+            .map(|type_| resolve_type_alias(type_))
+            .collect::<Vec<String>>();
+        let pokemon = CustomPokemon {
+            nickname: self.nickname.clone(),
+            base,
+            generation,
+            moves: self.moves.iter().map(|m| normalize_name(m)).collect(),
+            types: CustomTypes::from_list(&types)?,
+            stats: Default::default(),
+        };
+        // Nothing is written unless every move and type is valid
+        validate_custom(&pokemon, &app)?;
+
+        custom.add(pokemon)?;
+        custom_file.save(custom)?;
+        writeln!(
+            writer,
+            "Added {} to {}",
+            self.nickname,
+            custom_file.path().display()
+        )?;
+
+        Ok(0)
+    }
+}
+
+pub struct CustomRemoveCommand {
+    pub nickname: String,
+}
+impl Command for CustomRemoveCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let custom_file = CustomFile::from_config(&config);
+        let mut custom = custom_file.read()?;
+        let removed = custom.remove(&self.nickname)?;
+        custom_file.save(custom)?;
+        writeln!(
+            writer,
+            "Removed {} from {}",
+            removed.nickname,
+            custom_file.path().display()
+        )?;
+
+        Ok(0)
+    }
+}
+
+pub struct CustomListCommand;
+impl Command for CustomListCommand {
+    async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        let custom = CustomFile::from_config(&config).read()?;
+        if custom.pokemon().is_empty() {
+            writeln!(writer, "There are no custom Pokémon.")?;
+            return Ok(0);
+        }
+
+        for pokemon in custom.pokemon() {
+            writeln!(
+                writer,
+                "{nickname}  {base}  gen-{generation}",
+                nickname = pokemon.nickname,
+                base = pokemon.base,
+                generation = pokemon.generation
+            )?;
+        }
+
+        Ok(0)
+    }
+}
+
 pub struct ConfigCommand {
     pub key: Option<String>,
     pub value: Option<String>,
     pub unset: bool,
+    pub all: bool,
 }
 impl Command for ConfigCommand {
     async fn run(&self, config: Config, writer: &mut impl Write) -> Result<i32> {
+        if self.all {
+            for key in ConfigKey::ALL {
+                let value = config.get_value(key).unwrap_or(String::from("(unset)"));
+                let default = key.default_value();
+                writeln!(writer, "{key}: {value} (default: {default})")?;
+            }
+            return Ok(0);
+        }
+
+        let db_path = config.db_path.clone();
         let config_file = if let Some(path) = config.config_path {
             ConfigFile::new(path)
         } else {
@@ -371,7 +1740,9 @@ impl Command for ConfigCommand {
                 config.unset_value(key);
                 config_file.save(config)?;
             } else if let Some(value) = &self.value {
-                config.set_value(key, value);
+                let key = ConfigKey::from_str(key)?;
+                let value = Self::validate_value(key, value, db_path)?;
+                config.set_value(key.as_str(), &value);
                 config_file.save(config)?;
             } else if self.value.is_none() {
                 if let Some(value) = config.get_value(key) {
@@ -387,21 +1758,57 @@ impl Command for ConfigCommand {
         Ok(0)
     }
 }
+impl ConfigCommand {
+    fn validate_value(key: ConfigKey, value: &str, db_path: Option<PathBuf>) -> Result<String> {
+        match key {
+            ConfigKey::Game => {
+                let db_file = if let Some(path) = db_path {
+                    DatabaseFile::new(path)
+                } else {
+                    DatabaseFile::default()
+                };
+                let db = db_file.connect()?;
+                Validate::<GameRow>::validate(&db, value)
+            }
+            ConfigKey::Color
+            | ConfigKey::PokemonMoves
+            | ConfigKey::PokemonEvolution
+            | ConfigKey::MatchVerbose
+            | ConfigKey::MatchStabOnly => match value.parse::<bool>() {
+                Ok(color) => Ok(color.to_string()),
+                Err(_) => bail!("Config '{key}' must be either 'true' or 'false'"),
+            },
+            ConfigKey::Units => Ok(Units::from_str(value)?.to_string()),
+            ConfigKey::DbPath | ConfigKey::CustomPath | ConfigKey::TypeChartPath => {
+                if PathBuf::from(value).file_name().is_some() {
+                    Ok(String::from(value))
+                } else {
+                    bail!("Config '{key}' must be a path to a file")
+                }
+            }
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::Terrain;
     use crate::resource::ConfigBuilder;
     use std::env::current_dir;
 
     fn config(game: &str) -> Config {
         let mut custom_path = current_dir().expect("the current directory should be accessible");
         custom_path.push("configs/custom.yaml");
+        // A user's own type chart file must not affect the tests
+        let mut type_chart_path = std::env::temp_dir();
+        type_chart_path.push("dunspars_missing_type_chart.yaml");
 
         ConfigBuilder::default()
             .game(String::from(game))
             .color_enabled(false)
             .custom_path(custom_path)
+            .type_chart_path(type_chart_path)
             .build()
             .expect("the ConfigBuilder for commands.rs tests should succeed")
     }
@@ -412,6 +1819,44 @@ mod tests {
         String::from_utf8(writer).unwrap()
     }
 
+    #[tokio::test]
+    async fn run_pokemon_ev_suggest_before_gen_3() {
+        let config = config("red-blue");
+        let pokemon = PokemonCommand {
+            name: String::from("snorlax"),
+            moves: false,
+            evolution: false,
+            evolution_methods: false,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: Some(Role::Bulky),
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+
+        let mut writer = vec![];
+        let err = pokemon
+            .run(config, &mut writer)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "EV spreads and natures are not available before generation 3"
+        );
+        assert!(writer.is_empty());
+    }
+
     #[tokio::test]
     async fn run_pokemon() {
         let config = config("scarlet-violet");
@@ -419,6 +1864,22 @@ mod tests {
             name: String::from("ceruledge"),
             moves: false,
             evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
         };
 
         let output = run_command(pokemon, config).await;
@@ -431,6 +1892,126 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn run_pokemon_markdown() {
+        let config = config("scarlet-violet");
+        let ceruledge = PokemonCommand {
+            name: String::from("ceruledge"),
+            moves: true,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Markdown,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+
+        let output = run_command(ceruledge, config).await;
+
+        assert!(output.starts_with("## ceruledge\nfire ghost\n\n| hp | atk |"));
+        assert!(output.contains("| name | type | category | power | accuracy | pp | method |"));
+        assert!(output.contains("| bitter-blade(s) | fire | physical | 90 | 100 | 10 |"));
+        assert!(!output.contains('\u{1b}'));
+    }
+
+    #[tokio::test]
+    async fn run_random() {
+        let config = config("sword-shield");
+        let random = || RandomCommand {
+            type_: Some(String::from("dragon")),
+            legendary: false,
+            seed: Some(42),
+        };
+
+        let first = run_command(random(), config.clone()).await;
+        let second = run_command(random(), config).await;
+
+        assert_eq!(first, second);
+        assert!(first.lines().next().unwrap().contains(" dragon"));
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_all_of_type() {
+        let config = config("sword-shield");
+        let dragons = |page| PokemonTypeCommand {
+            type_: String::from("dragon"),
+            page,
+            page_size: 3,
+        };
+
+        let all = run_command(dragons(None), config.clone()).await;
+        let totals = all
+            .lines()
+            .map(|line| line.rsplit(' ').next().unwrap().parse::<i64>().unwrap())
+            .collect::<Vec<i64>>();
+        assert!(totals.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(all.lines().all(|line| line.contains("dragon")));
+
+        let first_page = run_command(dragons(Some(1)), config).await;
+        assert_eq!(
+            all.lines().take(3).collect::<Vec<&str>>(),
+            first_page.lines().collect::<Vec<&str>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn run_move_search() {
+        let search = |page| MoveSearchCommand {
+            keyword: String::from("paralyze"),
+            page,
+            page_size: 2,
+        };
+
+        let moves = run_command(search(None), config("red-blue")).await;
+        let moves = moves.lines().collect::<Vec<&str>>();
+        assert!(moves.contains(&"thunder-wave"));
+        assert!(moves.contains(&"body-slam"));
+        assert!(!moves.contains(&"nuzzle"));
+
+        let first_page = run_command(search(Some(1)), config("red-blue")).await;
+        assert_eq!(moves[..2], first_page.lines().collect::<Vec<&str>>());
+    }
+
+    #[tokio::test]
+    async fn run_search() {
+        let fast = SearchCommand {
+            min: vec![StatBound::from_str("spd=130").unwrap()],
+            max: vec![],
+            compact: false,
+            page: None,
+            page_size: 50,
+        };
+        let output = run_command(fast, config("sword-shield")).await;
+        let names = output.lines().collect::<Vec<&str>>();
+        assert!(names.contains(&"regieleki"));
+        assert!(names.contains(&"dragapult"));
+        assert!(!names.contains(&"snorlax"));
+
+        let frail = SearchCommand {
+            min: vec![StatBound::from_str("spd=130").unwrap()],
+            max: vec![StatBound::from_str("hp=80").unwrap()],
+            compact: true,
+            page: None,
+            page_size: 50,
+        };
+        let output = run_command(frail, config("sword-shield")).await;
+        assert!(output
+            .lines()
+            .any(|line| line == "regieleki electric 80/100/50/100/50/200 (580)"));
+        assert!(!output.contains("dragapult"));
+    }
+
     #[tokio::test]
     async fn run_pokemon_custom() {
         let config = config("scarlet-violet");
@@ -438,6 +2019,22 @@ mod tests {
             name: String::from("ramza"),
             moves: false,
             evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
         };
         let ramza_output = run_command(ramza, config.clone()).await;
 
@@ -449,6 +2046,233 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn run_pokemon_custom_stats() {
+        let config = config("scarlet-violet");
+        let brutus = PokemonCommand {
+            name: String::from("brutus"),
+            moves: false,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        let output = run_command(brutus, config).await;
+
+        // Machamp's 90 hp, 80 def, 65 satk, and 85 sdef are kept; atk and spd are overridden
+        assert!(output.contains("\n90    150   80    65    85    70    540   "));
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_custom_generation() {
+        let config = config("scarlet-violet");
+        let kenji = PokemonCommand {
+            name: String::from("kenji"),
+            moves: false,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        let output = run_command(kenji, config).await;
+
+        // The custom Pokémon's generation wins over the configured game
+        assert!(output.contains("\ngen-4\n"));
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_availability() {
+        // Kangaskhan never came to scarlet-violet, which would otherwise fail to load it
+        let config = config("scarlet-violet");
+        let kangaskhan = PokemonAvailabilityCommand {
+            name: String::from("kangaskhan"),
+        };
+        let output = run_command(kangaskhan, config.clone()).await;
+
+        assert!(output.starts_with("availability kangaskhan\ngen-1  red-blue yellow"));
+        assert!(output.contains("\ngen-7  sun-moon"));
+        assert!(!output.contains("\ngen-9"));
+        assert!(!output.contains("scarlet-violet"));
+
+        let custom = PokemonAvailabilityCommand {
+            name: String::from("kenji"),
+        };
+        let output = run_command(custom, config).await;
+        assert!(output.starts_with("availability garchomp\ngen-4  "));
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_matrix() {
+        let config = config("scarlet-violet");
+        let clefairy = PokemonMatrixCommand {
+            name: String::from("clefairy"),
+        };
+        let output = run_command(clefairy, config.clone()).await;
+
+        // Clefairy became fairy type in generation 6; gen 1 totals have no special defense
+        assert!(output.starts_with("availability matrix clefairy\n"));
+        assert!(output.contains("\ngen-1  normal  258\n"));
+        assert!(output.contains("\ngen-5  normal  323\n"));
+        assert!(output.contains("\ngen-6  fairy   323\n"));
+
+        let sprigatito = PokemonMatrixCommand {
+            name: String::from("sprigatito"),
+        };
+        let output = run_command(sprigatito, config).await;
+        assert!(output.contains("\ngen-8  -\n"));
+        assert!(output.contains("\ngen-9  grass  310\n"));
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_details() {
+        let config = config("scarlet-violet");
+        let dragonite = PokemonCommand {
+            name: String::from("dragonite"),
+            moves: false,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: true,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        let output = run_command(dragonite, config).await;
+
+        assert!(output.contains("\ncatch rate: 45  base exp: 300  growth: slow\n"));
+        assert!(output.contains("\nheight: 2.2 m  weight: 210.0 kg\n"));
+        assert!(output.contains("\nBST 600 — top "));
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_moves_vs() {
+        let config = config("scarlet-violet");
+        let pikachu = PokemonCommand {
+            name: String::from("pikachu"),
+            moves: true,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: Some(String::from("gyarados")),
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        let output = run_command(pikachu, config).await;
+        let line = |name: &str| {
+            output
+                .lines()
+                .find(|line| line.starts_with(name))
+                .unwrap()
+                .to_string()
+        };
+
+        assert!(output.contains("\nmoves vs gyarados\n"));
+        assert!(line("thunderbolt(s)").contains(" x4 "));
+        assert!(line("quick-attack ").contains(" x1 "));
+        assert!(!line("thunder-wave(s)").contains(" x"));
+    }
+
+    #[test]
+    fn learn_methods_are_unique() {
+        let learnable_moves = [
+            (String::from("thunderbolt"), String::from("level-up"), 26),
+            (String::from("thunder-shock"), String::from("level-up"), 1),
+            (String::from("thunderbolt"), String::from("machine"), 0),
+            (String::from("thunderbolt"), String::from("level-up"), 40),
+        ];
+        assert_eq!(
+            vec!["level-up", "machine"],
+            learn_methods(&learnable_moves, "thunderbolt")
+        );
+        assert!(learn_methods(&learnable_moves, "surf").is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_moves_explain() {
+        let config = config("scarlet-violet");
+        let pikachu = |move_name: &str| PokemonCommand {
+            name: String::from("pikachu"),
+            moves: true,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: Some(String::from(move_name)),
+        };
+
+        let output = run_command(pikachu("thunderbolt"), config.clone()).await;
+        assert!(output.contains("\npikachu learns thunderbolt in generation 9 by "));
+
+        // Return left the TMs in generation 8
+        let output = run_command(pikachu("return"), config.clone()).await;
+        let line = output.lines().last().unwrap();
+        assert!(line
+            .starts_with("pikachu does not learn return in generation 9; it does in generations "));
+        assert!(line.ends_with(", 7"));
+
+        let output = run_command(pikachu("flamethrower"), config).await;
+        assert!(output.ends_with("\npikachu does not learn flamethrower in any generation\n"));
+    }
+
     #[tokio::test]
     async fn run_pokemon_evolution() {
         let config = config("sword-shield");
@@ -456,6 +2280,22 @@ mod tests {
             name: String::from("cascoon"),
             moves: false,
             evolution: true,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
         };
         let cascoon_output = run_command(cascoon, config.clone()).await;
 
@@ -470,6 +2310,22 @@ mod tests {
             name: String::from("politoed"),
             moves: false,
             evolution: true,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
         };
         let politoed_output = run_command(politoed, config.clone()).await;
 
@@ -480,83 +2336,541 @@ mod tests {
             insta::assert_snapshot!(politoed_output);
         });
 
-        let applin = PokemonCommand {
-            name: String::from("applin"),
-            moves: false,
-            evolution: true,
+        let applin = PokemonCommand {
+            name: String::from("applin"),
+            moves: false,
+            evolution: true,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        let applin_output = run_command(applin, config.clone()).await;
+
+        insta::with_settings!({
+            description => "pokemon applin --evolution --game sword-shield",
+            omit_expression => true
+        }, {
+            insta::assert_snapshot!(applin_output);
+        });
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_moves() {
+        let config = config("scarlet-violet");
+        let blaziken = PokemonCommand {
+            name: String::from("blaziken"),
+            moves: true,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        let output = run_command(blaziken, config).await;
+
+        insta::with_settings!({
+            description => "pokemon blaziken --moves --game scarlet-violet",
+            omit_expression => true
+        }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+
+    #[tokio::test]
+    async fn run_type() {
+        let config = config("platinum");
+        let ice = TypeCommand {
+            primary_type: String::from("ice"),
+            secondary_type: None,
+            vs: vec![],
+            se_only: false,
+            bars: false,
+        };
+        let output = run_command(ice, config.clone()).await;
+
+        insta::with_settings!({
+            description => "type ice --game platinum",
+            omit_expression => true
+        }, {
+            insta::assert_snapshot!(output);
+        });
+
+        let ground_water = TypeCommand {
+            primary_type: String::from("ground"),
+            secondary_type: Some(String::from("water")),
+            vs: vec![],
+            se_only: false,
+            bars: false,
+        };
+        let output = run_command(ground_water, config.clone()).await;
+
+        insta::with_settings!({
+            description => "type ground water --game platinum",
+            omit_expression => true
+        }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+
+    #[tokio::test]
+    async fn run_type_bars() {
+        let ice = TypeCommand {
+            primary_type: String::from("ice"),
+            secondary_type: None,
+            vs: vec![],
+            se_only: false,
+            bars: true,
+        };
+        let output = run_command(ice, config("platinum")).await;
+
+        insta::with_settings!({
+            description => "type ice --bars --game platinum",
+            omit_expression => true
+        }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+
+    #[tokio::test]
+    async fn run_type_vs() {
+        let config = config("scarlet-violet");
+        let vs = |vs: &[&str]| TypeCommand {
+            primary_type: String::from("fire"),
+            secondary_type: None,
+            vs: vs.iter().map(|t| t.to_string()).collect(),
+            se_only: false,
+            bars: false,
+        };
+
+        let output = run_command(vs(&["water"]), config.clone()).await;
+        assert_eq!(
+            "offense\nfire vs water: x0.5\ndefense\nwater vs fire: x2\n",
+            output
+        );
+
+        let output = run_command(vs(&["fire", "flying"]), config.clone()).await;
+        assert_eq!(
+            "offense\nfire vs fire flying: x0.5\ndefense\nfire vs fire: x0.5\nflying vs fire: x1\n",
+            output
+        );
+
+        let mut writer = vec![];
+        let err = vs(&["watr"])
+            .run(config.clone(), &mut writer)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("water"));
+    }
+
+    #[tokio::test]
+    async fn run_type_se_only() {
+        let ground_water = TypeCommand {
+            primary_type: String::from("ground"),
+            secondary_type: Some(String::from("water")),
+            vs: vec![],
+            se_only: true,
+            bars: false,
+        };
+        let output = run_command(ground_water, config("platinum")).await;
+        assert_eq!(
+            "ground water super effective: electric fire ground poison rock steel\n",
+            output
+        );
+    }
+
+    #[tokio::test]
+    async fn run_type_coverage() {
+        let coverage = |types: &[&str]| TypeCoverageCommand {
+            types: types.iter().map(|t| t.to_string()).collect(),
+        };
+
+        let output = run_command(coverage(&["fire", "ground"]), config("scarlet-violet")).await;
+        assert!(output.starts_with("fire ground coverage\n"));
+        assert!(output.contains("\nflying water: x0.5\n"));
+        assert!(!output.contains("\nsteel"));
+
+        // Fairy is not a defending type before generation 6
+        let output = run_command(coverage(&["fire", "ground"]), config("black-white")).await;
+        assert!(!output.contains("fairy"));
+    }
+
+    #[tokio::test]
+    async fn run_type_custom_chart() {
+        let mut type_chart_path = current_dir().unwrap();
+        type_chart_path.push("configs/type_chart.yaml");
+        let mut config = config("scarlet-violet");
+        config.type_chart_path = Some(type_chart_path);
+
+        let steel = TypeCommand {
+            primary_type: String::from("steel"),
+            secondary_type: None,
+            vs: vec![String::from("fairy")],
+            se_only: false,
+            bars: false,
+        };
+        let output = run_command(steel, config.clone()).await;
+        assert_eq!(
+            "offense\nsteel vs fairy: x2\ndefense\nfairy vs steel: x1\n",
+            output
+        );
+
+        // Pokémon weaknesses follow the file as well
+        let mut writer = vec![];
+        let defender = PokemonCommand {
+            name: String::from("pikachu"),
+            moves: true,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: Some(String::from("klefki")),
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        defender.run(config, &mut writer).await.unwrap();
+        let output = String::from_utf8(writer).unwrap();
+        let play_rough = output
+            .lines()
+            .find(|l| l.starts_with("play-rough"))
+            .unwrap();
+        assert!(play_rough.contains(" x1 "));
+    }
+
+    #[tokio::test]
+    async fn run_pokemon_custom_chart_to() {
+        let mut type_chart_path = std::env::temp_dir();
+        type_chart_path.push(format!("dunspars-type-chart-{}.yaml", std::process::id()));
+        fs::write(&type_chart_path, "normal:\n  to:\n    ghost: 1\n").unwrap();
+        let mut config = config("scarlet-violet");
+        config.type_chart_path = Some(type_chart_path.clone());
+
+        // Normal's offense side makes ghost Pokémon no longer immune to it
+        let ceruledge = PokemonCommand {
+            name: String::from("ceruledge"),
+            moves: false,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
+        let output = run_command(ceruledge, config).await;
+        fs::remove_file(&type_chart_path).unwrap();
+        assert!(output.contains("zero (immune): fighting\n"));
+        assert!(output.contains("immune to: fighting\n"));
+    }
+
+    #[tokio::test]
+    async fn run_move() {
+        let config = config("sun-moon");
+        let brick_break = MoveCommand {
+            name: String::from("brick-break"),
+            verbose: true,
+            learners: false,
+            learn_method: None,
+            effect_only: false,
+            history: false,
+            generations: None,
+            effect_chance: None,
+        };
+        let output = run_command(brick_break, config).await;
+
+        insta::with_settings!({
+            description => "move brick-break --verbose --game sun-moon",
+            omit_expression => true
+        }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+
+    #[tokio::test]
+    async fn run_move_not_in_generation() {
+        let earth_power = MoveCommand {
+            name: String::from("earth-power"),
+            verbose: false,
+            learners: false,
+            learn_method: None,
+            effect_only: false,
+            history: false,
+            generations: None,
+            effect_chance: None,
+        };
+        let mut writer = vec![];
+        let err = earth_power
+            .run(config("ruby-sapphire"), &mut writer)
+            .await
+            .unwrap_err();
+        let err = err.to_string();
+        assert!(
+            err.starts_with("Move 'earth-power' is not present in generation 3"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_move_effect_chance() {
+        let config = config("scarlet-violet");
+        let flamethrower = |effect_chance| MoveCommand {
+            name: String::from("flamethrower"),
+            verbose: false,
+            learners: false,
+            learn_method: None,
+            effect_only: true,
+            history: false,
+            generations: None,
+            effect_chance,
+        };
+
+        let output = run_command(flamethrower(None), config.clone()).await;
+        assert!(output.contains("10% chance"));
+
+        // e.g. serene grace doubling the chance
+        let output = run_command(flamethrower(Some(20)), config.clone()).await;
+        assert!(output.contains("20% chance"));
+
+        let tackle = MoveCommand {
+            name: String::from("tackle"),
+            verbose: false,
+            learners: false,
+            learn_method: None,
+            effect_only: true,
+            history: false,
+            generations: None,
+            effect_chance: Some(20),
+        };
+        let mut writer = vec![];
+        let err = tackle.run(config, &mut writer).await.unwrap_err();
+        assert_eq!("tackle has no effect chance to override", err.to_string());
+    }
+
+    #[tokio::test]
+    async fn run_move_meta() {
+        let config = config("scarlet-violet");
+        let move_ = |name: &str| MoveCommand {
+            name: String::from(name),
+            verbose: false,
+            learners: false,
+            learn_method: None,
+            effect_only: false,
+            history: false,
+            generations: None,
+            effect_chance: None,
+        };
+
+        let output = run_command(move_("giga-drain"), config.clone()).await;
+        assert!(output.contains("meta (latest)\ndrains 50% of damage dealt\n"));
+
+        let output = run_command(move_("swords-dance"), config.clone()).await;
+        assert!(output.contains("meta (latest)\n+2 Atk to self\n"));
+
+        let crunch = MoveCommand {
+            effect_chance: Some(40),
+            ..move_("crunch")
+        };
+        let output = run_command(crunch, config).await;
+        assert!(output.contains("-1 Def to target (40% chance)"));
+    }
+
+    #[tokio::test]
+    async fn run_move_learners() {
+        let config = config("sword-shield");
+        let stealth_rock = |method: &str| MoveCommand {
+            name: String::from("stealth-rock"),
+            verbose: false,
+            learners: true,
+            learn_method: Some(String::from(method)),
+            effect_only: false,
+            history: false,
+            generations: None,
+            effect_chance: None,
         };
-        let applin_output = run_command(applin, config.clone()).await;
+        let output = run_command(stealth_rock("level-up"), config.clone()).await;
+        let (_, learners) = output.split_once("learners\n").unwrap();
 
-        insta::with_settings!({
-            description => "pokemon applin --evolution --game sword-shield",
-            omit_expression => true
-        }, {
-            insta::assert_snapshot!(applin_output);
-        });
+        assert!(learners.split_whitespace().any(|p| p == "onix"));
+
+        let mut writer = vec![];
+        let err = stealth_rock("levle-up")
+            .run(config, &mut writer)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("Learn method 'levle-up' not found. Potential matches: "),
+            "{err}"
+        );
+        assert!(writer.is_empty());
     }
 
     #[tokio::test]
-    async fn run_pokemon_moves() {
+    async fn run_pokemon_compact() {
         let config = config("scarlet-violet");
-        let blaziken = PokemonCommand {
-            name: String::from("blaziken"),
-            moves: true,
+        let compact = |name: &str| PokemonCommand {
+            name: String::from(name),
+            moves: false,
             evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: true,
+            assets: false,
+            bars: false,
+            explain: None,
         };
-        let output = run_command(blaziken, config).await;
 
+        let ceruledge = run_command(compact("ceruledge"), config.clone()).await;
         insta::with_settings!({
-            description => "pokemon blaziken --moves --game scarlet-violet",
+            description => "pokemon ceruledge --compact --game scarlet-violet",
             omit_expression => true
         }, {
-            insta::assert_snapshot!(output);
+            insta::assert_snapshot!(ceruledge);
         });
+
+        let pikachu = run_command(compact("pikachu"), config).await;
+        assert_eq!("pikachu electric 35/55/40/50/50/90 (320)\n", pikachu);
     }
 
     #[tokio::test]
-    async fn run_type() {
-        let config = config("platinum");
-        let ice = TypeCommand {
-            primary_type: String::from("ice"),
-            secondary_type: None,
+    async fn run_pokemon_assets() {
+        let config = config("scarlet-violet");
+        let assets = |format| PokemonCommand {
+            name: String::from("pikachu"),
+            moves: false,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: true,
+            bars: false,
+            explain: None,
         };
-        let output = run_command(ice, config.clone()).await;
-
-        insta::with_settings!({
-            description => "type ice --game platinum",
-            omit_expression => true
-        }, {
-            insta::assert_snapshot!(output);
-        });
 
-        let ground_water = TypeCommand {
-            primary_type: String::from("ground"),
-            secondary_type: Some(String::from("water")),
-        };
-        let output = run_command(ground_water, config.clone()).await;
+        let text = run_command(assets(OutputFormat::Text), config.clone()).await;
+        assert!(text.starts_with("sprite: https://"));
+        assert!(text.ends_with("/pokemon/25.png\n"));
 
-        insta::with_settings!({
-            description => "type ground water --game platinum",
-            omit_expression => true
-        }, {
-            insta::assert_snapshot!(output);
-        });
+        let json = run_command(assets(OutputFormat::Json), config).await;
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!("pikachu", json["name"]);
+        assert!(json["sprite"]
+            .as_str()
+            .unwrap()
+            .ends_with("/pokemon/25.png"));
     }
 
     #[tokio::test]
-    async fn run_move() {
-        let config = config("sun-moon");
+    async fn run_quiet() {
+        let quiet_config = |game: &str| Config {
+            quiet: true,
+            ..config(game)
+        };
+
+        let ceruledge = PokemonCommand {
+            name: String::from("ceruledge"),
+            moves: false,
+            evolution: false,
+            evolution_methods: true,
+            evolution_stages: false,
+            forms: false,
+            up_to_level: None,
+            include_tm: false,
+            format: OutputFormat::Text,
+            sort: None,
+            details: false,
+            vs: None,
+            tm: false,
+            ev_suggest: None,
+            form: None,
+            compact: false,
+            assets: false,
+            bars: false,
+            explain: None,
+        };
         let brick_break = MoveCommand {
             name: String::from("brick-break"),
+            verbose: false,
+            learners: false,
+            learn_method: None,
+            effect_only: false,
+            history: false,
+            generations: None,
+            effect_chance: None,
         };
-        let output = run_command(brick_break, config).await;
 
-        insta::with_settings!({
-            description => "move brick-break --game sun-moon",
-            omit_expression => true
-        }, {
-            insta::assert_snapshot!(output);
-        });
+        assert_eq!(
+            "ceruledge fire ghost\n",
+            run_command(ceruledge, quiet_config("scarlet-violet")).await
+        );
+        assert_eq!(
+            "power: 75   accuracy: 100  pp: 15 \n",
+            run_command(brick_break, quiet_config("sun-moon")).await
+        );
     }
 
     #[tokio::test]
@@ -564,11 +2878,14 @@ mod tests {
         let config = config("black-white");
         let intimidate = AbilityCommand {
             name: String::from("intimidate"),
+            verbose: true,
+            pokemon: false,
+            hidden_only: false,
         };
         let output = run_command(intimidate, config).await;
 
         insta::with_settings!({
-            description => "ability intimidate --game black-white",
+            description => "ability intimidate --verbose --game black-white",
             omit_expression => true
         }, {
             insta::assert_snapshot!(output);
@@ -580,9 +2897,13 @@ mod tests {
         let config = config("x-y");
         let non_verbose_cmd = MatchCommand {
             defender_names: vec![String::from("golem"), String::from("pachirisu")],
-            attacker_name: String::from("lapras"),
+            attacker_names: vec![String::from("lapras")],
+            attacker_types: vec![],
             verbose: false,
             stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
         };
         let stab_only_cmd = MatchCommand {
             stab_only: true,
@@ -619,14 +2940,342 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn run_match_terrain() {
+        let config = config("scarlet-violet");
+        let clear = MatchCommand {
+            defender_names: vec![String::from("snorlax")],
+            attacker_names: vec![String::from("pikachu")],
+            attacker_types: vec![],
+            verbose: true,
+            stab_only: true,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+        let electric_terrain = MatchCommand {
+            conditions: BattleConditions {
+                weather: None,
+                terrain: Some(Terrain::Electric),
+            },
+            ..clear.clone()
+        };
+
+        let clear = run_command(clear, config.clone()).await;
+        let electric_terrain = run_command(electric_terrain, config).await;
+        assert!(clear.contains("thunderbolt(s) "));
+        assert!(electric_terrain.contains("thunderbolt(s)x1.3 "));
+        assert_ne!(clear, electric_terrain);
+    }
+
+    #[tokio::test]
+    async fn run_match_multiple_attackers() {
+        let config = config("x-y");
+        let cmd = MatchCommand {
+            defender_names: vec![String::from("pachirisu")],
+            attacker_names: vec![String::from("lapras"), String::from("golem")],
+            attacker_types: vec![],
+            verbose: false,
+            stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+        let output = run_command(cmd, config).await;
+
+        let lapras_block = output
+            .find("[lapras]")
+            .expect("lapras should have a header");
+        let golem_block = output.find("[golem]").expect("golem should have a header");
+        assert!(lapras_block < golem_block);
+    }
+
+    #[tokio::test]
+    async fn run_match_sort() {
+        let config = config("x-y");
+        let cmd = |sort| MatchCommand {
+            defender_names: vec![
+                String::from("magikarp"),
+                String::from("golem"),
+                String::from("feebas"),
+            ],
+            attacker_names: vec![String::from("lapras")],
+            attacker_types: vec![],
+            verbose: false,
+            stab_only: false,
+            sort,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+        let block = |output: &str, name: &str| {
+            output
+                .find(&format!("{name}'s moves vs lapras"))
+                .expect("every defender should have a block")
+        };
+
+        // Golem's rock and fighting moves threaten lapras, magikarp and feebas have nothing
+        // super effective and keep their relative order
+        let unsorted = run_command(cmd(false), config.clone()).await;
+        assert!(block(&unsorted, "magikarp") < block(&unsorted, "golem"));
+        assert!(block(&unsorted, "golem") < block(&unsorted, "feebas"));
+
+        let sorted = run_command(cmd(true), config.clone()).await;
+        assert!(block(&sorted, "golem") < block(&sorted, "magikarp"));
+        assert!(block(&sorted, "magikarp") < block(&sorted, "feebas"));
+    }
+
+    #[tokio::test]
+    async fn run_match_sort_attackers() {
+        let config = config("x-y");
+        let cmd = MatchCommand {
+            defender_names: vec![
+                String::from("magikarp"),
+                String::from("golem"),
+                String::from("feebas"),
+            ],
+            attacker_names: vec![String::from("charizard"), String::from("lapras")],
+            attacker_types: vec![],
+            verbose: false,
+            stab_only: false,
+            sort: true,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+        let output = run_command(cmd, config).await;
+        let (charizard, lapras) = output.split_once("[lapras]\n").unwrap();
+        let block = |output: &str, name: &str, attacker: &str| {
+            output
+                .find(&format!("{name}'s moves vs {attacker}"))
+                .expect("every defender should have a block")
+        };
+
+        // Feebas' water moves threaten charizard, which puts it ahead of magikarp
+        assert!(
+            block(charizard, "feebas", "charizard") < block(charizard, "magikarp", "charizard")
+        );
+        // Neither threatens lapras, so they go back to their input order
+        assert!(block(lapras, "golem", "lapras") < block(lapras, "magikarp", "lapras"));
+        assert!(block(lapras, "magikarp", "lapras") < block(lapras, "feebas", "lapras"));
+    }
+
+    #[test]
+    fn parse_matchup_lines() {
+        let (defenders, attacker) = parse_matchup_line("golem,pachirisu  lapras").unwrap();
+        assert_eq!(vec!["golem", "pachirisu"], defenders);
+        assert_eq!("lapras", attacker);
+
+        let (defenders, _) = parse_matchup_line("golem, lapras").unwrap();
+        assert_eq!(vec!["golem"], defenders);
+
+        parse_matchup_line("golem pachirisu lapras").unwrap_err();
+        parse_matchup_line("lapras").unwrap_err();
+        parse_matchup_line("a,b,c,d,e,f,g lapras").unwrap_err();
+    }
+
+    #[test]
+    fn resource_paginate() {
+        let names = (1..=7).map(|n| n.to_string()).collect::<Vec<String>>();
+        let resource = |page| ResourceCommand {
+            resource: ResourceArgs::Pokemon,
+            delimiter: None,
+            page,
+            page_size: 3,
+            columns: false,
+            order: None,
+            current_gen: false,
+        };
+
+        assert_eq!(names, resource(None).paginate(names.clone()).unwrap());
+        assert_eq!(
+            vec!["4", "5", "6"],
+            resource(Some(2)).paginate(names.clone()).unwrap()
+        );
+        assert_eq!(
+            vec!["7"],
+            resource(Some(3)).paginate(names.clone()).unwrap()
+        );
+
+        let err = resource(Some(4)).paginate(names.clone()).unwrap_err();
+        assert_eq!(
+            "Page 4 is out of range; there are 3 pages.",
+            err.to_string()
+        );
+        resource(Some(0)).paginate(names).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn run_resource_order() {
+        let resource = |order| ResourceCommand {
+            resource: ResourceArgs::Pokemon,
+            delimiter: Some(String::from(",")),
+            page: None,
+            page_size: 50,
+            columns: false,
+            order: Some(order),
+            current_gen: false,
+        };
+
+        let dex = run_command(resource(ResourceOrder::Dex), config("x-y")).await;
+        let dex = dex.trim_end().split(',').collect::<Vec<&str>>();
+        let position = |name| dex.iter().position(|p| *p == name).unwrap();
+        assert!(position("venusaur") < position("venusaur-mega"));
+        assert!(position("venusaur-mega") < position("charmander"));
+
+        let names = run_command(resource(ResourceOrder::Name), config("x-y")).await;
+        assert!(names.starts_with("abomasnow,abomasnow-mega,"));
+
+        let bst = run_command(resource(ResourceOrder::Bst), config("x-y")).await;
+        assert!(bst.starts_with("eternatus-eternamax,"));
+    }
+
+    #[tokio::test]
+    async fn run_resource_current_gen() {
+        let resource = |resource| ResourceCommand {
+            resource,
+            delimiter: Some(String::from(",")),
+            page: None,
+            page_size: 50,
+            columns: false,
+            order: None,
+            current_gen: true,
+        };
+
+        let pokemon = run_command(resource(ResourceArgs::Pokemon), config("red-blue")).await;
+        let pokemon = pokemon.trim_end().split(',').collect::<Vec<&str>>();
+        assert!(pokemon.contains(&"mew"));
+        assert!(!pokemon.contains(&"chikorita"));
+
+        let moves = run_command(resource(ResourceArgs::Moves), config("red-blue")).await;
+        let moves = moves.trim_end().split(',').collect::<Vec<&str>>();
+        assert!(moves.contains(&"thunderbolt"));
+        assert!(!moves.contains(&"shadow-ball"));
+
+        let types = run_command(resource(ResourceArgs::Types), config("red-blue")).await;
+        assert!(!types
+            .trim_end()
+            .split(',')
+            .any(|t| t == "steel" || t == "dark"));
+
+        let chains = run_command(resource(ResourceArgs::Evolutions), config("red-blue")).await;
+        let chains = chains.trim_end().split(',').collect::<Vec<&str>>();
+        assert!(chains.contains(&"bulbasaur"));
+        assert!(!chains.contains(&"chikorita"));
+    }
+
+    #[tokio::test]
+    async fn run_match_duplicates() {
+        let cmd = MatchCommand {
+            defender_names: ["golem", "pachirisu", "golem"].map(String::from).to_vec(),
+            attacker_names: vec![String::from("lapras")],
+            attacker_types: vec![],
+            verbose: false,
+            stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+        let output = run_command(cmd, config("x-y")).await;
+        assert!(output.starts_with("Duplicate defenders are listed once: golem\n"));
+        assert_eq!(1, output.matches("lapras's moves vs golem").count());
+        assert!(!output.contains("mirror matchup"));
+
+        let mirror = MatchCommand {
+            defender_names: vec![String::from("golem")],
+            attacker_names: vec![String::from("golem")],
+            attacker_types: vec![],
+            verbose: false,
+            stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+        let output = run_command(mirror, config("x-y")).await;
+        assert!(output.starts_with("mirror matchup: golem vs itself\n"));
+    }
+
+    #[tokio::test]
+    async fn run_match_attacker_type() {
+        let cmd = MatchCommand {
+            defender_names: vec![String::from("golem")],
+            attacker_names: vec![],
+            attacker_types: vec![String::from("water"), String::from("elec")],
+            verbose: false,
+            stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+        let output = run_command(cmd, config("sword-shield")).await;
+
+        assert!(output.contains("water/electric's stab vs golem\nwater: x4\nelectric: x0"));
+        assert!(output.contains("golem's moves vs water/electric"));
+    }
+
+    #[tokio::test]
+    async fn run_match_file() {
+        let mut path = current_dir().expect("the current directory should be accessible");
+        path.push("configs/matchups.txt");
+
+        let config = config("x-y");
+        let match_file = MatchFileCommand {
+            path,
+            verbose: false,
+            stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+        let output = run_command(match_file, config).await;
+
+        insta::with_settings!({
+            description => "match --from configs/matchups.txt --game x-y",
+            omit_expression => true
+        }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+
+    #[tokio::test]
+    async fn run_match_file_invalid_line() {
+        let mut path = std::env::temp_dir();
+        path.push("dunspars_run_match_file_invalid_line.txt");
+        fs::write(&path, "golme lapras\ngolem,pachirisu lapras\n").unwrap();
+
+        let config = config("x-y");
+        let match_file = MatchFileCommand {
+            path: path.clone(),
+            verbose: false,
+            stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
+        };
+
+        let mut writer = vec![];
+        let result = match_file.run(config, &mut writer).await;
+        // Removed before any assertion so that a failing run does not leave it behind
+        fs::remove_file(&path).unwrap();
+        let status_code = result.unwrap();
+        let output = String::from_utf8(writer).unwrap();
+
+        // An invalid line is reported without aborting the rest of the batch
+        assert_eq!(1, status_code);
+        assert!(output.contains("[1] golme lapras\n\nPokémon 'golme' not found."));
+        assert!(output.contains("[2] golem,pachirisu lapras\n\ngolem"));
+    }
+
     #[tokio::test]
     async fn run_match_custom() {
         let config = config("the-indigo-disk");
         let custom_cmd = MatchCommand {
             defender_names: vec![String::from("ramza")],
-            attacker_name: String::from("crawford"),
+            attacker_names: vec![String::from("crawford")],
+            attacker_types: vec![],
             verbose: true,
             stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![],
         };
         let custom = run_command(custom_cmd, config.clone()).await;
 
@@ -650,6 +3299,13 @@ mod tests {
                 String::from("dudunsparce"),
                 String::from("sinistcha"),
             ],
+            sort: false,
+            min: None,
+            include_overlap: false,
+            summary: false,
+            exclude: vec![],
+            synergy: false,
+            top_moves: None,
         };
 
         let output = run_command(coverage, config).await;
@@ -662,11 +3318,69 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn run_coverage_top_moves_summary() {
+        let config = config("scarlet-violet");
+        let coverage = CoverageCommand {
+            names: vec![String::from("pikachu")],
+            sort: false,
+            min: None,
+            include_overlap: false,
+            summary: true,
+            exclude: vec![],
+            synergy: false,
+            top_moves: Some(4),
+        };
+
+        let output = run_command(coverage, config).await;
+        let offense = output.split("defense coverage").next().unwrap();
+        // Only pikachu's electric moves are STAB, and electric is only super effective on these
+        let covered = offense
+            .lines()
+            .filter(|line| line.contains(": "))
+            .filter(|line| !line.starts_with("water") && !line.starts_with("flying"))
+            .collect::<Vec<&str>>();
+        assert!(!covered.is_empty());
+        assert!(covered.iter().all(|line| !line.contains("stab")));
+        assert!(offense.contains("water: 1 stab"));
+    }
+
+    #[tokio::test]
+    async fn run_coverage_sorted() {
+        let config = config("the-indigo-disk");
+        let coverage = CoverageCommand {
+            names: vec![String::from("crawford"), String::from("ramza")],
+            sort: true,
+            min: Some(2),
+            include_overlap: false,
+            summary: false,
+            exclude: vec![],
+            synergy: false,
+            top_moves: None,
+        };
+
+        let output = run_command(coverage, config).await;
+
+        insta::with_settings!({
+            description => "coverage crawford ramza --sort --min 2 --game the-indigo-disk",
+            omit_expression => true
+        }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+
     #[tokio::test]
     async fn run_coverage_custom() {
         let config = config("the-indigo-disk");
         let coverage = CoverageCommand {
             names: vec![String::from("crawford"), String::from("ramza")],
+            sort: false,
+            min: None,
+            include_overlap: false,
+            summary: false,
+            exclude: vec![],
+            synergy: false,
+            top_moves: None,
         };
 
         let output = run_command(coverage, config).await;
@@ -678,4 +3392,263 @@ mod tests {
             insta::assert_snapshot!(output);
         });
     }
+
+    #[tokio::test]
+    async fn run_coverage_overlap() {
+        let config = config("the-indigo-disk");
+        let coverage = |include_overlap| CoverageCommand {
+            names: vec![String::from("pikachu")],
+            sort: false,
+            min: None,
+            include_overlap,
+            summary: false,
+            exclude: vec![],
+            synergy: false,
+            top_moves: None,
+        };
+
+        let output = run_command(coverage(false), config.clone()).await;
+        assert!(output.contains("\nfire\n"));
+        assert!(output.contains("\nground\n"));
+
+        // Pikachu hits fire neutrally but cannot touch ground with its typing
+        let output = run_command(coverage(true), config).await;
+        assert!(output.contains("\nfire: pikachu (electric)\n"));
+        assert!(output.contains("\nground\n"));
+        assert!(output.contains("\nwater: pikachu (electric)\n"));
+    }
+
+    #[tokio::test]
+    async fn run_coverage_summary() {
+        let config = config("the-indigo-disk");
+        let coverage = CoverageCommand {
+            names: vec![String::from("crawford"), String::from("ramza")],
+            sort: false,
+            min: None,
+            include_overlap: false,
+            summary: true,
+            exclude: vec![],
+            synergy: false,
+            top_moves: None,
+        };
+        let output = run_command(coverage, config).await;
+
+        // Crawford's drill-peck is STAB against fighting; ramza's drill-run covers electric without it
+        assert!(output.contains("\nfighting: 1 stab\n"));
+        assert!(output.contains("\nelectric: 1\n"));
+        assert!(!output.contains("(drill-peck+)"));
+    }
+
+    #[tokio::test]
+    async fn run_coverage_synergy() {
+        let synergy = |names: &[&str]| CoverageCommand {
+            names: names.iter().map(|n| n.to_string()).collect(),
+            sort: false,
+            min: None,
+            include_overlap: false,
+            summary: false,
+            exclude: vec![],
+            synergy: true,
+            top_moves: None,
+        };
+
+        let output = run_command(
+            synergy(&["charizard", "blastoise"]),
+            config("scarlet-violet"),
+        )
+        .await;
+        assert!(output.starts_with(
+            "charizard with blastoise\nweaknesses blastoise resists\n[ ] electric\n[ ] rock\n[x] water\n"
+        ));
+        assert!(output.contains("blastoise with charizard\n"));
+        assert!(output.contains("\n[x] grass\n"));
+
+        let err = synergy(&["charizard"])
+            .run(config("scarlet-violet"), &mut vec![])
+            .await
+            .unwrap_err();
+        assert_eq!("--synergy compares exactly two Pokémon", err.to_string());
+    }
+
+    #[tokio::test]
+    async fn run_exclude_moves() {
+        let config = config("the-indigo-disk");
+        let coverage = CoverageCommand {
+            names: vec![String::from("crawford"), String::from("ramza")],
+            sort: false,
+            min: None,
+            include_overlap: false,
+            summary: true,
+            exclude: vec![String::from("drill-peck")],
+            synergy: false,
+            top_moves: None,
+        };
+        let output = run_command(coverage, config.clone()).await;
+        assert!(!output.contains("\nfighting: 1 stab\n"));
+
+        let match_ = |exclude: &str| MatchCommand {
+            defender_names: vec![String::from("ramza")],
+            attacker_names: vec![String::from("crawford")],
+            attacker_types: vec![],
+            verbose: true,
+            stab_only: false,
+            sort: false,
+            conditions: BattleConditions::default(),
+            exclude: vec![String::from(exclude)],
+        };
+        let output = run_command(match_("flamethrower"), config.clone()).await;
+        assert!(output.contains("drill-run("));
+        assert!(!output.contains("flamethrower("));
+
+        let err = match_("flamethrowr")
+            .run(config.clone(), &mut vec![])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("flamethrower"));
+    }
+
+    #[tokio::test]
+    async fn run_data_version() {
+        let config = config("x-y");
+        let output = run_command(DataVersionCommand, config).await;
+
+        assert!(output.contains("\nbuilt by: dunspars "));
+        assert!(output.contains("\nmachines: "));
+        assert!(output.contains("games: "));
+        assert!(output.contains("(gen-1 to gen-"));
+    }
+
+    #[tokio::test]
+    async fn run_config_all() {
+        let config = config("x-y");
+        let config_all = ConfigCommand {
+            key: None,
+            value: None,
+            unset: false,
+            all: true,
+        };
+        let output = run_command(config_all, config).await;
+
+        assert!(output.contains("game: x-y (default: latest game)\n"));
+        assert!(output.contains("color: false (default: auto)\n"));
+        assert!(output.contains("db_path: (unset) (default: "));
+    }
+
+    fn custom_config(dir: &str) -> Config {
+        let mut custom_path = std::env::temp_dir();
+        custom_path.push(format!("{dir}/custom.yaml"));
+        let _ = fs::remove_file(&custom_path);
+
+        let mut config = config("scarlet-violet");
+        config.custom_path = Some(custom_path);
+        config
+    }
+
+    #[tokio::test]
+    async fn run_custom_list_remove() {
+        let config = custom_config("dunspars_run_custom_list_remove");
+        let custom_file = CustomFile::from_config(&config);
+        let mut custom = CustomCollection::default();
+        custom
+            .add(CustomPokemon {
+                nickname: String::from("kenji"),
+                base: String::from("garchomp"),
+                generation: 4,
+                moves: vec![String::from("earthquake")],
+                types: None,
+                stats: Default::default(),
+            })
+            .unwrap();
+        custom_file.save(custom).unwrap();
+
+        let output = run_command(CustomListCommand, config.clone()).await;
+        assert_eq!("kenji  garchomp  gen-4\n", output);
+
+        let remove = || CustomRemoveCommand {
+            nickname: String::from("Kenji"),
+        };
+        let output = run_command(remove(), config.clone()).await;
+        assert!(output.starts_with("Removed kenji from "));
+
+        let output = run_command(CustomListCommand, config.clone()).await;
+        assert_eq!("There are no custom Pokémon.\n", output);
+
+        let err = remove().run(config, &mut vec![]).await.unwrap_err();
+        assert_eq!("Custom Pokémon 'Kenji' not found.", err.to_string());
+    }
+
+    #[tokio::test]
+    async fn run_custom_add() {
+        let config = custom_config("dunspars_run_custom_add");
+        let add = |moves: &[&str]| CustomAddCommand {
+            nickname: String::from("sparky"),
+            base: String::from("Pikachu"),
+            moves: moves.iter().map(|m| m.to_string()).collect(),
+            types: vec![String::from("elec"), String::from("fairy")],
+            generation: None,
+        };
+
+        // Invalid moves are reported and nothing is written
+        let err = add(&["thunderbolt", "thunderbolf"])
+            .run(config.clone(), &mut vec![])
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Move 'thunderbolf' not found."), "{err}");
+        assert!(!config.custom_path.as_ref().unwrap().exists());
+
+        let output = run_command(add(&["thunderbolt", "Play Rough"]), config.clone()).await;
+        assert!(output.starts_with("Added sparky to "));
+        let custom = CustomFile::from_config(&config).read().unwrap();
+        let sparky = custom.find_pokemon("sparky").unwrap();
+        assert_eq!("pikachu", sparky.base);
+        assert_eq!(9, sparky.generation);
+        assert_eq!(vec!["thunderbolt", "play-rough"], sparky.moves);
+
+        let err = add(&["thunderbolt"])
+            .run(config, &mut vec![])
+            .await
+            .unwrap_err();
+        assert_eq!("Custom Pokémon 'sparky' already exists", err.to_string());
+    }
+
+    #[tokio::test]
+    async fn run_config_set_invalid() {
+        let mut config_path = std::env::temp_dir();
+        config_path.push("dunspars_run_config_set_invalid/config.yaml");
+        let config = ConfigBuilder::default()
+            .config_path(config_path)
+            .build()
+            .unwrap();
+
+        let unknown_key = ConfigCommand {
+            key: Some(String::from("colour")),
+            value: Some(String::from("true")),
+            unset: false,
+            all: false,
+        };
+        let err = unknown_key
+            .run(config.clone(), &mut vec![])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            "Config key 'colour' not found. Potential matches: color.",
+            err.to_string()
+        );
+
+        let non_bool_color = ConfigCommand {
+            key: Some(String::from("color")),
+            value: Some(String::from("sometimes")),
+            unset: false,
+            all: false,
+        };
+        let err = non_bool_color
+            .run(config.clone(), &mut vec![])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            "Config 'color' must be either 'true' or 'false'",
+            err.to_string()
+        );
+    }
 }
@@ -1,24 +1,49 @@
 mod ability;
+mod availability;
+mod columns;
 mod coverage;
+mod encounter_list;
 mod evolution_step;
+mod forms;
 mod match_;
 mod move_;
 mod move_list;
 mod move_weakness;
 mod pokemon;
 mod stats;
+mod synergy;
 mod typechart;
 mod weakness;
 
+pub use ability::{AbilityComponent, AbilityPokemonComponent};
+pub use availability::{AvailabilityComponent, AvailabilityMatrixComponent};
+pub use columns::ColumnsComponent;
 pub use coverage::CoverageComponent;
+pub use encounter_list::EncounterListComponent;
+pub use forms::FormsComponent;
 pub use match_::MatchComponent;
+pub use move_::{MoveComponent, MoveHistoryComponent, MoveLearnersComponent, MoveMetaComponent};
 pub use move_list::MoveListComponent;
 pub use move_weakness::MoveWeaknessComponent;
-pub use typechart::TypeChartComponent;
-use weakness::WeaknessDisplay;
+pub use pokemon::{PokemonComponent, PokemonListComponent};
+pub use stats::StatsComponent;
+pub use synergy::SynergyComponent;
+pub use typechart::{
+    SuperEffectiveComponent, TypeChartComponent, TypeCoverageComponent, TypeMatchupComponent,
+};
+use weakness::{nearest_multiplier, WeaknessDisplay};
 
 use super::utils::is_color_enabled;
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Markdown,
+    Dot,
+    Json,
+}
+
 pub struct DisplayComponent<T> {
     context: T,
     color_enabled: Option<bool>,
@@ -48,10 +73,64 @@ impl<T> DisplayComponent<T> {
         self.style().fg(color).effect(Effects::Bold).ansi()
     }
 
+    fn ansi_dim(&self) -> anstyle::Style {
+        self.style().effect(Effects::Dim).ansi()
+    }
+
     #[allow(dead_code)]
     fn ansi_underline(&self, color: Colors) -> anstyle::Style {
         self.style().fg(color).effect(Effects::Underline).ansi()
     }
+
+    fn power_ansi(&self, power: Option<i64>) -> anstyle::Style {
+        // 150 is the ceiling for the vast majority of damaging moves
+        let color = power.map_or(Colors::Red, |power| Colors::rate(power, 150));
+        self.ansi(color)
+    }
+
+    fn accuracy_ansi(&self, accuracy: Option<i64>) -> anstyle::Style {
+        let color = accuracy.map_or(Colors::Green, Colors::rate_accuracy);
+        self.ansi(color)
+    }
+
+    // Colored like the matching group in type charts
+    fn multiplier_label(&self, multiplier: f32) -> String {
+        let color = self.ansi(Colors::multiplier(multiplier));
+        format!("{color}x{multiplier}{color:#}")
+    }
+}
+
+// Length of a string without its ansi escape codes
+fn visible_len(text: &str) -> usize {
+    let mut len = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        match c {
+            '\u{1b}' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => len += 1,
+        }
+    }
+    len
+}
+
+// Pipes would otherwise split a Markdown table cell
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn markdown_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut table = format!("| {} |\n", headers.join(" | "));
+    table += &format!("|{}\n", " --- |".repeat(headers.len()));
+    for row in rows {
+        let cells = row
+            .iter()
+            .map(|cell| markdown_cell(cell))
+            .collect::<Vec<String>>();
+        table += &format!("| {} |\n", cells.join(" | "));
+    }
+    table
 }
 
 #[derive(Debug, PartialEq)]
@@ -67,6 +146,18 @@ enum Colors {
 }
 
 impl Colors {
+    fn multiplier(multiplier: f32) -> Self {
+        match multiplier {
+            4.0 => Colors::Red,
+            2.0 => Colors::Orange,
+            1.0 => Colors::Green,
+            0.5 => Colors::Cyan,
+            0.25 => Colors::Blue,
+            0.0 => Colors::Violet,
+            _ => Colors::Yellow,
+        }
+    }
+
     fn rate(number: i64, ceiling: i64) -> Self {
         let number = number as f64;
         let ceiling = ceiling as f64;
@@ -81,6 +172,16 @@ impl Colors {
         }
     }
 
+    // Unlike rate(), lower values are worse; unreliable moves stand out in red
+    fn rate_accuracy(accuracy: i64) -> Self {
+        match accuracy {
+            accuracy if accuracy >= 100 => Colors::Green,
+            accuracy if accuracy >= 90 => Colors::Yellow,
+            accuracy if accuracy >= 75 => Colors::Orange,
+            _ => Colors::Red,
+        }
+    }
+
     fn get(&self) -> Option<anstyle::Color> {
         match self {
             Colors::Header => Some(anstyle::Ansi256Color(10).into()),
@@ -97,6 +198,7 @@ impl Colors {
 
 enum Effects {
     Bold,
+    Dim,
     Underline,
 }
 
@@ -104,6 +206,7 @@ impl Effects {
     fn get(&self) -> anstyle::Effects {
         match self {
             Effects::Bold => anstyle::Effects::BOLD,
+            Effects::Dim => anstyle::Effects::DIMMED,
             Effects::Underline => anstyle::Effects::UNDERLINE,
         }
     }
@@ -174,6 +277,37 @@ mod tests {
         assert_eq!(Colors::Violet, Colors::rate(10, 100));
     }
 
+    #[test]
+    fn colors_rate_accuracy() {
+        assert_eq!(Colors::Green, Colors::rate_accuracy(100));
+        assert_eq!(Colors::Yellow, Colors::rate_accuracy(95));
+        assert_eq!(Colors::Orange, Colors::rate_accuracy(80));
+        assert_eq!(Colors::Red, Colors::rate_accuracy(50));
+    }
+
+    #[test]
+    fn visible_len_ignores_ansi() {
+        let red = Style::new(true).fg(Colors::Red).ansi();
+        let bold = Style::new(true)
+            .fg(Colors::Header)
+            .effect(Effects::Bold)
+            .ansi();
+        assert_eq!(
+            12,
+            visible_len(&format!("{red}power{red:#}: {bold}100{bold:#}  "))
+        );
+        assert_eq!(5, visible_len("plain"));
+    }
+
+    #[test]
+    fn markdown_table_escapes_pipes() {
+        let table = markdown_table(
+            &["name", "effect"],
+            vec![vec![String::from("a|b"), String::from("c")]],
+        );
+        assert_eq!("| name | effect |\n| --- | --- |\n| a\\|b | c |\n", table);
+    }
+
     #[test]
     fn colors_ansi() {
         let orange = Style::new(false).fg(Colors::Orange).ansi();
@@ -5,9 +5,21 @@ use std::fmt;
 
 use indoc::writedoc;
 
-impl fmt::Display for DisplayComponent<&Ability> {
+pub struct AbilityComponent<'a> {
+    pub ability: &'a Ability,
+    pub verbose: bool,
+}
+
+impl fmt::Display for DisplayComponent<AbilityComponent<'_>> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let Ability { name, effect, .. } = self.context;
+        let AbilityComponent { ability, verbose } = self.context;
+        let Ability {
+            name,
+            effect,
+            short_effect,
+            ..
+        } = ability;
+        let effect = if verbose { effect } else { short_effect };
 
         writedoc! {
             f,
@@ -17,3 +29,57 @@ impl fmt::Display for DisplayComponent<&Ability> {
         }
     }
 }
+
+pub struct AbilityPokemonComponent<'a> {
+    pub pokemon: &'a [(String, bool)],
+}
+
+impl fmt::Display for DisplayComponent<AbilityPokemonComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{header}pokemon{header:#}",
+            header = self.ansi_bold(Colors::Header)
+        )?;
+
+        let AbilityPokemonComponent { pokemon } = self.context;
+        if pokemon.is_empty() {
+            return write!(f, "There are no Pokémon to display.");
+        }
+
+        let pokemon = pokemon
+            .iter()
+            .map(|(name, is_hidden)| {
+                let hidden = if *is_hidden { "(h)" } else { "" };
+                format!(
+                    "{green}{name}{green:#}{hidden}",
+                    green = self.ansi(Colors::Green)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "{pokemon}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_ability_pokemon() {
+        let pokemon = vec![
+            (String::from("ekans"), false),
+            (String::from("litleo"), true),
+        ];
+        let display =
+            DisplayComponent::new(AbilityPokemonComponent { pokemon: &pokemon }, Some(false));
+        assert_eq!("pokemon\nekans litleo(h)", display.to_string());
+
+        let display = DisplayComponent::new(AbilityPokemonComponent { pokemon: &[] }, Some(false));
+        assert_eq!(
+            "pokemon\nThere are no Pokémon to display.",
+            display.to_string()
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use super::{Colors, DisplayComponent};
+
+use std::fmt;
+
+pub struct AvailabilityComponent<'a> {
+    pub name: &'a str,
+    // Games grouped by generation, oldest first
+    pub availability: &'a [(u8, Vec<String>)],
+}
+
+impl fmt::Display for DisplayComponent<AvailabilityComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let AvailabilityComponent { name, availability } = self.context;
+        write!(
+            f,
+            "{header}availability{header:#} {name}",
+            header = self.ansi_bold(Colors::Header)
+        )?;
+
+        if availability.is_empty() {
+            return write!(f, "\n{name} is not present in any game.");
+        }
+
+        for (generation, games) in availability {
+            write!(
+                f,
+                "\ngen-{generation}  {green}{games}{green:#}",
+                games = games.join(" "),
+                green = self.ansi(Colors::Green)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Types and base stat total per generation, or None when absent from it
+type Matrix = [(u8, Option<(Vec<String>, i64)>)];
+
+pub struct AvailabilityMatrixComponent<'a> {
+    pub name: &'a str,
+    pub matrix: &'a Matrix,
+}
+
+impl fmt::Display for DisplayComponent<AvailabilityMatrixComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let AvailabilityMatrixComponent { name, matrix } = self.context;
+        write!(
+            f,
+            "{header}availability matrix{header:#} {name}",
+            header = self.ansi_bold(Colors::Header)
+        )?;
+
+        let typing_width = matrix
+            .iter()
+            .filter_map(|(_, entry)| entry.as_ref())
+            .map(|(types, _)| types.join("/").len())
+            .max()
+            .unwrap_or(0);
+
+        for (generation, entry) in matrix {
+            match entry {
+                Some((types, total)) => write!(
+                    f,
+                    "\ngen-{generation}  {green}{typing:typing_width$}{green:#}  {total}",
+                    typing = types.join("/"),
+                    green = self.ansi(Colors::Green)
+                )?,
+                None => write!(
+                    f,
+                    "\ngen-{generation}  {dim}-{dim:#}",
+                    dim = self.ansi_dim()
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_availability() {
+        let availability = vec![
+            (
+                3,
+                vec![String::from("ruby-sapphire"), String::from("emerald")],
+            ),
+            (8, vec![String::from("sword-shield")]),
+        ];
+        let display = DisplayComponent::new(
+            AvailabilityComponent {
+                name: "wailord",
+                availability: &availability,
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "availability wailord\ngen-3  ruby-sapphire emerald\ngen-8  sword-shield",
+            display.to_string()
+        );
+
+        let display = DisplayComponent::new(
+            AvailabilityComponent {
+                name: "missingno",
+                availability: &[],
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "availability missingno\nmissingno is not present in any game.",
+            display.to_string()
+        );
+    }
+
+    #[test]
+    fn display_availability_matrix() {
+        let matrix = vec![
+            (5, None),
+            (6, Some((vec![String::from("normal")], 323))),
+            (
+                7,
+                Some((vec![String::from("fire"), String::from("ghost")], 525)),
+            ),
+        ];
+        let display = DisplayComponent::new(
+            AvailabilityMatrixComponent {
+                name: "clefairy",
+                matrix: &matrix,
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "availability matrix clefairy\ngen-5  -\ngen-6  normal      323\ngen-7  fire/ghost  525",
+            display.to_string()
+        );
+    }
+}
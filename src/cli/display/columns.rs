@@ -0,0 +1,64 @@
+use super::DisplayComponent;
+
+use std::fmt;
+
+pub struct ColumnsComponent<'a> {
+    pub names: &'a [String],
+    pub width: usize,
+}
+
+impl fmt::Display for DisplayComponent<ColumnsComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ColumnsComponent { names, width } = self.context;
+        let column_width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0) + 2;
+        let columns = (width / column_width).max(1);
+
+        let rows = names
+            .chunks(columns)
+            .map(|row| {
+                row.iter()
+                    .map(|name| format!("{name:column_width$}"))
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_columns() {
+        let names = ["bulbasaur", "ivysaur", "venusaur", "charmander", "mew"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<String>>();
+
+        let display = DisplayComponent::new(
+            ColumnsComponent {
+                names: &names,
+                width: 40,
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "bulbasaur   ivysaur     venusaur\ncharmander  mew",
+            display.to_string()
+        );
+
+        // A column too wide for the terminal falls back to one name per line
+        let display = DisplayComponent::new(
+            ColumnsComponent {
+                names: &names,
+                width: 5,
+            },
+            Some(false),
+        );
+        assert_eq!(names.join("\n"), display.to_string());
+    }
+}
@@ -1,59 +1,95 @@
 use super::{Colors, DisplayComponent};
 use crate::cli::utils::is_stab;
-use crate::models::{FromDb, Move, Pokemon, Type, TypeChart, TypeCharts, TYPES};
+use crate::models::db::Db;
+use crate::models::{Move, Pokemon, TypeChart, TypeCharts, TYPES};
+use crate::resource::type_chart::TypeChartCollection;
 
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::fmt;
 
-use rusqlite::Connection;
-
 pub struct CoverageComponent<'a> {
     pub pokemon: &'a Vec<Pokemon>,
-    pub db: &'a Connection,
+    pub db: &'a Db,
+    pub sort: bool,
+    pub min: Option<usize>,
+    pub include_overlap: bool,
+    pub summary: bool,
+    // Moves left out of custom Pokémon's and top moves' offense coverage
+    pub exclude: &'a HashSet<String>,
+    // Non-custom Pokémon use this many of their strongest learnable moves instead of their types
+    pub top_moves: Option<usize>,
+    pub type_charts: &'a TypeChartCollection,
 }
 
+type Coverage = HashMap<String, Vec<(String, String)>>;
+
 impl fmt::Display for DisplayComponent<CoverageComponent<'_>> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let (offense_coverage, defense_coverage) = self.build_coverages();
+        let (offense_coverage, defense_coverage, neutral) = self.build_coverages();
+        let (neutral_offense, neutral_defense) = neutral.unzip();
         let header = self.ansi_bold(Colors::Header);
 
         writeln!(f, "{header}offense coverage{header:#}")?;
-        self.write_coverage(f, offense_coverage)?;
+        if self.context.summary {
+            self.write_summary(f, offense_coverage)?;
+        } else {
+            self.write_coverage(f, offense_coverage, neutral_offense)?;
+        }
 
         writeln!(f, "\n{header}defense coverage{header:#}")?;
-        self.write_coverage(f, defense_coverage)?;
+        self.write_coverage(f, defense_coverage, neutral_defense)?;
 
         Ok(())
     }
 }
 
 impl DisplayComponent<CoverageComponent<'_>> {
+    // Neutral coverage, when given, is listed dimmed after the advantageous coverage.
+    // Types only covered neutrally are yellow; types nobody covers remain red.
     fn write_coverage(
         &self,
         f: &mut fmt::Formatter,
-        mut coverage: HashMap<String, Vec<String>>,
+        mut coverage: Coverage,
+        neutral: Option<Coverage>,
     ) -> fmt::Result {
-        let mut types = coverage
-            .iter()
-            .map(|t| t.0.clone())
-            .collect::<Vec<String>>();
-        types.sort();
+        let CoverageComponent { sort, min, .. } = self.context;
+        let types = Self::order_types(&coverage, sort, min);
 
         for type_ in types {
             let pokemon = coverage.get_mut(&type_).unwrap();
+            let neutral_by = neutral
+                .as_ref()
+                .map(|neutral| self.neutral_coverage(&neutral[&type_], pokemon))
+                .unwrap_or_default();
             let type_label;
             let covered_by;
 
-            if pokemon.is_empty() {
+            if pokemon.is_empty() && neutral_by.is_empty() {
                 type_label = format!("{red}{type_}{red:#}", red = self.ansi_bold(Colors::Red));
                 covered_by = String::from("");
+            } else if pokemon.is_empty() {
+                type_label = format!(
+                    "{yellow}{type_}{yellow:#}: ",
+                    yellow = self.ansi(Colors::Yellow)
+                );
+                covered_by = neutral_by;
             } else {
                 pokemon.sort();
                 type_label = format!(
                     "{green}{type_}{green:#}: ",
                     green = self.ansi(Colors::Green)
                 );
-                covered_by = pokemon.join(" ");
+                covered_by = pokemon
+                    .iter()
+                    .map(|(name, tag)| {
+                        format!(
+                            "{cyan}{name}{cyan:#} ({tag})",
+                            cyan = self.ansi(Colors::Cyan)
+                        )
+                    })
+                    .chain((!neutral_by.is_empty()).then_some(neutral_by))
+                    .collect::<Vec<String>>()
+                    .join(" ");
             };
 
             writeln!(f, "{type_label}{covered_by}")?
@@ -62,92 +98,206 @@ impl DisplayComponent<CoverageComponent<'_>> {
         Ok(())
     }
 
-    fn build_coverages(&self) -> (HashMap<String, Vec<String>>, HashMap<String, Vec<String>>) {
-        let mut offense_coverage: HashMap<String, Vec<String>> = HashMap::new();
-        let mut defense_coverage: HashMap<String, Vec<String>> = HashMap::new();
+    // Condenses each type's offense coverage into how many Pokémon cover it and whether any does so with STAB
+    fn write_summary(&self, f: &mut fmt::Formatter, coverage: Coverage) -> fmt::Result {
+        let CoverageComponent {
+            pokemon: team,
+            sort,
+            min,
+            top_moves,
+            ..
+        } = self.context;
+        let types = Self::order_types(&coverage, sort, min);
+
+        for type_ in types {
+            let pokemon = &coverage[&type_];
+            if pokemon.is_empty() {
+                writeln!(f, "{red}{type_}{red:#}", red = self.ansi_bold(Colors::Red))?;
+                continue;
+            }
+
+            // Pokémon without custom moves are covered by their own types, which is always STAB,
+            // unless their top moves stand in for them and carry the tag instead
+            let stab = pokemon.iter().any(|(name, tag)| {
+                tag.ends_with('+')
+                    || (top_moves.is_none()
+                        && team.iter().any(|p| p.name == *name && p.moves.is_empty()))
+            });
+            let stab = if stab {
+                format!(" {cyan}stab{cyan:#}", cyan = self.ansi(Colors::Cyan))
+            } else {
+                String::from("")
+            };
+
+            writeln!(
+                f,
+                "{green}{type_}{green:#}: {count}{stab}",
+                green = self.ansi(Colors::Green),
+                count = Self::count_pokemon(pokemon)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Pokémon that already have an advantage over the type are left out
+    fn neutral_coverage(
+        &self,
+        neutral: &[(String, String)],
+        covered: &[(String, String)],
+    ) -> String {
+        let mut neutral = neutral
+            .iter()
+            .filter(|(name, _)| covered.iter().all(|(covered_name, _)| covered_name != name))
+            .collect::<Vec<&(String, String)>>();
+        neutral.sort();
 
-        let CoverageComponent { pokemon, db } = self.context;
+        let dim = self.ansi_dim();
+        neutral
+            .into_iter()
+            .map(|(name, tag)| format!("{dim}{name} ({tag}){dim:#}"))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
 
-        for type_ in TYPES {
-            offense_coverage.insert(String::from(type_), vec![]);
-            defense_coverage.insert(String::from(type_), vec![]);
+    // Sorting by count places the least covered types first, as these are the gaps in a team.
+    fn order_types(coverage: &Coverage, sort: bool, min: Option<usize>) -> Vec<String> {
+        let mut types = coverage
+            .iter()
+            .map(|(type_, pokemon)| (type_.clone(), Self::count_pokemon(pokemon)))
+            .filter(|(_, count)| min.is_none_or(|min| *count < min))
+            .collect::<Vec<(String, usize)>>();
+
+        if sort {
+            types.sort_by(|(a_type, a_count), (b_type, b_count)| {
+                a_count.cmp(b_count).then(a_type.cmp(b_type))
+            });
+        } else {
+            types.sort();
         }
 
+        types.into_iter().map(|(type_, _)| type_).collect()
+    }
+
+    fn count_pokemon(entries: &[(String, String)]) -> usize {
+        entries
+            .iter()
+            .map(|(name, _)| name)
+            .collect::<HashSet<&String>>()
+            .len()
+    }
+
+    // Neutral (1x) coverage is only built when overlap is included; each Pokémon's moves and
+    // types are loaded once for both
+    fn build_coverages(&self) -> (Coverage, Coverage, Option<(Coverage, Coverage)>) {
+        let CoverageComponent {
+            pokemon,
+            db,
+            exclude,
+            type_charts,
+            top_moves,
+            include_overlap,
+            ..
+        } = self.context;
+
+        let empty = || -> Coverage {
+            TYPES
+                .iter()
+                .map(|type_| (String::from(*type_), vec![]))
+                .collect()
+        };
+        let mut offense_coverage = empty();
+        let mut defense_coverage = empty();
+        let mut neutral_coverage = include_overlap.then(|| (empty(), empty()));
+
         for pokemon in pokemon {
-            let move_list = pokemon.get_move_list(db).unwrap();
-
-            // If the pokemon's move list is empty (i.e. non-custom), use its types as its offensive coverage
-            if move_list.is_empty() {
-                let primary_type =
-                    Type::from_db(&pokemon.primary_type, pokemon.generation, db).unwrap();
-                self.add_type_coverage(pokemon, &primary_type.offense_chart, &mut offense_coverage);
-
-                if let Some(secondary_type) = pokemon.secondary_type.as_ref() {
-                    let secondary_type =
-                        Type::from_db(secondary_type, pokemon.generation, db).unwrap();
-                    self.add_type_coverage(
-                        pokemon,
-                        &secondary_type.offense_chart,
-                        &mut offense_coverage,
-                    );
-                }
-            } else {
-                for move_ in move_list.get_list().values() {
-                    if move_.is_combat() {
-                        self.add_move_coverage(pokemon, move_, &mut offense_coverage);
-                    }
+            let move_list = db.move_list(&pokemon.moves, pokemon.generation).unwrap();
+            // Each offensive chart paired with the tag its Pokémon is listed with
+            let move_chart = |move_: &Move| {
+                let move_type = db
+                    .type_(&move_.type_, move_.generation)
+                    .unwrap()
+                    .with_custom_chart(type_charts);
+                let mut tag = move_.name.clone();
+                if is_stab(&move_.type_, pokemon) {
+                    tag += "+";
                 }
-            }
+                (move_type.offense_chart, tag)
+            };
 
+            // If the pokemon's move list is empty (i.e. non-custom), use its types as its offensive coverage,
+            // or its strongest learnable moves when asked to approximate a moveset
+            let offense = if let (true, Some(count)) = (move_list.is_empty(), top_moves) {
+                let learnable = db.attacking_moves(pokemon).unwrap();
+                learnable
+                    .strongest_by_type(count, exclude)
+                    .into_iter()
+                    .map(move_chart)
+                    .collect::<Vec<_>>()
+            } else if move_list.is_empty() {
+                pokemon
+                    .get_types()
+                    .into_iter()
+                    .map(|type_| {
+                        let type_ = db
+                            .type_(type_, pokemon.generation)
+                            .unwrap()
+                            .with_custom_chart(type_charts);
+                        let tag = type_.offense_chart.get_label();
+                        (type_.offense_chart, tag)
+                    })
+                    .collect()
+            } else {
+                move_list
+                    .get_list()
+                    .values()
+                    .filter(|move_| move_.is_combat() && !exclude.contains(&move_.name))
+                    .map(move_chart)
+                    .collect()
+            };
             let defense_chart = pokemon.get_defense_chart(db).unwrap();
-            self.add_type_coverage(pokemon, &defense_chart, &mut defense_coverage);
-        }
 
-        (offense_coverage, defense_coverage)
-    }
+            for (chart, tag) in &offense {
+                self.add_chart_coverage(pokemon, chart, Some(tag), false, &mut offense_coverage);
+            }
+            self.add_chart_coverage(pokemon, &defense_chart, None, false, &mut defense_coverage);
 
-    fn add_move_coverage(
-        &self,
-        pokemon: &Pokemon,
-        move_: &Move,
-        coverage: &mut HashMap<String, Vec<String>>,
-    ) {
-        let move_type = Type::from_db(&move_.type_, move_.generation, self.context.db).unwrap();
-        let covered_types = self.get_covered_types(&move_type.offense_chart);
-        for type_ in covered_types {
-            let mut tag = move_.name.clone();
-            if is_stab(&move_.type_, pokemon) {
-                tag += "+";
+            if let Some((neutral_offense, neutral_defense)) = neutral_coverage.as_mut() {
+                for (chart, tag) in &offense {
+                    self.add_chart_coverage(pokemon, chart, Some(tag), true, neutral_offense);
+                }
+                self.add_chart_coverage(pokemon, &defense_chart, None, true, neutral_defense);
             }
-            self.add_to_coverage(&pokemon.name, &tag, &type_, coverage);
         }
+
+        (offense_coverage, defense_coverage, neutral_coverage)
     }
 
-    fn add_type_coverage(
+    // Without a tag, each type is tagged with the chart's multiplier, e.g. for defense
+    fn add_chart_coverage(
         &self,
         pokemon: &Pokemon,
         type_chart: &impl TypeChart,
-        coverage: &mut HashMap<String, Vec<String>>,
+        tag: Option<&str>,
+        neutral: bool,
+        coverage: &mut Coverage,
     ) {
-        let covered_types = self.get_covered_types(type_chart);
-        for type_ in covered_types {
-            let tag = match type_chart.get_type() {
-                TypeCharts::Offense => type_chart.get_label(),
-                TypeCharts::Defense => {
-                    let multiplier = type_chart.get_multiplier(&type_);
-                    multiplier.to_string()
-                }
-            };
+        for type_ in self.get_covered_types(type_chart, neutral) {
+            let tag = tag.map_or_else(
+                || type_chart.get_multiplier(&type_).to_string(),
+                String::from,
+            );
             self.add_to_coverage(&pokemon.name, &tag, &type_, coverage);
         }
     }
 
-    fn get_covered_types(&self, type_chart: &impl TypeChart) -> Vec<String> {
+    fn get_covered_types(&self, type_chart: &impl TypeChart, neutral: bool) -> Vec<String> {
         type_chart
             .get_chart()
             .iter()
             .filter_map(|(type_, multiplier)| {
                 let covered = match type_chart.get_type() {
+                    _ if neutral => *multiplier == 1.0,
                     TypeCharts::Offense => *multiplier > 1.0,
                     TypeCharts::Defense => *multiplier < 1.0,
                 };
@@ -160,21 +310,13 @@ impl DisplayComponent<CoverageComponent<'_>> {
             .collect()
     }
 
-    fn add_to_coverage(
-        &self,
-        name: &str,
-        tag: &str,
-        type_: &str,
-        coverage: &mut HashMap<String, Vec<String>>,
-    ) {
+    fn add_to_coverage(&self, name: &str, tag: &str, type_: &str, coverage: &mut Coverage) {
         let entry = coverage.entry(String::from(type_));
 
         if let Entry::Occupied(mut entry) = entry {
-            let pokemon = format!(
-                "{green}{name}{green:#} ({tag})",
-                green = self.ansi(Colors::Cyan)
-            );
-            entry.get_mut().push(pokemon);
+            entry
+                .get_mut()
+                .push((String::from(name), String::from(tag)));
         }
     }
 }
@@ -0,0 +1,90 @@
+use super::{Colors, DisplayComponent};
+use crate::models::Encounter;
+
+use std::fmt;
+
+pub struct EncounterListComponent<'a> {
+    pub encounters: &'a [Encounter],
+    pub game: &'a str,
+}
+
+impl fmt::Display for DisplayComponent<EncounterListComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let EncounterListComponent { encounters, game } = self.context;
+        write!(
+            f,
+            "{header}encounters{header:#} {game}",
+            header = self.ansi_bold(Colors::Header)
+        )?;
+
+        if encounters.is_empty() {
+            return write!(f, "\nThere are no encounters to display.");
+        }
+
+        for Encounter {
+            location,
+            method,
+            version,
+            min_level,
+            max_level,
+        } in encounters.iter()
+        {
+            let levels = if min_level == max_level {
+                min_level.to_string()
+            } else {
+                format!("{min_level}-{max_level}")
+            };
+
+            write!(
+                f,
+                "\n{green}{location}{green:#} {version} {blue}{method}{blue:#} level {levels}",
+                green = self.ansi(Colors::Green),
+                blue = self.ansi(Colors::Blue),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_encounters() {
+        let encounters = vec![
+            Encounter {
+                location: String::from("viridian-forest-area"),
+                method: String::from("walk"),
+                version: String::from("red"),
+                min_level: 3,
+                max_level: 5,
+            },
+            Encounter {
+                location: String::from("power-plant-area"),
+                method: String::from("walk"),
+                version: String::from("blue"),
+                min_level: 22,
+                max_level: 22,
+            },
+        ];
+        let context = EncounterListComponent {
+            encounters: &encounters,
+            game: "red-blue",
+        };
+        assert_eq!(
+            "encounters red-blue\nviridian-forest-area red walk level 3-5\npower-plant-area blue walk level 22",
+            DisplayComponent::new(context, Some(false)).to_string()
+        );
+
+        let context = EncounterListComponent {
+            encounters: &[],
+            game: "sword-shield",
+        };
+        assert_eq!(
+            "encounters sword-shield\nThere are no encounters to display.",
+            DisplayComponent::new(context, Some(false)).to_string()
+        );
+    }
+}
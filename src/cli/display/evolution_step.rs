@@ -10,7 +10,7 @@ impl fmt::Display for DisplayComponent<&EvolutionStep> {
             "{header}evolution{header:#}",
             header = self.ansi_bold(Colors::Header)
         )?;
-        self.traverse_dfs(f, self.context, 0)?;
+        self.traverse_dfs(f, self.context, 0, true, false)?;
         Ok(())
     }
 }
@@ -18,29 +18,85 @@ impl fmt::Display for DisplayComponent<&EvolutionStep> {
 impl DisplayComponent<&EvolutionStep> {
     pub fn traverse_dfs(
         &self,
-        f: &mut fmt::Formatter,
+        f: &mut impl fmt::Write,
         node: &EvolutionStep,
         depth: usize,
+        methods: bool,
+        stages: bool,
     ) -> fmt::Result {
-        self.write_step(f, node, depth)?;
+        self.write_step(f, node, depth, methods, stages)?;
         for child in &node.evolves_to {
             writeln!(f)?;
-            self.traverse_dfs(f, child, depth + 1)?;
+            self.traverse_dfs(f, child, depth + 1, methods, stages)?;
         }
 
         Ok(())
     }
 
+    // Species tree, optionally without evolution methods or with a stage label on each line
+    pub fn tree(&self, methods: bool, stages: bool) -> String {
+        let mut output = format!(
+            "{header}evolution{header:#}\n",
+            header = self.ansi_bold(Colors::Header)
+        );
+        self.traverse_dfs(&mut output, self.context, 0, methods, stages)
+            .expect("writing to a String should not fail");
+        output
+    }
+
+    // Graphviz digraph of the evolution line; edges are labeled by evolution method
+    pub fn dot(&self) -> String {
+        let plain = DisplayComponent::new(self.context, Some(false));
+        let mut lines = vec![String::from("digraph evolution {")];
+        plain.dot_step(self.context, &mut lines);
+        lines.push(String::from("}"));
+        lines.join("\n")
+    }
+
+    fn dot_step(&self, step: &EvolutionStep, lines: &mut Vec<String>) {
+        lines.push(format!(
+            "    {id} [label=\"{name}\"];",
+            id = dot_id(&step.name),
+            name = step.name
+        ));
+
+        for child in &step.evolves_to {
+            self.dot_step(child, lines);
+            lines.push(format!(
+                "    {from} -> {to} [label=\"{label}\"];",
+                from = dot_id(&step.name),
+                to = dot_id(&child.name),
+                label = self.format_methods(&child.methods).replace('"', "\\\""),
+            ));
+        }
+    }
+
     fn write_step(
         &self,
-        f: &mut fmt::Formatter,
+        f: &mut impl fmt::Write,
         step: &EvolutionStep,
         depth: usize,
+        methods: bool,
+        stages: bool,
     ) -> fmt::Result {
-        let methods = self.format_methods(&step.methods);
+        let methods = if methods {
+            format!(" {}", self.format_methods(&step.methods))
+        } else {
+            String::from("")
+        };
+        // Stages follow the TCG: branches at the same depth share a stage
+        let stage = if stages {
+            let label = match depth {
+                0 => String::from("basic"),
+                depth => format!("stage {depth}"),
+            };
+            format!("{dim}{label:<7}{dim:#} ", dim = self.ansi_dim())
+        } else {
+            String::from("")
+        };
         write!(
             f,
-            "{indentation}{green}{species}{green:#} {methods}",
+            "{stage}{indentation}{green}{species}{green:#}{methods}",
             indentation = "  ".repeat(depth),
             green = self.ansi(Colors::Green),
             species = step.name
@@ -76,85 +132,270 @@ impl DisplayComponent<&EvolutionStep> {
             trade_species,
             turn_upside_down,
         } = method;
-        let mut output = format!("{blue}{trigger}{blue:#}", blue = self.ansi(Colors::Blue));
+        let upside_down = turn_upside_down.unwrap_or(false);
+        let mut fragments = vec![];
+
+        // The trigger leads the phrase; a minimum level is folded into it when possible
+        let mut level_in_trigger = false;
+        let trigger_phrase = match trigger.as_str() {
+            "level-up" if upside_down => String::from("turn console upside down"),
+            "level-up" => match min_level {
+                Some(min_level) => {
+                    level_in_trigger = true;
+                    format!("level {min_level}")
+                }
+                None => String::from("level up"),
+            },
+            "use-item" => String::from("use"),
+            other => other.replace('-', " "),
+        };
+        fragments.push(format!(
+            "{blue}{trigger_phrase}{blue:#}",
+            blue = self.ansi(Colors::Blue)
+        ));
 
         if let Some(item) = item {
-            output += &format!(" {item}");
+            fragments.push(item.clone());
         }
 
-        if let Some(gender_int) = gender {
-            let gender = match gender_int {
-                1 => "female",
-                2 => "male",
-                _ => "other",
-            };
-            output += &format!(" gender-{gender}");
+        if let Some(trade_species) = trade_species {
+            fragments.push(format!("for {trade_species}"));
+        }
+
+        if let Some(min_level) = min_level {
+            if !level_in_trigger {
+                fragments.push(format!("at level {min_level}"));
+            }
         }
 
         if let Some(held_item) = held_item {
-            output += &format!(" {held_item}");
+            fragments.push(format!("holding {held_item}"));
         }
 
         if let Some(known_move) = known_move {
-            output += &format!(" {known_move}");
+            fragments.push(format!("knowing {known_move}"));
         }
 
         if let Some(known_move_type) = known_move_type {
-            output += &format!(" {known_move_type}");
-        }
-
-        if let Some(location) = location {
-            output += &format!(" {location}");
-        }
-
-        if let Some(min_level) = min_level {
-            output += &format!(" level-{min_level}");
+            fragments.push(format!("knowing a {known_move_type} move"));
         }
 
         if let Some(min_happiness) = min_happiness {
-            output += &format!(" happiness-{min_happiness}");
+            fragments.push(format!("with {min_happiness} happiness"));
         }
 
         if let Some(min_beauty) = min_beauty {
-            output += &format!(" beauty-{min_beauty}");
+            fragments.push(format!("with {min_beauty} beauty"));
         }
 
         if let Some(min_affection) = min_affection {
-            output += &format!(" affection-{min_affection}");
-        }
-
-        if let Some(needs_overworld_rain) = needs_overworld_rain {
-            if *needs_overworld_rain {
-                output += " rain";
-            }
+            fragments.push(format!("with {min_affection} affection"));
         }
 
         if let Some(party_species) = party_species {
-            output += &format!(" {party_species}");
+            fragments.push(format!("with {party_species} in party"));
         }
 
         if let Some(party_type) = party_type {
-            output += &format!(" {party_type}");
+            fragments.push(format!("with a {party_type} type in party"));
         }
 
         if let Some(relative_physical_stats) = relative_physical_stats {
-            output += &format!(" physical-{relative_physical_stats}");
+            let comparison = match relative_physical_stats {
+                1 => ">",
+                -1 => "<",
+                _ => "=",
+            };
+            fragments.push(format!("with attack {comparison} defense"));
         }
 
-        if let Some(time_of_day) = time_of_day {
-            output += &format!(" {time_of_day}");
+        if let Some(gender_int) = gender {
+            let gender = match gender_int {
+                1 => "female",
+                2 => "male",
+                _ => "other",
+            };
+            fragments.push(format!("if {gender}"));
         }
 
-        if let Some(trade_species) = trade_species {
-            output += &format!(" {trade_species}");
+        if let Some(location) = location {
+            fragments.push(format!("at {location}"));
         }
 
-        if let Some(turn_upside_down) = turn_upside_down {
-            if *turn_upside_down {
-                output += " upside-down";
-            }
+        if let Some(true) = needs_overworld_rain {
+            fragments.push(String::from("in the rain"));
         }
 
-        output
+        if let Some(time_of_day) = time_of_day {
+            let time_of_day = match time_of_day.as_str() {
+                "day" => String::from("during the day"),
+                time_of_day => format!("at {time_of_day}"),
+            };
+            fragments.push(time_of_day);
+        }
+
+        fragments.join(" ")
+    }
+}
+
+// DOT ids cannot contain hyphens unless quoted, e.g. mr-mime
+fn dot_id(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(step: &EvolutionStep) -> String {
+        DisplayComponent::new(step, Some(false)).to_string()
+    }
+
+    #[test]
+    fn display_eevee_evolution() {
+        let step = EvolutionStep::new(
+            String::from("eevee"),
+            vec![],
+            vec![
+                EvolutionStep::new(
+                    String::from("vaporeon"),
+                    vec![EvolutionMethod::new(String::from("use-item"))
+                        .item(String::from("water-stone"))],
+                    vec![],
+                ),
+                EvolutionStep::new(
+                    String::from("espeon"),
+                    vec![EvolutionMethod::new(String::from("level-up"))
+                        .min_happiness(160)
+                        .time_of_day(String::from("day"))],
+                    vec![],
+                ),
+                EvolutionStep::new(
+                    String::from("umbreon"),
+                    vec![EvolutionMethod::new(String::from("level-up"))
+                        .min_happiness(160)
+                        .time_of_day(String::from("night"))],
+                    vec![],
+                ),
+                EvolutionStep::new(
+                    String::from("leafeon"),
+                    vec![
+                        EvolutionMethod::new(String::from("level-up"))
+                            .location(String::from("eterna-forest")),
+                        EvolutionMethod::new(String::from("use-item"))
+                            .item(String::from("leaf-stone")),
+                    ],
+                    vec![],
+                ),
+                EvolutionStep::new(
+                    String::from("sylveon"),
+                    vec![EvolutionMethod::new(String::from("level-up"))
+                        .known_move_type(String::from("fairy"))
+                        .min_affection(2)],
+                    vec![],
+                ),
+            ],
+        );
+
+        insta::assert_snapshot!(format(&step));
+    }
+
+    #[test]
+    fn display_inkay_evolution() {
+        let step = EvolutionStep::new(
+            String::from("inkay"),
+            vec![],
+            vec![EvolutionStep::new(
+                String::from("malamar"),
+                vec![EvolutionMethod::new(String::from("level-up"))
+                    .min_level(30)
+                    .turn_upside_down(true)],
+                vec![],
+            )],
+        );
+
+        insta::assert_snapshot!(format(&step));
+    }
+
+    #[test]
+    fn display_trade_item_evolution() {
+        let step = EvolutionStep::new(
+            String::from("onix"),
+            vec![],
+            vec![EvolutionStep::new(
+                String::from("steelix"),
+                vec![EvolutionMethod::new(String::from("trade"))
+                    .held_item(String::from("metal-coat"))],
+                vec![],
+            )],
+        );
+
+        insta::assert_snapshot!(format(&step));
+    }
+
+    #[test]
+    fn compact_evolution() {
+        let step = EvolutionStep::new(
+            String::from("wurmple"),
+            vec![],
+            vec![
+                EvolutionStep::new(
+                    String::from("silcoon"),
+                    vec![EvolutionMethod::new(String::from("level-up")).min_level(7)],
+                    vec![EvolutionStep::new(
+                        String::from("beautifly"),
+                        vec![EvolutionMethod::new(String::from("level-up")).min_level(10)],
+                        vec![],
+                    )],
+                ),
+                EvolutionStep::new(
+                    String::from("cascoon"),
+                    vec![EvolutionMethod::new(String::from("level-up")).min_level(7)],
+                    vec![],
+                ),
+            ],
+        );
+        assert_eq!(
+            "evolution\nwurmple\n  silcoon\n    beautifly\n  cascoon",
+            DisplayComponent::new(&step, Some(false)).tree(false, false)
+        );
+
+        let green = DisplayComponent::new(&step, Some(true)).tree(false, false);
+        assert!(green.contains("\n  \u{1b}[38;5;77msilcoon\u{1b}[0m\n"));
+
+        assert_eq!(
+            "evolution\nbasic   wurmple\nstage 1   silcoon\nstage 2     beautifly\nstage 1   cascoon",
+            DisplayComponent::new(&step, Some(false)).tree(false, true)
+        );
+    }
+
+    #[test]
+    fn dot_evolution() {
+        let step = EvolutionStep::new(
+            String::from("mime-jr"),
+            vec![],
+            vec![EvolutionStep::new(
+                String::from("mr-mime"),
+                vec![EvolutionMethod::new(String::from("level-up"))
+                    .known_move(String::from("mimic"))],
+                vec![],
+            )],
+        );
+        assert_eq!(
+            "digraph evolution {\n    \
+            mime_jr [label=\"mime-jr\"];\n    \
+            mr_mime [label=\"mr-mime\"];\n    \
+            mime_jr -> mr_mime [label=\"level up knowing mimic\"];\n\
+            }",
+            DisplayComponent::new(&step, Some(true)).dot()
+        );
+
+        let step = EvolutionStep::new(String::from("tauros"), vec![], vec![]);
+        assert_eq!(
+            "digraph evolution {\n    tauros [label=\"tauros\"];\n}",
+            DisplayComponent::new(&step, Some(false)).dot()
+        );
     }
 }
@@ -0,0 +1,45 @@
+use super::{Colors, DisplayComponent};
+
+use std::fmt;
+
+pub struct FormsComponent<'a> {
+    pub forms: &'a [String],
+}
+
+impl fmt::Display for DisplayComponent<FormsComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{header}forms{header:#}",
+            header = self.ansi_bold(Colors::Header)
+        )?;
+
+        let FormsComponent { forms } = self.context;
+        if forms.len() <= 1 {
+            return write!(f, "There are no alternate forms.");
+        }
+
+        let forms = forms
+            .iter()
+            .map(|form| format!("{green}{form}{green:#}", green = self.ansi(Colors::Green)))
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "{forms}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_forms() {
+        let forms = vec![String::from("rotom"), String::from("rotom-wash")];
+        let display = DisplayComponent::new(FormsComponent { forms: &forms }, Some(false));
+        assert_eq!("forms\nrotom rotom-wash", display.to_string());
+
+        let forms = vec![String::from("dunsparce")];
+        let display = DisplayComponent::new(FormsComponent { forms: &forms }, Some(false));
+        assert_eq!("forms\nThere are no alternate forms.", display.to_string());
+    }
+}
@@ -1,17 +1,20 @@
 use super::{Colors, DisplayComponent, MoveWeaknessComponent};
-use crate::models::Pokemon;
+use crate::models::db::Db;
+use crate::models::{BattleConditions, Pokemon, TypeChart};
 
+use std::collections::HashSet;
 use std::fmt;
 
 use indoc::writedoc;
-use rusqlite::Connection;
 
 pub struct MatchComponent<'a> {
     pub defender: &'a Pokemon,
     pub attacker: &'a Pokemon,
-    pub db: &'a Connection,
+    pub db: &'a Db,
     pub verbose: bool,
     pub stab_only: bool,
+    pub conditions: BattleConditions,
+    pub exclude: &'a HashSet<String>,
 }
 
 impl fmt::Display for DisplayComponent<MatchComponent<'_>> {
@@ -22,21 +25,41 @@ impl fmt::Display for DisplayComponent<MatchComponent<'_>> {
             db,
             verbose,
             stab_only,
+            conditions,
+            exclude,
         } = self.context;
 
         let defender_stats = DisplayComponent::new(&defender.stats, self.color_enabled);
-        let attacker_stats = DisplayComponent::new(&attacker.stats, self.color_enabled);
+        // A typing-only attacker has no stats or moves; its STAB types are rated instead
+        let attacker_stats = if attacker.is_typing_only() {
+            String::from("")
+        } else {
+            format!(
+                "\n{}",
+                DisplayComponent::new(&attacker.stats, self.color_enabled)
+            )
+        };
 
-        let defender_moves_header =
-            format!("{}'s moves vs {}", attacker.nickname, defender.nickname);
-        let defender_context = MoveWeaknessComponent {
-            defender,
-            attacker,
-            db,
-            verbose,
-            stab_only,
+        let (defender_moves_header, defender_weaknesses) = if attacker.is_typing_only() {
+            (
+                format!("{}'s stab vs {}", attacker.nickname, defender.nickname),
+                self.stab_multipliers(),
+            )
+        } else {
+            let defender_context = MoveWeaknessComponent {
+                defender,
+                attacker,
+                db,
+                verbose,
+                stab_only,
+                conditions,
+                exclude,
+            };
+            (
+                format!("{}'s moves vs {}", attacker.nickname, defender.nickname),
+                DisplayComponent::new(defender_context, self.color_enabled).to_string(),
+            )
         };
-        let defender_weaknesses = DisplayComponent::new(defender_context, self.color_enabled);
 
         let attacker_moves_header =
             format!("{}'s moves vs {}", defender.nickname, attacker.nickname);
@@ -46,6 +69,8 @@ impl fmt::Display for DisplayComponent<MatchComponent<'_>> {
             db,
             verbose,
             stab_only,
+            conditions,
+            exclude,
         };
         let attacker_weaknesses = DisplayComponent::new(attacker_context, self.color_enabled);
 
@@ -53,19 +78,71 @@ impl fmt::Display for DisplayComponent<MatchComponent<'_>> {
             f,
             "{header}{defender_header}{header:#} {defender_primary_type} {defender_secondary_type}
             {defender_stats}
-            {header}{attacker_header}{header:#} {attacker_primary_type} {attacker_secondary_type}
-            {attacker_stats}
+            {header}{attacker_header}{header:#} {attacker_primary_type} {attacker_secondary_type}{attacker_stats}
 
-            {header}{defender_moves_header}{header:#}{defender_weaknesses}
+            {header}{defender_moves_header}{header:#}{defender_marker}{defender_weaknesses}
 
-            {header}{attacker_moves_header}{header:#}{attacker_weaknesses}",
+            {header}{attacker_moves_header}{header:#}{attacker_marker}{attacker_weaknesses}",
             defender_header = &defender.nickname,
             defender_primary_type = defender.primary_type,
-            defender_secondary_type = defender.secondary_type.as_deref().unwrap_or(""),
+            defender_secondary_type = join_other_types(defender),
             attacker_header = &attacker.nickname,
             attacker_primary_type = attacker.primary_type,
-            attacker_secondary_type = attacker.secondary_type.as_deref().unwrap_or(""),
+            attacker_secondary_type = join_other_types(attacker),
+            defender_marker = self.quad_stab_marker(defender, attacker),
+            attacker_marker = self.quad_stab_marker(attacker, defender),
             header = self.ansi_bold(Colors::Header),
         }
     }
 }
+
+impl DisplayComponent<MatchComponent<'_>> {
+    fn stab_multipliers(&self) -> String {
+        let MatchComponent {
+            defender,
+            attacker,
+            db,
+            ..
+        } = self.context;
+        let defense_chart = defender.get_defense_chart(db).unwrap();
+
+        attacker
+            .get_types()
+            .into_iter()
+            .map(|type_| {
+                let multiplier = self.multiplier_label(defense_chart.get_multiplier(type_));
+                format!("\n{type_}: {multiplier}")
+            })
+            .collect()
+    }
+
+    // Flags the attacker's STAB types that the defender is 4x weak to, e.g. " (x4 stab: water)"
+    fn quad_stab_marker(&self, defender: &Pokemon, attacker: &Pokemon) -> String {
+        let defense_chart = defender.get_defense_chart(self.context.db).unwrap();
+        let quad_types = attacker
+            .get_types()
+            .into_iter()
+            .filter(|type_| defense_chart.get_multiplier(type_) >= 4.0)
+            .cloned()
+            .collect::<Vec<String>>();
+
+        if quad_types.is_empty() {
+            return String::new();
+        }
+        format!(
+            " ({} stab: {})",
+            self.multiplier_label(4.0),
+            quad_types.join(", ")
+        )
+    }
+}
+
+fn join_other_types(pokemon: &Pokemon) -> String {
+    pokemon
+        .get_types()
+        .into_iter()
+        .skip(1)
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -1,23 +1,40 @@
 use super::{Colors, DisplayComponent};
-use crate::models::Move;
+use crate::models::{Move, MoveHistory, MoveMeta};
 
 use std::fmt;
 
 use indoc::writedoc;
 
-impl fmt::Display for DisplayComponent<&Move> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+pub struct MoveComponent<'a> {
+    pub move_: &'a Move,
+    pub verbose: bool,
+}
+
+impl DisplayComponent<MoveComponent<'_>> {
+    pub fn stats(&self) -> String {
         let Move {
             power,
             accuracy,
             pp,
-            name,
-            effect,
-            damage_class,
-            effect_chance,
-            type_,
             ..
-        } = self.context;
+        } = self.context.move_;
+        self.move_stats(*power, *accuracy, *pp)
+    }
+
+    pub fn effect(&self) -> String {
+        let MoveComponent { move_, verbose } = self.context;
+        if verbose {
+            move_.resolved_effect()
+        } else {
+            move_.resolved_short_effect()
+        }
+    }
+}
+
+impl<T> DisplayComponent<T> {
+    fn move_stats(&self, power: Option<i64>, accuracy: Option<i64>, pp: Option<i64>) -> String {
+        let power_color = self.power_ansi(power);
+        let accuracy_color = self.accuracy_ansi(accuracy);
 
         let power = if let Some(power) = power {
             power.to_string()
@@ -35,18 +52,24 @@ impl fmt::Display for DisplayComponent<&Move> {
             "N/A".to_string()
         };
 
-        let stats = format!(
-            "power: {red}{power:3}{red:#}  accuracy: {green}{accuracy:3}{green:#}  pp: {blue}{pp:3}{blue:#}",
-            red = self.ansi(Colors::Red),
-            green = self.ansi(Colors::Green),
+        format!(
+            "power: {power_color}{power:3}{power_color:#}  accuracy: {accuracy_color}{accuracy:3}{accuracy_color:#}  pp: {blue}{pp:3}{blue:#}",
             blue = self.ansi(Colors::Blue),
-        );
+        )
+    }
+}
 
-        let effect_text = if let Some(chance) = effect_chance {
-            effect.replace("$effect_chance", &chance.to_string())
-        } else {
-            effect.to_string()
-        };
+impl fmt::Display for DisplayComponent<MoveComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Move {
+            name,
+            damage_class,
+            type_,
+            ..
+        } = self.context.move_;
+
+        let stats = self.stats();
+        let effect_text = self.effect();
 
         writedoc! {
             f,
@@ -58,3 +81,283 @@ impl fmt::Display for DisplayComponent<&Move> {
         }
     }
 }
+
+pub struct MoveLearnersComponent<'a> {
+    pub pokemon: &'a [String],
+}
+
+impl fmt::Display for DisplayComponent<MoveLearnersComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{header}learners{header:#}",
+            header = self.ansi_bold(Colors::Header)
+        )?;
+
+        let MoveLearnersComponent { pokemon } = self.context;
+        if pokemon.is_empty() {
+            return write!(f, "There are no Pokémon to display.");
+        }
+
+        let green = self.ansi(Colors::Green);
+        let pokemon = pokemon
+            .iter()
+            .map(|name| format!("{green}{name}{green:#}"))
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "{pokemon}")
+    }
+}
+
+pub struct MoveHistoryComponent<'a> {
+    pub history: &'a [MoveHistory],
+}
+
+impl fmt::Display for DisplayComponent<MoveHistoryComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{header}history{header:#}",
+            header = self.ansi_bold(Colors::Header)
+        )?;
+
+        let MoveHistoryComponent { history } = self.context;
+        let spans = history
+            .iter()
+            .map(|h| match h.to {
+                Some(to) if to == h.from => format!("gen-{}", h.from),
+                Some(to) => format!("gen-{}-{to}", h.from),
+                None => format!("gen-{}+", h.from),
+            })
+            .collect::<Vec<String>>();
+        let width = spans.iter().map(|s| s.len()).max().unwrap_or(0);
+
+        for (span, h) in spans.iter().zip(history) {
+            let stats = self.move_stats(h.power, h.accuracy, h.pp);
+            write!(f, "\n{span:width$}  {stats}  {type_}", type_ = h.type_)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct MoveMetaComponent<'a> {
+    pub meta: &'a MoveMeta,
+}
+
+impl fmt::Display for DisplayComponent<MoveMetaComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{header}meta (latest){header:#}",
+            header = self.ansi_bold(Colors::Header)
+        )?;
+
+        let MoveMetaComponent { meta } = self.context;
+        let mut lines = vec![];
+        match meta.drain {
+            drain if drain > 0 => lines.push(format!("drains {drain}% of damage dealt")),
+            drain if drain < 0 => lines.push(format!("{}% recoil", -drain)),
+            _ => {}
+        }
+        if meta.healing > 0 {
+            lines.push(format!("heals {}% of max HP", meta.healing));
+        }
+        if meta.crit_rate > 0 {
+            lines.push(format!("+{} crit stage", meta.crit_rate));
+        }
+        if meta.flinch_chance > 0 {
+            lines.push(format!("{}% flinch chance", meta.flinch_chance));
+        }
+        match meta.hits {
+            Some((min, max)) if min == max => lines.push(format!("hits {min} times")),
+            Some((min, max)) => lines.push(format!("hits {min}-{max} times")),
+            None => {}
+        }
+
+        let target = if meta.stat_changes_user {
+            "self"
+        } else {
+            "target"
+        };
+        let chance = if meta.stat_chance > 0 {
+            format!(" ({}% chance)", meta.stat_chance)
+        } else {
+            String::new()
+        };
+        for (stat, change) in &meta.stat_changes {
+            lines.push(format!(
+                "{change:+} {} to {target}{chance}",
+                stat_label(stat)
+            ));
+        }
+
+        for line in lines {
+            write!(f, "\n{line}")?;
+        }
+        Ok(())
+    }
+}
+
+fn stat_label(stat: &str) -> &str {
+    match stat {
+        "hp" => "HP",
+        "attack" => "Atk",
+        "defense" => "Def",
+        "special-attack" => "SpA",
+        "special-defense" => "SpD",
+        "speed" => "Spe",
+        "accuracy" => "Acc",
+        "evasion" => "Eva",
+        _ => stat,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_move_effect() {
+        let move_ = Move {
+            name: String::from("ember"),
+            accuracy: Some(100),
+            power: Some(40),
+            pp: Some(25),
+            damage_class: String::from("special"),
+            type_: String::from("fire"),
+            effect: String::from(
+                "Inflicts regular damage.  Has a $effect_chance% chance to burn the target.",
+            ),
+            short_effect: String::from("Has a $effect_chance% chance to burn the target."),
+            effect_chance: Some(10),
+            generation: 1,
+        };
+
+        let short = DisplayComponent::new(
+            MoveComponent {
+                move_: &move_,
+                verbose: false,
+            },
+            Some(false),
+        )
+        .to_string();
+        assert!(short.ends_with("\nHas a 10% chance to burn the target."));
+
+        let verbose = DisplayComponent::new(
+            MoveComponent {
+                move_: &move_,
+                verbose: true,
+            },
+            Some(false),
+        )
+        .to_string();
+        assert!(
+            verbose.ends_with("\nInflicts regular damage.  Has a 10% chance to burn the target.")
+        );
+
+        let stats = DisplayComponent::new(
+            MoveComponent {
+                move_: &move_,
+                verbose: false,
+            },
+            Some(false),
+        )
+        .stats();
+        assert_eq!("power: 40   accuracy: 100  pp: 25 ", stats);
+
+        let effect = DisplayComponent::new(
+            MoveComponent {
+                move_: &move_,
+                verbose: false,
+            },
+            Some(true),
+        )
+        .effect();
+        assert_eq!("Has a 10% chance to burn the target.", effect);
+    }
+
+    #[test]
+    fn display_move_history() {
+        let history = vec![
+            MoveHistory {
+                from: 1,
+                to: Some(4),
+                power: Some(35),
+                accuracy: Some(95),
+                pp: Some(35),
+                type_: String::from("normal"),
+            },
+            MoveHistory {
+                from: 7,
+                to: None,
+                power: Some(40),
+                accuracy: Some(100),
+                pp: Some(35),
+                type_: String::from("normal"),
+            },
+        ];
+        let display =
+            DisplayComponent::new(MoveHistoryComponent { history: &history }, Some(false));
+        assert_eq!(
+            "history\ngen-1-4  power: 35   accuracy: 95   pp: 35   normal\ngen-7+   power: 40   accuracy: 100  pp: 35   normal",
+            display.to_string()
+        );
+    }
+
+    #[test]
+    fn display_move_learners() {
+        let pokemon = vec![String::from("geodude"), String::from("onix")];
+        let display =
+            DisplayComponent::new(MoveLearnersComponent { pokemon: &pokemon }, Some(false));
+        assert_eq!("learners\ngeodude onix", display.to_string());
+
+        let display = DisplayComponent::new(MoveLearnersComponent { pokemon: &[] }, Some(false));
+        assert_eq!(
+            "learners\nThere are no Pokémon to display.",
+            display.to_string()
+        );
+    }
+
+    #[test]
+    fn display_move_meta() {
+        let close_combat = MoveMeta {
+            stat_changes: vec![
+                (String::from("defense"), -1),
+                (String::from("special-defense"), -1),
+            ],
+            stat_changes_user: true,
+            ..Default::default()
+        };
+        let display = DisplayComponent::new(
+            MoveMetaComponent {
+                meta: &close_combat,
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "meta (latest)\n-1 Def to self\n-1 SpD to self",
+            display.to_string()
+        );
+
+        let double_edge = MoveMeta {
+            drain: -33,
+            ..Default::default()
+        };
+        let display = DisplayComponent::new(MoveMetaComponent { meta: &double_edge }, Some(false));
+        assert_eq!("meta (latest)\n33% recoil", display.to_string());
+
+        let mixed = MoveMeta {
+            flinch_chance: 10,
+            stat_chance: 20,
+            stat_changes: vec![(String::from("accuracy"), -1)],
+            hits: Some((2, 5)),
+            ..Default::default()
+        };
+        let display = DisplayComponent::new(MoveMetaComponent { meta: &mixed }, Some(false));
+        assert_eq!(
+            "meta (latest)\n10% flinch chance\nhits 2-5 times\n-1 Acc to target (20% chance)",
+            display.to_string()
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use super::{Colors, DisplayComponent};
+use super::{markdown_table, visible_len, Colors, DisplayComponent};
 use crate::cli::utils::is_stab;
-use crate::models::{Move, MoveList, Pokemon};
+use crate::models::{DefenseTypeChart, Machines, Move, MoveList, Pokemon, TypeChart};
 
 use std::fmt;
 
@@ -9,31 +9,39 @@ use indoc::writedoc;
 pub struct MoveListComponent<'a> {
     pub move_list: &'a MoveList,
     pub pokemon: &'a Pokemon,
+    pub up_to_level: Option<i64>,
+    pub include_tm: bool,
+    // Defender's name and chart to rate each move against
+    pub vs: Option<(&'a str, &'a DefenseTypeChart)>,
+    // Machine numbers to annotate machine moves with
+    pub machines: Option<&'a Machines>,
+    // Terminal width the name, type, and stats columns are scaled to
+    pub width: usize,
 }
 
 impl fmt::Display for DisplayComponent<MoveListComponent<'_>> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let MoveListComponent {
+            pokemon,
+            move_list,
+            vs,
+            width,
+            ..
+        } = self.context;
+
+        let vs_label = vs.map_or(String::from(""), |(defender, _)| format!(" vs {defender}"));
         write!(
             f,
-            "{header}moves{header:#}",
+            "{header}moves{vs_label}{header:#}",
             header = self.ansi_bold(Colors::Header)
         )?;
-
-        let MoveListComponent { pokemon, move_list } = self.context;
-        let mut learn_moves = pokemon.learnable_moves.clone();
+        let learn_moves = self.learn_moves();
 
         if learn_moves.is_empty() {
             write!(f, "\nThere are no moves to display.\n")?;
-        } else {
-            // Sort by name, then by level, then by method
-            learn_moves.sort_by(|(a_name, a_method, a_level), (b_name, b_method, b_level)| {
-                a_method
-                    .cmp(b_method)
-                    .then(a_level.cmp(b_level))
-                    .then(a_name.cmp(b_name))
-            });
         }
 
+        let mut rows = vec![];
         for (name, learn_method, learn_level) in learn_moves {
             let Move {
                 name,
@@ -46,6 +54,8 @@ impl fmt::Display for DisplayComponent<MoveListComponent<'_>> {
             } = move_list.get_move(&name).unwrap();
 
             let stab = if is_stab(type_, pokemon) { "(s)" } else { "" };
+            let power_color = self.power_ansi(*power);
+            let accuracy_color = self.accuracy_ansi(*accuracy);
 
             let power = if let Some(power) = power {
                 power.to_string()
@@ -63,13 +73,7 @@ impl fmt::Display for DisplayComponent<MoveListComponent<'_>> {
                 "N/A".to_string()
             };
 
-            let level = if learn_level == 0i64 && learn_method == "level-up" {
-                "evolve".to_string()
-            } else if learn_method == "level-up" {
-                learn_level.to_string()
-            } else {
-                "".to_string()
-            };
+            let level = self.level_label(name, &learn_method, learn_level);
 
             let move_name = format!(
                 "{green}{name}{green:#}{stab}",
@@ -77,26 +81,365 @@ impl fmt::Display for DisplayComponent<MoveListComponent<'_>> {
             );
             let move_type = format!("{type_} {damage_class}");
             let move_stats = format!(
-                "power: {red}{power:3}{red:#}  accuracy: {green}{accuracy:3}{green:#}  pp: {blue}{pp:2}{blue:#}",
-                green = self.ansi(Colors::Green),
-                red = self.ansi(Colors::Red),
+                "power: {power_color}{power:3}{power_color:#}  accuracy: {accuracy_color}{accuracy:3}{accuracy_color:#}  pp: {blue}{pp:2}{blue:#}",
                 blue = self.ansi(Colors::Blue),
             );
 
-            // std::fmt's formatting widths are affected by ansi codes in the string.
-            // It's hacky, but having different width values based on the color setting is a suitable fix for now.
-            let (name_width, type_width, stats_width) = if self.is_color_enabled() {
-                (35, 20, 80)
-            } else {
-                (21, 20, 37)
+            let effectiveness = match vs {
+                Some((_, chart)) if damage_class != "status" => {
+                    let multiplier = self.multiplier_label(chart.get_multiplier(type_));
+                    let width = 6 + multiplier.len() - visible_len(&multiplier);
+                    format!("{multiplier:width$}")
+                }
+                Some(_) => format!("{:6}", ""),
+                None => String::from(""),
             };
 
+            let tail = format!("{effectiveness}{learn_method} {level}");
+            rows.push((move_name, move_type, move_stats, tail));
+        }
+
+        // Every column holds its widest entry and a space, and the tail is left out of the scaling
+        let widest = |column: fn(&(String, String, String, String)) -> &String| {
+            rows.iter()
+                .map(|row| visible_len(column(row)))
+                .max()
+                .unwrap_or(0)
+        };
+        let (name_width, type_width, stats_width) = column_widths(
+            width,
+            widest(|row| &row.3),
+            (
+                widest(|row| &row.0) + 1,
+                widest(|row| &row.1) + 1,
+                widest(|row| &row.2) + 1,
+            ),
+        );
+
+        for (move_name, move_type, move_stats, tail) in rows {
+            // std::fmt's formatting widths are affected by ansi codes in the string
+            let name_width = name_width + move_name.len() - visible_len(&move_name);
+            let stats_width = stats_width + move_stats.len() - visible_len(&move_stats);
+
             writedoc! {
                 f,
-                "\n{move_name:name_width$}{move_type:type_width$}{move_stats:stats_width$}{learn_method} {level}",
+                "\n{move_name:name_width$}{move_type:type_width$}{move_stats:stats_width$}{tail}",
             }?;
         }
 
         Ok(())
     }
 }
+
+impl DisplayComponent<MoveListComponent<'_>> {
+    fn learn_moves(&self) -> Vec<(String, String, i64)> {
+        let MoveListComponent {
+            pokemon,
+            up_to_level,
+            include_tm,
+            ..
+        } = self.context;
+        let mut learn_moves =
+            available_moves(pokemon.learnable_moves.clone(), up_to_level, include_tm);
+
+        // Sort by method, then by level, then by name
+        learn_moves.sort_by(|(a_name, a_method, a_level), (b_name, b_method, b_level)| {
+            a_method
+                .cmp(b_method)
+                .then(a_level.cmp(b_level))
+                .then(a_name.cmp(b_name))
+        });
+        learn_moves
+    }
+
+    fn level_label(&self, name: &str, learn_method: &str, learn_level: i64) -> String {
+        let machine = self
+            .context
+            .machines
+            .filter(|_| learn_method == "machine")
+            .and_then(|machines| machines.get_machine(name));
+
+        match machine {
+            Some(machine) => machine.clone(),
+            None => level_label(learn_method, learn_level),
+        }
+    }
+
+    pub fn markdown(&self) -> String {
+        let MoveListComponent {
+            pokemon,
+            move_list,
+            vs,
+            ..
+        } = self.context;
+        let learn_moves = self.learn_moves();
+
+        if learn_moves.is_empty() {
+            return String::from("There are no moves to display.\n");
+        }
+
+        let na = |value: &Option<i64>| value.map_or(String::from("N/A"), |v| v.to_string());
+        let rows = learn_moves
+            .into_iter()
+            .map(|(name, learn_method, learn_level)| {
+                let move_ = move_list.get_move(&name).unwrap();
+                let stab = if is_stab(&move_.type_, pokemon) {
+                    "(s)"
+                } else {
+                    ""
+                };
+                let method = format!(
+                    "{learn_method} {}",
+                    self.level_label(&name, &learn_method, learn_level)
+                );
+
+                let mut row = vec![
+                    format!("{}{stab}", move_.name),
+                    move_.type_.clone(),
+                    move_.damage_class.clone(),
+                    na(&move_.power),
+                    na(&move_.accuracy),
+                    na(&move_.pp),
+                ];
+                if let Some((_, chart)) = vs {
+                    row.push(if move_.is_combat() {
+                        format!("x{}", chart.get_multiplier(&move_.type_))
+                    } else {
+                        String::from("")
+                    });
+                }
+                row.push(method.trim_end().to_string());
+                row
+            })
+            .collect();
+
+        let mut headers = vec!["name", "type", "category", "power", "accuracy", "pp"];
+        if vs.is_some() {
+            headers.push("vs");
+        }
+        headers.push("method");
+        markdown_table(&headers, rows)
+    }
+}
+
+// Name, type, and stats column widths; the 80 column layout is scaled to what the terminal has
+// left after the effectiveness and method tail, but never below each column's minimum
+fn column_widths(
+    width: usize,
+    tail: usize,
+    (name_min, type_min, stats_min): (usize, usize, usize),
+) -> (usize, usize, usize) {
+    let budget = width.saturating_sub(tail);
+    let scale = |column: usize, minimum: usize| (column * budget / 78).max(minimum);
+    (
+        scale(21, name_min),
+        scale(20, type_min),
+        scale(37, stats_min),
+    )
+}
+
+fn level_label(learn_method: &str, learn_level: i64) -> String {
+    if learn_level == 0i64 && learn_method == "level-up" {
+        "evolve".to_string()
+    } else if learn_method == "level-up" {
+        learn_level.to_string()
+    } else {
+        "".to_string()
+    }
+}
+
+// With a level cap, only level-up moves learned by then (evolve moves included) and optionally TMs are available
+fn available_moves(
+    learn_moves: Vec<(String, String, i64)>,
+    up_to_level: Option<i64>,
+    include_tm: bool,
+) -> Vec<(String, String, i64)> {
+    let Some(up_to_level) = up_to_level else {
+        return learn_moves;
+    };
+
+    learn_moves
+        .into_iter()
+        .filter(
+            |(_, learn_method, learn_level)| match learn_method.as_str() {
+                "level-up" => *learn_level <= up_to_level,
+                "machine" => include_tm,
+                _ => false,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NewTypeChart, OffenseTypeChart, Type};
+
+    use std::collections::HashMap;
+
+    fn learn_move(name: &str, learn_method: &str, learn_level: i64) -> (String, String, i64) {
+        (String::from(name), String::from(learn_method), learn_level)
+    }
+
+    fn electric_pokemon(learnable_moves: Vec<(String, String, i64)>) -> Pokemon {
+        let electric = Type {
+            name: String::from("electric"),
+            offense_chart: OffenseTypeChart::new(HashMap::new()),
+            defense_chart: DefenseTypeChart::new(HashMap::new()),
+            generation: 9,
+        };
+        let mut pokemon = Pokemon::from_types(&[electric], 9).unwrap();
+        pokemon.learnable_moves = learnable_moves;
+        pokemon
+    }
+
+    fn move_(name: &str, type_: &str) -> Move {
+        Move {
+            name: String::from(name),
+            accuracy: Some(100),
+            power: Some(90),
+            pp: Some(15),
+            damage_class: String::from("special"),
+            type_: String::from(type_),
+            effect: String::new(),
+            short_effect: String::new(),
+            effect_chance: None,
+            generation: 9,
+        }
+    }
+
+    #[test]
+    fn display_machine_labels() {
+        let pokemon = electric_pokemon(vec![
+            learn_move("thunderbolt", "machine", 0),
+            learn_move("thunder-shock", "level-up", 1),
+            learn_move("protect", "machine", 0),
+        ]);
+        let move_list = [
+            move_("thunderbolt", "electric"),
+            move_("thunder-shock", "electric"),
+            move_("protect", "normal"),
+        ]
+        .into_iter()
+        .collect::<MoveList>();
+        // Protect is left out to show that machine moves without a number keep a blank label
+        let machines = [(String::from("thunderbolt"), String::from("tm126"))]
+            .into_iter()
+            .collect::<Machines>();
+
+        let display = DisplayComponent::new(
+            MoveListComponent {
+                move_list: &move_list,
+                pokemon: &pokemon,
+                up_to_level: None,
+                include_tm: false,
+                vs: None,
+                machines: Some(&machines),
+                width: 80,
+            },
+            Some(false),
+        )
+        .to_string();
+        let line = |name: &str| {
+            display
+                .lines()
+                .find(|line| line.starts_with(name))
+                .unwrap()
+                .to_string()
+        };
+        assert!(line("thunderbolt(s)").ends_with("machine TM126"));
+        assert!(line("protect").ends_with("machine "));
+        assert!(line("thunder-shock(s)").ends_with("level-up 1"));
+    }
+
+    #[test]
+    fn column_widths_scale() {
+        assert_eq!((21, 20, 37), column_widths(80, 2, (0, 0, 0)));
+        assert_eq!((28, 26, 49), column_widths(120, 15, (0, 0, 0)));
+        // Columns never shrink below their minimum
+        assert_eq!((17, 18, 34), column_widths(60, 15, (17, 18, 34)));
+        assert_eq!((17, 18, 34), column_widths(40, 15, (17, 18, 34)));
+    }
+
+    #[test]
+    fn display_narrow_widths() {
+        let pokemon = electric_pokemon(vec![
+            learn_move("thunder-punch", "level-up", 1),
+            learn_move("tackle", "level-up", 5),
+        ]);
+        let move_list = [
+            move_("thunder-punch", "electric"),
+            move_("tackle", "normal"),
+        ]
+        .into_iter()
+        .collect::<MoveList>();
+        let display = |width| {
+            DisplayComponent::new(
+                MoveListComponent {
+                    move_list: &move_list,
+                    pokemon: &pokemon,
+                    up_to_level: None,
+                    include_tm: false,
+                    vs: None,
+                    machines: None,
+                    width,
+                },
+                Some(false),
+            )
+            .to_string()
+        };
+
+        for width in [40, 60] {
+            assert_eq!(
+                "moves\n\
+                thunder-punch(s) electric special power: 90   accuracy: 100  pp: 15 level-up 1\n\
+                tackle           normal special   power: 90   accuracy: 100  pp: 15 level-up 5",
+                display(width)
+            );
+        }
+
+        // With room to spare, the tail is kept within the width
+        let wide = display(120);
+        for line in wide.lines() {
+            assert!(line.len() <= 120, "{line}");
+        }
+        assert!(wide.contains(&format!("\nthunder-punch(s){:13}electric special", "")));
+    }
+
+    #[test]
+    fn available_moves_up_to_level() {
+        let learn_moves = vec![
+            learn_move("ember", "level-up", 0),
+            learn_move("scratch", "level-up", 1),
+            learn_move("flamethrower", "level-up", 36),
+            learn_move("protect", "machine", 0),
+            learn_move("baton-pass", "egg", 0),
+            learn_move("fire-punch", "tutor", 0),
+        ];
+
+        let all = available_moves(learn_moves.clone(), None, false);
+        assert_eq!(learn_moves, all);
+
+        let capped = available_moves(learn_moves.clone(), Some(20), false);
+        assert_eq!(
+            vec![
+                learn_move("ember", "level-up", 0),
+                learn_move("scratch", "level-up", 1)
+            ],
+            capped
+        );
+
+        let capped_tm = available_moves(learn_moves.clone(), Some(20), true);
+        assert_eq!(
+            vec![
+                learn_move("ember", "level-up", 0),
+                learn_move("scratch", "level-up", 1),
+                learn_move("protect", "machine", 0)
+            ],
+            capped_tm
+        );
+
+        let learn_moves = vec![learn_move("flamethrower", "level-up", 36)];
+        assert!(available_moves(learn_moves, Some(10), false).is_empty());
+    }
+}
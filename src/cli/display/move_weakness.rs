@@ -1,52 +1,75 @@
-use super::{Colors, DisplayComponent, Effects, WeaknessDisplay};
+use super::{nearest_multiplier, Colors, DisplayComponent, Effects, WeaknessDisplay};
 use crate::cli::utils::is_stab;
-use crate::models::{Move, Pokemon, TypeChart};
+use crate::models::db::Db;
+use crate::models::{BattleConditions, Move, MoveList, Pokemon, TypeChart};
 
+use std::collections::HashSet;
 use std::fmt;
 
 use indoc::writedoc;
-use rusqlite::Connection;
 
 pub struct MoveWeaknessComponent<'a> {
     pub defender: &'a Pokemon,
     pub attacker: &'a Pokemon,
-    pub db: &'a Connection,
+    pub db: &'a Db,
     pub verbose: bool,
     pub stab_only: bool,
+    pub conditions: BattleConditions,
+    // Moves left out of the analysis, e.g. ones the attacker will not realistically run
+    pub exclude: &'a HashSet<String>,
 }
 
-impl fmt::Display for DisplayComponent<MoveWeaknessComponent<'_>> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let MoveWeaknessComponent {
-            defender,
-            attacker,
-            db,
-            verbose,
-            stab_only,
-        } = self.context;
-
-        let move_list = attacker.get_move_list(db).unwrap();
-        let attacker_moves = if move_list.is_empty() {
-            attacker.get_learnable_move_list(db).unwrap()
-        } else {
-            move_list
-        };
+impl MoveWeaknessComponent<'_> {
+    fn attacker_moves(&self) -> MoveList {
+        self.db.attacking_moves(self.attacker).unwrap()
+    }
 
-        let defender_defense = defender.get_defense_chart(db).unwrap();
+    // Combat moves paired with their multiplier against the defender, with the weather and
+    // terrain modifier applied and snapped to the nearest group, and with the modifier itself
+    fn multipliers<'m>(&self, moves: &'m MoveList) -> Vec<(&'m Move, f32, f32)> {
+        let defender_defense = self.defender.get_defense_chart(self.db).unwrap();
+        let defender_types = self.defender.get_types();
 
-        let weakness_groups = self.group_by_weakness(attacker_moves.get_list().values(), |move_| {
-            let multiplier = defender_defense.get_multiplier(&move_.type_);
+        moves
+            .get_list()
+            .values()
+            .filter(|move_| move_.is_combat() && !self.exclude.contains(&move_.name))
+            .filter(|move_| !self.stab_only || is_stab(&move_.type_, self.attacker))
+            .map(|move_| {
+                let modifier =
+                    self.conditions
+                        .modifier(move_, &defender_types, self.defender.generation);
+                let multiplier = defender_defense.get_multiplier(&move_.type_) * modifier;
+                (move_, nearest_multiplier(multiplier), modifier)
+            })
+            .collect()
+    }
 
-            let stab_qualified = !stab_only || is_stab(&move_.type_, attacker);
-            let verbose_qualified = verbose || multiplier >= 2.0;
+    pub fn threat_score(&self) -> usize {
+        let attacker_moves = self.attacker_moves();
+        let multipliers = self.multipliers(&attacker_moves);
+        threat_score(multipliers.into_iter().map(|(_, multiplier, _)| multiplier))
+    }
+}
 
-            if move_.is_combat() && stab_qualified && verbose_qualified {
-                Some((move_, multiplier))
-            } else {
-                None
-            }
-        });
-        let defender_weaknesses = self.format_groups(weakness_groups);
+// How threatening a move set is to the defender: each super effective move scores 1,
+// and each quadruple effective move scores 2; resisted and neutral moves score nothing
+fn threat_score(multipliers: impl Iterator<Item = f32>) -> usize {
+    multipliers
+        .map(|multiplier| match multiplier {
+            m if m >= 4.0 => 2,
+            m if m >= 2.0 => 1,
+            _ => 0,
+        })
+        .sum()
+}
+
+impl fmt::Display for DisplayComponent<MoveWeaknessComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let attacker_moves = self.context.attacker_moves();
+
+        let multipliers = self.context.multipliers(&attacker_moves);
+        let defender_weaknesses = self.format_weaknesses(multipliers);
 
         writedoc! {
             f,
@@ -55,16 +78,34 @@ impl fmt::Display for DisplayComponent<MoveWeaknessComponent<'_>> {
     }
 }
 
-impl WeaknessDisplay<&Move> for DisplayComponent<MoveWeaknessComponent<'_>> {
-    fn format_group(&self, label: &'static str, mut moves: Vec<&Move>, color: Colors) -> String {
+impl DisplayComponent<MoveWeaknessComponent<'_>> {
+    // Moves are filtered and grouped on the same modified multiplier, and labelled with the
+    // exact modifier so that weather and terrain stay visible after the rounding
+    fn format_weaknesses(&self, multipliers: Vec<(&Move, f32, f32)>) -> String {
+        let weakness_groups =
+            self.group_by_weakness(multipliers, |(move_, multiplier, modifier)| {
+                let verbose_qualified = self.context.verbose || multiplier >= 2.0;
+                verbose_qualified.then_some(((move_, modifier), multiplier))
+            });
+        self.format_groups(weakness_groups)
+    }
+}
+
+impl WeaknessDisplay<(&Move, f32)> for DisplayComponent<MoveWeaknessComponent<'_>> {
+    fn format_group(
+        &self,
+        label: &'static str,
+        mut moves: Vec<(&Move, f32)>,
+        color: Colors,
+    ) -> String {
         let mut output = format!("\n{label}: ");
 
         let style = self.style().fg(color);
         let normal_color = style.ansi();
         let stab_color = style.effect(Effects::Underline).ansi();
 
-        moves.sort_by_key(|m| m.name.clone());
-        for move_ in moves {
+        moves.sort_by_key(|(m, _)| m.name.clone());
+        for (move_, modifier) in moves {
             let damage_class = match move_.damage_class.as_str() {
                 "special" => "s",
                 "physical" => "p",
@@ -77,11 +118,211 @@ impl WeaknessDisplay<&Move> for DisplayComponent<MoveWeaknessComponent<'_>> {
             };
 
             output += &format!(
-                "{color}{move_name}({damage_class}){color:#} ",
+                "{color}{move_name}({damage_class}){color:#}{modifier} ",
                 move_name = move_.name,
+                modifier = modifier_label(modifier),
             );
         }
 
         output
     }
 }
+
+// e.g. "x1.3" for a terrain boost, or nothing when the conditions leave the move alone
+fn modifier_label(modifier: f32) -> String {
+    if modifier == 1.0 {
+        return String::new();
+    }
+    let modifier = format!("{modifier:.2}");
+    format!("x{}", modifier.trim_end_matches('0').trim_end_matches('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::database::{InsertRow, MoveRow};
+    use crate::models::{DefenseTypeChart, NewTypeChart, OffenseTypeChart, Terrain, Type, Weather};
+
+    use std::collections::HashMap;
+
+    use rusqlite::Connection;
+
+    #[test]
+    fn threat_score_weights_multipliers() {
+        assert_eq!(0, threat_score([].into_iter()));
+        assert_eq!(0, threat_score([0.0, 0.25, 0.5, 1.0].into_iter()));
+        assert_eq!(2, threat_score([2.0, 1.0, 2.0].into_iter()));
+        assert_eq!(3, threat_score([4.0, 2.0, 0.5].into_iter()));
+    }
+
+    fn pokemon(type_: &str, defense: &[(&str, f32)], moves: &[&str], generation: u8) -> Pokemon {
+        let defense = defense
+            .iter()
+            .map(|(t, multiplier)| (String::from(*t), *multiplier))
+            .collect();
+        let type_ = Type {
+            name: String::from(type_),
+            offense_chart: OffenseTypeChart::new(HashMap::new()),
+            defense_chart: DefenseTypeChart::new(defense),
+            generation,
+        };
+        let mut pokemon = Pokemon::from_types(&[type_], generation).unwrap();
+        pokemon.moves = moves.iter().map(|name| String::from(*name)).collect();
+        pokemon
+    }
+
+    fn match_display(
+        db: &Db,
+        defender: &Pokemon,
+        attacker: &Pokemon,
+        verbose: bool,
+        weather: Option<Weather>,
+    ) -> String {
+        let context = MoveWeaknessComponent {
+            defender,
+            attacker,
+            db,
+            verbose,
+            stab_only: false,
+            conditions: BattleConditions {
+                weather,
+                terrain: None,
+            },
+            exclude: &HashSet::new(),
+        };
+        DisplayComponent::new(context, Some(false)).to_string()
+    }
+
+    #[test]
+    fn rain_boosts_water_move() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(include_str!("../../sql/create_schema.sql"))
+            .unwrap();
+        for (id, name, type_) in [(1, "surf", "water"), (2, "flamethrower", "fire")] {
+            MoveRow {
+                id,
+                name: String::from(name),
+                power: Some(90),
+                accuracy: Some(100),
+                pp: Some(15),
+                effect_chance: None,
+                effect: String::new(),
+                short_effect: String::new(),
+                type_: String::from(type_),
+                damage_class: String::from("special"),
+                generation: 1,
+            }
+            .insert(&connection)
+            .unwrap();
+        }
+        let db = Db::new(connection);
+
+        let attacker = pokemon("water", &[], &["surf", "flamethrower"], 9);
+        let normal = pokemon("normal", &[], &[], 9);
+        let grass = pokemon("grass", &[("fire", 2.0), ("water", 0.5)], &[], 9);
+        let fire = pokemon("fire", &[("water", 2.0), ("fire", 0.5)], &[], 9);
+
+        // Rain moves surf from the neutral group into the double group, and flamethrower down
+        assert_eq!(
+            "\nneutral: flamethrower(s) surf(s) ",
+            match_display(&db, &normal, &attacker, true, None)
+        );
+        assert_eq!(
+            "\ndouble: surf(s)x1.5 \nhalf: flamethrower(s)x0.5 ",
+            match_display(&db, &normal, &attacker, true, Some(Weather::Rain))
+        );
+        assert_eq!(
+            "\ndouble: surf(s)x1.5 ",
+            match_display(&db, &normal, &attacker, false, Some(Weather::Rain))
+        );
+        // and from the double group into the quad group
+        assert_eq!(
+            "\nquad: surf(s)x1.5 \nquarter: flamethrower(s)x0.5 ",
+            match_display(&db, &fire, &attacker, true, Some(Weather::Rain))
+        );
+
+        // Rain drops flamethrower from the double group, so it is left out without --verbose
+        assert_eq!(
+            "\ndouble: flamethrower(s) ",
+            match_display(&db, &grass, &attacker, false, None)
+        );
+        assert_eq!(
+            "\nNone",
+            match_display(&db, &grass, &attacker, false, Some(Weather::Rain))
+        );
+
+        // There is no weather in generation 1
+        let attacker = pokemon("water", &[], &["surf"], 1);
+        let normal = pokemon("normal", &[], &[], 1);
+        assert_eq!(
+            "\nneutral: surf(s) ",
+            match_display(&db, &normal, &attacker, true, Some(Weather::Rain))
+        );
+    }
+
+    #[test]
+    fn conditions_follow_generation() {
+        let move_ = |type_: &str, damage_class: &str| Move {
+            name: String::from("move"),
+            accuracy: Some(100),
+            power: Some(90),
+            pp: Some(15),
+            damage_class: String::from(damage_class),
+            type_: String::from(type_),
+            effect: String::new(),
+            short_effect: String::new(),
+            effect_chance: None,
+            generation: 1,
+        };
+        let conditions = |weather, terrain| BattleConditions { weather, terrain };
+        let (rock, ice) = (String::from("rock"), String::from("ice"));
+
+        let rain = conditions(Some(Weather::Rain), None);
+        assert_eq!(1.0, rain.modifier(&move_("water", "special"), &[], 1));
+        assert_eq!(1.5, rain.modifier(&move_("water", "special"), &[], 2));
+
+        let sand = conditions(Some(Weather::Sand), None);
+        assert_eq!(1.0, sand.modifier(&move_("water", "special"), &[&rock], 3));
+        assert_eq!(
+            2.0 / 3.0,
+            sand.modifier(&move_("water", "special"), &[&rock], 4)
+        );
+
+        let snow = conditions(Some(Weather::Snow), None);
+        assert_eq!(
+            1.0,
+            snow.modifier(&move_("fighting", "physical"), &[&ice], 8)
+        );
+        assert_eq!(
+            2.0 / 3.0,
+            snow.modifier(&move_("fighting", "physical"), &[&ice], 9)
+        );
+
+        let electric = conditions(None, Some(Terrain::Electric));
+        assert_eq!(
+            1.0,
+            electric.modifier(&move_("electric", "special"), &[], 5)
+        );
+        assert_eq!(
+            1.5,
+            electric.modifier(&move_("electric", "special"), &[], 7)
+        );
+        assert_eq!(
+            1.3,
+            electric.modifier(&move_("electric", "special"), &[], 8)
+        );
+
+        let psychic = conditions(None, Some(Terrain::Psychic));
+        assert_eq!(1.0, psychic.modifier(&move_("psychic", "special"), &[], 6));
+        assert_eq!(1.5, psychic.modifier(&move_("psychic", "special"), &[], 7));
+    }
+
+    #[test]
+    fn modifier_labels_are_exact() {
+        assert_eq!("x1.3", modifier_label(1.3));
+        assert_eq!("x0.67", modifier_label(2.0 / 3.0));
+        assert_eq!("x0.33", modifier_label(0.5 * 2.0 / 3.0));
+        assert_eq!("x2", modifier_label(2.0));
+    }
+}
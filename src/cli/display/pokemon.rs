@@ -1,23 +1,137 @@
+use super::StatsComponent;
 use super::{Colors, DisplayComponent};
-use crate::models::Pokemon;
+use crate::models::{Pokemon, PokemonDetails, Stat, StatTotals, Units};
 
 use std::fmt;
 
 use indoc::writedoc;
 
+impl DisplayComponent<&Pokemon> {
+    // Single line of the Pokémon's name and types
+    pub fn summary(&self) -> String {
+        let pokemon = self.context;
+        let types = pokemon
+            .get_types()
+            .into_iter()
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        format!(
+            "{header}{nickname}{header:#} {types}",
+            nickname = pokemon.nickname,
+            header = self.ansi_bold(Colors::Header)
+        )
+    }
+
+    // Single line of the name, types, and base stats, e.g. for shell loops
+    pub fn compact(&self) -> String {
+        let pokemon = self.context;
+        let types = pokemon
+            .get_types()
+            .into_iter()
+            .cloned()
+            .collect::<Vec<String>>()
+            .join("/");
+        let stats = [
+            Stat::Hp,
+            Stat::Attack,
+            Stat::Defense,
+            Stat::SpecialAttack,
+            Stat::SpecialDefense,
+            Stat::Speed,
+        ]
+        .map(|stat| pokemon.stats.get(stat).to_string())
+        .join("/");
+
+        format!(
+            "{header}{nickname}{header:#} {types} {stats} ({total})",
+            nickname = pokemon.nickname,
+            total = pokemon.stats.get(Stat::Total),
+            header = self.ansi_bold(Colors::Header)
+        )
+    }
+}
+
+pub struct PokemonListComponent<'a> {
+    pub pokemon: &'a [Pokemon],
+}
+
+impl fmt::Display for DisplayComponent<PokemonListComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let PokemonListComponent { pokemon } = self.context;
+        let header = self.ansi_bold(Colors::Header);
+
+        let rows = pokemon
+            .iter()
+            .map(|p| {
+                let types = p
+                    .get_types()
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                (p.nickname.as_str(), types, p.stats.get(Stat::Total))
+            })
+            .collect::<Vec<_>>();
+        let name_width = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0);
+        let types_width = rows.iter().map(|r| r.1.chars().count()).max().unwrap_or(0);
+
+        let lines = rows
+            .iter()
+            .map(|(name, types, total)| {
+                format!("{header}{name:name_width$}{header:#}  {types:types_width$}  {total}")
+            })
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct PokemonComponent<'a> {
+    pub pokemon: &'a Pokemon,
+    pub sort: Option<Stat>,
+    pub details: bool,
+    // Displays where the base stat total ranks in its generation
+    pub stat_totals: Option<&'a StatTotals>,
+    pub units: Units,
+}
+
 impl fmt::Display for DisplayComponent<&Pokemon> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pokemon_context = PokemonComponent {
+            pokemon: self.context,
+            sort: None,
+            details: false,
+            stat_totals: None,
+            units: Units::default(),
+        };
+        DisplayComponent::new(pokemon_context, self.color_enabled).fmt(f)
+    }
+}
+
+impl fmt::Display for DisplayComponent<PokemonComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let PokemonComponent {
+            pokemon,
+            sort,
+            details,
+            stat_totals,
+            units,
+        } = self.context;
         let Pokemon {
             name,
             nickname,
             generation,
             primary_type,
             secondary_type,
+            tertiary_type,
             group,
             stats,
             abilities,
+            details: pokemon_details,
             ..
-        } = self.context;
+        } = pokemon;
 
         let name_header = if nickname != name {
             format!(
@@ -31,12 +145,13 @@ impl fmt::Display for DisplayComponent<&Pokemon> {
             )
         };
 
-        let secondary_type = match secondary_type {
-            Some(type_) => format!(" {type_} "),
-            None => " ".to_string(),
-        };
+        let other_types = [secondary_type, tertiary_type]
+            .into_iter()
+            .flatten()
+            .fold(String::from(" "), |types, type_| types + type_ + " ");
 
-        let stats_display = DisplayComponent::new(stats, self.color_enabled);
+        let stats_context = StatsComponent { stats, sort };
+        let stats_display = DisplayComponent::new(stats_context, self.color_enabled);
         let abilities = abilities
             .iter()
             .map(|a| {
@@ -49,11 +164,35 @@ impl fmt::Display for DisplayComponent<&Pokemon> {
             .collect::<Vec<_>>()
             .join(" ");
 
+        let details = if details {
+            let PokemonDetails {
+                capture_rate,
+                base_experience,
+                growth_rate,
+                ..
+            } = pokemon_details;
+            let base_experience = base_experience.map_or(String::from("N/A"), |e| e.to_string());
+            format!(
+                "\ncatch rate: {capture_rate}  base exp: {base_experience}  growth: {growth_rate}\nheight: {height}  weight: {weight}",
+                height = pokemon_details.format_height(units),
+                weight = pokemon_details.format_weight(units),
+            )
+        } else {
+            String::from("")
+        };
+
+        let rank = if let Some(stat_totals) = stat_totals {
+            let total = stats.get(Stat::Total);
+            format!("\nBST {total} — top {}%", stat_totals.top_percent(total))
+        } else {
+            String::from("")
+        };
+
         writedoc! {
             f,
-            "{name_header} {primary_type}{secondary_type}{yellow}{group}{yellow:#}
+            "{name_header} {primary_type}{other_types}{yellow}{group}{yellow:#}{details}
             {abilities}
-            {stats_display}
+            {stats_display}{rank}
             gen-{generation}",
             yellow = self.ansi(Colors::Yellow),
         }
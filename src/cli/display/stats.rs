@@ -1,37 +1,287 @@
-use super::{Colors, DisplayComponent};
-use crate::models::Stats;
+use super::{markdown_table, Colors, DisplayComponent};
+use crate::models::{EvSpread, Stat, Stats};
 
 use std::fmt;
 
-use indoc::writedoc;
+pub struct StatsComponent<'a> {
+    pub stats: &'a Stats,
+    // Puts this stat first and the others from highest to lowest, with the total last
+    pub sort: Option<Stat>,
+}
+
+// Stats with their labels, in the standard order unless sorted
+fn stat_columns(stats: &Stats, sort: Option<Stat>) -> Vec<(Stat, &'static str, i64)> {
+    let column = |label: &'static str, stat: Stat| (stat, label, stats.get(stat));
+    let mut columns = if stats.is_gen1_special() {
+        // PokéAPI does not keep the original Special stat; special attack matches it for most Pokémon
+        vec![
+            column("hp", Stat::Hp),
+            column("atk", Stat::Attack),
+            column("def", Stat::Defense),
+            column("spc", Stat::SpecialAttack),
+            column("spd", Stat::Speed),
+        ]
+    } else {
+        vec![
+            column("hp", Stat::Hp),
+            column("atk", Stat::Attack),
+            column("def", Stat::Defense),
+            column("satk", Stat::SpecialAttack),
+            column("sdef", Stat::SpecialDefense),
+            column("spd", Stat::Speed),
+        ]
+    };
+
+    if let Some(sort) = sort {
+        // Special defense is folded into the Special stat in generation 1
+        let sort = match sort {
+            Stat::SpecialDefense if stats.is_gen1_special() => Stat::SpecialAttack,
+            sort => sort,
+        };
+        // Stable, so tied stats keep the standard order
+        columns.sort_by_key(|(_, _, value)| std::cmp::Reverse(*value));
+        columns.push(column("total", Stat::Total));
+        if let Some(index) = columns.iter().position(|c| c.0 == sort) {
+            let column = columns.remove(index);
+            columns.insert(0, column);
+        }
+    } else {
+        columns.push(column("total", Stat::Total));
+    }
+
+    columns
+}
+
+impl fmt::Display for DisplayComponent<StatsComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let StatsComponent { stats, sort } = self.context;
+
+        // 255 is the actual stat ceiling, but 200 is the ceiling for the vast majority of pokemon.
+        // 720 is based on Arceus' total stats, 600 on Mewtwo's in generation 1
+        let total_ceiling = if stats.is_gen1_special() { 600 } else { 720 };
+        let columns = stat_columns(stats, sort)
+            .into_iter()
+            .map(|(stat, label, value)| {
+                let color = match stat {
+                    Stat::Total => self.ansi_bold(Colors::rate(value, total_ceiling)),
+                    _ => self.ansi(Colors::rate(value, 200)),
+                };
+                (label, value, color)
+            })
+            .collect::<Vec<_>>();
+
+        let header = columns
+            .iter()
+            .map(|(label, _, _)| format!("{label:<6}"))
+            .collect::<String>();
+        // Each style is reset right after its value so that it does not bleed into the padding
+        let values = columns
+            .iter()
+            .map(|(_, value, color)| {
+                let value = value.to_string();
+                let padding = " ".repeat(6usize.saturating_sub(value.len()));
+                format!("{color}{value}{color:#}{padding}")
+            })
+            .collect::<String>();
+
+        write!(f, "{header}\n{values}", header = header.trim_end())
+    }
+}
+impl DisplayComponent<StatsComponent<'_>> {
+    pub fn markdown(&self) -> String {
+        let StatsComponent { stats, sort } = self.context;
+        let columns = stat_columns(stats, sort);
+        let headers = columns
+            .iter()
+            .map(|(_, label, _)| *label)
+            .collect::<Vec<&str>>();
+        let values = columns
+            .iter()
+            .map(|(_, _, value)| value.to_string())
+            .collect();
+        markdown_table(&headers, vec![values])
+    }
+}
 
 impl fmt::Display for DisplayComponent<&Stats> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Stats {
-            hp,
-            attack,
-            defense,
-            special_attack,
-            special_defense,
-            speed,
-        } = self.context;
-        let total = hp + attack + defense + special_attack + special_defense + speed;
-
-        // 255 is the actual stat ceiling, but 200 is the ceiling for the vast majority of pokemon
-        let hp_color = self.ansi(Colors::rate(*hp, 200));
-        let at_color = self.ansi(Colors::rate(*attack, 200));
-        let df_color = self.ansi(Colors::rate(*defense, 200));
-        let sat_color = self.ansi(Colors::rate(*special_attack, 200));
-        let sdf_color = self.ansi(Colors::rate(*special_defense, 200));
-        let spd_color = self.ansi(Colors::rate(*speed, 200));
-        // 720 is based on Arceus' total stats
-        let total_color = self.ansi_bold(Colors::rate(total, 720));
-
-        writedoc! {
+        let stats_context = StatsComponent {
+            stats: self.context,
+            sort: None,
+        };
+        DisplayComponent::new(stats_context, self.color_enabled).fmt(f)
+    }
+}
+
+// Spread as it is written in Showdown team exports
+impl fmt::Display for DisplayComponent<&EvSpread> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let EvSpread { evs, nature } = self.context;
+        let evs = evs
+            .iter()
+            .map(|(stat, ev)| format!("{ev} {}", showdown_label(*stat)))
+            .collect::<Vec<String>>()
+            .join(" / ");
+
+        write!(
             f,
-            "hp    atk   def   satk  sdef  spd   total
-            {hp_color}{hp:<6}{at_color}{attack:<6}{df_color}{defense:<6}{sat_color}{special_attack:<6}\
-            {sdf_color}{special_defense:<6}{spd_color}{speed:<6}{total_color}{total:<6}{total_color:#}",
+            "{header}ev spread{header:#}\nEVs: {evs}\n{nature} Nature",
+            header = self.ansi_bold(Colors::Header)
+        )
+    }
+}
+
+fn showdown_label(stat: Stat) -> &'static str {
+    match stat {
+        Stat::Hp => "HP",
+        Stat::Attack => "Atk",
+        Stat::Defense => "Def",
+        Stat::SpecialAttack => "SpA",
+        Stat::SpecialDefense => "SpD",
+        Stat::Speed => "Spe",
+        Stat::Total => "Total",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Role;
+
+    fn mewtwo(generation: u8) -> Stats {
+        Stats {
+            hp: 106,
+            attack: 110,
+            defense: 90,
+            special_attack: 154,
+            special_defense: 90,
+            speed: 130,
+            generation,
         }
     }
+
+    #[test]
+    fn display_gen1_special() {
+        let stats = mewtwo(1);
+        assert_eq!(
+            "hp    atk   def   spc   spd   total\n106   110   90    154   130   590   ",
+            DisplayComponent::new(&stats, Some(false)).to_string()
+        );
+
+        let stats = mewtwo(2);
+        assert_eq!(
+            "hp    atk   def   satk  sdef  spd   total\n106   110   90    154   90    130   680   ",
+            DisplayComponent::new(&stats, Some(false)).to_string()
+        );
+    }
+
+    #[test]
+    fn display_ev_spread() {
+        let spread = mewtwo(9).suggest_spread(Role::Special);
+        assert_eq!(
+            "ev spread\nEVs: 4 HP / 252 SpA / 252 Spe\nTimid Nature",
+            DisplayComponent::new(&spread, Some(false)).to_string()
+        );
+    }
+
+    #[test]
+    fn markdown_stats() {
+        // Colors are omitted even when enabled
+        let stats = mewtwo(2);
+        assert_eq!(
+            "| hp | atk | def | satk | sdef | spd | total |\n\
+            | --- | --- | --- | --- | --- | --- | --- |\n\
+            | 106 | 110 | 90 | 154 | 90 | 130 | 680 |\n",
+            DisplayComponent::new(
+                StatsComponent {
+                    stats: &stats,
+                    sort: None
+                },
+                Some(true)
+            )
+            .markdown()
+        );
+
+        let stats = mewtwo(1);
+        assert_eq!(
+            "| hp | atk | def | spc | spd | total |\n\
+            | --- | --- | --- | --- | --- | --- |\n\
+            | 106 | 110 | 90 | 154 | 130 | 590 |\n",
+            DisplayComponent::new(
+                StatsComponent {
+                    stats: &stats,
+                    sort: None
+                },
+                Some(true)
+            )
+            .markdown()
+        );
+    }
+
+    #[test]
+    fn display_sorted_stats() {
+        let stats = mewtwo(2);
+        let display = DisplayComponent::new(
+            StatsComponent {
+                stats: &stats,
+                sort: Some(Stat::Speed),
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "spd   satk  atk   hp    def   sdef  total\n130   154   110   106   90    90    680   ",
+            display.to_string()
+        );
+
+        let stats = mewtwo(1);
+        let display = DisplayComponent::new(
+            StatsComponent {
+                stats: &stats,
+                sort: Some(Stat::SpecialDefense),
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "spc   spd   atk   hp    def   total\n154   130   110   106   90    590   ",
+            display.to_string()
+        );
+    }
+
+    #[test]
+    fn markdown_sorted_stats() {
+        let stats = mewtwo(2);
+        let display = DisplayComponent::new(
+            StatsComponent {
+                stats: &stats,
+                sort: Some(Stat::Attack),
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "| atk | satk | spd | hp | def | sdef | total |\n\
+            | --- | --- | --- | --- | --- | --- | --- |\n\
+            | 110 | 154 | 130 | 106 | 90 | 90 | 680 |\n",
+            display.markdown()
+        );
+    }
+
+    #[test]
+    fn sorted_total_resets_bold() {
+        let stats = mewtwo(2);
+        let display = DisplayComponent::new(
+            StatsComponent {
+                stats: &stats,
+                sort: Some(Stat::Total),
+            },
+            Some(true),
+        )
+        .to_string();
+        let values = display.lines().nth(1).unwrap();
+        let reset = "\u{1b}[0m";
+
+        assert!(values.contains(&format!("680{reset}   ")));
+        // Every value closes its own style, so none of them carries the bold of the total
+        assert_eq!(7, values.matches(reset).count());
+        assert!(values.ends_with(&format!("90{reset}    ")));
+    }
 }
@@ -0,0 +1,74 @@
+use super::{Colors, DisplayComponent};
+use crate::models::Synergy;
+
+use std::fmt;
+
+pub struct SynergyComponent<'a> {
+    pub pokemon: &'a str,
+    pub partner: &'a str,
+    pub synergy: &'a Synergy,
+}
+
+impl fmt::Display for DisplayComponent<SynergyComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let SynergyComponent {
+            pokemon,
+            partner,
+            synergy,
+        } = self.context;
+        let header = self.ansi_bold(Colors::Header);
+
+        write!(f, "{header}{pokemon} with {partner}{header:#}")?;
+        write!(f, "\nweaknesses {partner} resists")?;
+        self.write_checks(f, &synergy.weaknesses)?;
+        write!(f, "\nresists {partner} hits super effectively")?;
+        self.write_checks(f, &synergy.walls)
+    }
+}
+
+impl DisplayComponent<SynergyComponent<'_>> {
+    fn write_checks(&self, f: &mut fmt::Formatter, checks: &[(String, bool)]) -> fmt::Result {
+        if checks.is_empty() {
+            return write!(f, "\nnone");
+        }
+
+        for (type_, covered) in checks {
+            let (mark, color) = if *covered {
+                ("[x]", self.ansi(Colors::Green))
+            } else {
+                ("[ ]", self.ansi(Colors::Red))
+            };
+            write!(f, "\n{color}{mark}{color:#} {type_}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_synergy() {
+        let synergy = Synergy {
+            weaknesses: vec![
+                (String::from("grass"), true),
+                (String::from("electric"), false),
+            ],
+            walls: vec![],
+        };
+        let display = DisplayComponent::new(
+            SynergyComponent {
+                pokemon: "blastoise",
+                partner: "charizard",
+                synergy: &synergy,
+            },
+            Some(false),
+        );
+
+        assert_eq!(
+            "blastoise with charizard\nweaknesses charizard resists\n[x] grass\n[ ] electric\nresists charizard hits super effectively\nnone",
+            display.to_string()
+        );
+    }
+}
@@ -1,5 +1,5 @@
-use super::{Colors, DisplayComponent, WeaknessDisplay};
-use crate::models::{TypeChart, TypeCharts};
+use super::{Colors, DisplayComponent, Effects, WeaknessDisplay};
+use crate::models::{TypeChart, TypeCharts, TypeScore};
 
 use std::fmt;
 
@@ -7,19 +7,25 @@ use indoc::writedoc;
 
 pub struct TypeChartComponent<'a> {
     pub type_chart: &'a dyn TypeChart,
+    // Draws a bar per type instead of grouping the types by multiplier
+    pub bars: bool,
 }
 
 impl fmt::Display for DisplayComponent<TypeChartComponent<'_>> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let TypeChartComponent { type_chart } = self.context;
+        let TypeChartComponent { type_chart, bars } = self.context;
         let label = match type_chart.get_type() {
             TypeCharts::Offense => type_chart.get_label() + " offense",
             TypeCharts::Defense => type_chart.get_label() + " defense",
         };
-        let chart = type_chart.get_chart();
+        let chart = type_chart.get_sorted_chart();
 
-        let weakness_groups = self.group_by_weakness(chart, |item| Some((item.0.clone(), *item.1)));
-        let type_chart = self.format_groups(weakness_groups);
+        let type_chart = if bars {
+            self.format_bars(chart)
+        } else {
+            let weakness_groups = self.group_by_weakness(chart, Some);
+            self.format_groups(weakness_groups)
+        };
 
         writedoc! {
             f,
@@ -32,7 +38,339 @@ impl fmt::Display for DisplayComponent<TypeChartComponent<'_>> {
 impl WeaknessDisplay<String> for DisplayComponent<TypeChartComponent<'_>> {
     fn format_group(&self, label: &'static str, mut types: Vec<String>, color: Colors) -> String {
         types.sort();
-        let style = self.ansi(color);
+        // Extremes are marked in text as well so that they stand out without color
+        let (label, style) = match label {
+            "quad" => (
+                "quad (!)",
+                self.style().fg(color).effect(Effects::Bold).ansi(),
+            ),
+            "zero" => ("zero (immune)", self.ansi(color)),
+            label => (label, self.ansi(color)),
+        };
         format!("\n{label}: {style}{}{style:#}", types.join(" "))
     }
 }
+
+impl DisplayComponent<TypeChartComponent<'_>> {
+    // Two characters per 1x so that halves fit; without color, ¼x is a dot and immunities are blank
+    fn format_bars(&self, mut chart: Vec<(String, f32)>) -> String {
+        chart.sort_by(|(a_type, a), (b_type, b)| b.total_cmp(a).then(a_type.cmp(b_type)));
+        let block = if self.is_color_enabled() { "█" } else { "#" };
+        let bar = |multiplier: f32| match multiplier {
+            0.0 => String::new(),
+            m if m < 0.5 => String::from("."),
+            m => block.repeat((m * 2.0) as usize),
+        };
+
+        let type_width = chart.iter().map(|(type_, _)| type_.len()).max();
+        let bar_width = chart.iter().map(|(_, m)| bar(*m).chars().count()).max();
+        let (type_width, bar_width) = (type_width.unwrap_or(0), bar_width.unwrap_or(0));
+
+        chart
+            .into_iter()
+            .map(|(type_, multiplier)| {
+                let bar = bar(multiplier);
+                let padding = " ".repeat(bar_width - bar.chars().count());
+                let color = self.ansi(Colors::multiplier(multiplier));
+                format!(
+                    "\n{type_:type_width$}  {color}{bar}{color:#}{padding}  {label}",
+                    label = self.multiplier_label(multiplier)
+                )
+            })
+            .collect()
+    }
+
+    // Immunities are easy to miss among the other groups, so they get their own line
+    pub fn immunities(&self) -> Option<String> {
+        let types = self
+            .context
+            .type_chart
+            .get_sorted_chart()
+            .into_iter()
+            .filter(|(_, multiplier)| *multiplier == 0.0)
+            .map(|(type_, _)| type_)
+            .collect::<Vec<String>>();
+        if types.is_empty() {
+            return None;
+        }
+
+        let violet = self.ansi_bold(Colors::Violet);
+        Some(format!("{violet}immune to:{violet:#} {}", types.join(" ")))
+    }
+}
+
+// Types hit super effectively by any of the charts, on one line; dual types are de-duplicated
+pub struct SuperEffectiveComponent<'a> {
+    pub type_charts: &'a [&'a dyn TypeChart],
+}
+
+impl fmt::Display for DisplayComponent<SuperEffectiveComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let SuperEffectiveComponent { type_charts } = self.context;
+        let label = type_charts
+            .iter()
+            .map(|chart| chart.get_label())
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        let mut types = type_charts
+            .iter()
+            .flat_map(|chart| chart.get_sorted_chart())
+            .filter(|(_, multiplier)| *multiplier >= 2.0)
+            .map(|(type_, _)| type_)
+            .collect::<Vec<String>>();
+        types.sort();
+        types.dedup();
+        let types = if types.is_empty() {
+            String::from("none")
+        } else {
+            let orange = self.ansi(Colors::Orange);
+            format!("{orange}{}{orange:#}", types.join(" "))
+        };
+
+        write!(
+            f,
+            "{header}{label} super effective:{header:#} {types}",
+            header = self.ansi_bold(Colors::Header)
+        )
+    }
+}
+
+impl fmt::Display for DisplayComponent<&TypeScore> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let TypeScore { defense, offense } = self.context;
+        let defense_color = match defense {
+            defense if *defense > 0 => self.ansi(Colors::Green),
+            0 => self.ansi(Colors::Yellow),
+            _ => self.ansi(Colors::Red),
+        };
+
+        writedoc! {
+            f,
+            "
+            {header}score{header:#}
+            defense: {defense_color}{defense:+}{defense_color:#}
+            offense: {offense}",
+            header = self.ansi_bold(Colors::Header),
+        }
+    }
+}
+
+// Multipliers between the given types and a set of opposing types, e.g. fire vs water
+pub struct TypeMatchupComponent<'a> {
+    pub types: &'a [String],
+    pub vs: &'a [String],
+    // Each of the types attacking the combined opposing types
+    pub offense: &'a [f32],
+    // Each of the opposing types attacking the combined types
+    pub defense: &'a [f32],
+}
+
+impl fmt::Display for DisplayComponent<TypeMatchupComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let TypeMatchupComponent {
+            types,
+            vs,
+            offense,
+            defense,
+        } = self.context;
+        let header = self.ansi_bold(Colors::Header);
+
+        write!(f, "{header}offense{header:#}")?;
+        for (type_, multiplier) in types.iter().zip(offense) {
+            let multiplier = self.multiplier_label(*multiplier);
+            write!(f, "\n{type_} vs {}: {multiplier}", vs.join(" "))?;
+        }
+
+        write!(f, "\n{header}defense{header:#}")?;
+        for (type_, multiplier) in vs.iter().zip(defense) {
+            let multiplier = self.multiplier_label(*multiplier);
+            write!(f, "\n{type_} vs {}: {multiplier}", types.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+// Defending typings that an offensive core of types does not hit super effectively
+pub struct TypeCoverageComponent<'a> {
+    pub types: &'a [String],
+    pub uncovered: &'a [(Vec<String>, f32)],
+}
+
+impl fmt::Display for DisplayComponent<TypeCoverageComponent<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let TypeCoverageComponent { types, uncovered } = self.context;
+        let header = self.ansi_bold(Colors::Header);
+        write!(f, "{header}{} coverage{header:#}", types.join(" "))?;
+
+        if uncovered.is_empty() {
+            return write!(f, "\nEvery typing is hit super effectively.");
+        }
+
+        let mut uncovered = uncovered.iter().collect::<Vec<_>>();
+        uncovered.sort_by(|(a_types, a), (b_types, b)| a.total_cmp(b).then(a_types.cmp(b_types)));
+        for (typing, multiplier) in uncovered {
+            let multiplier = self.multiplier_label(*multiplier);
+            write!(f, "\n{}: {multiplier}", typing.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DefenseTypeChart, NewTypeChart, OffenseTypeChart};
+
+    #[test]
+    fn display_extreme_groups() {
+        let mut chart = DefenseTypeChart::new_struct(
+            [
+                ("water", 4.0),
+                ("grass", 4.0),
+                ("fire", 1.0),
+                ("electric", 0.0),
+            ]
+            .into_iter()
+            .map(|(t, m)| (String::from(t), m))
+            .collect(),
+        );
+        chart.set_label("golem");
+        let display = DisplayComponent::new(
+            TypeChartComponent {
+                type_chart: &chart,
+                bars: false,
+            },
+            Some(false),
+        );
+
+        assert_eq!(
+            "golem defense\nquad (!): grass water\nneutral: fire\nzero (immune): electric",
+            display.to_string()
+        );
+        assert_eq!(
+            Some(String::from("immune to: electric")),
+            display.immunities()
+        );
+    }
+
+    #[test]
+    fn display_bars() {
+        let mut chart = DefenseTypeChart::new_struct(
+            [
+                ("water", 4.0),
+                ("rock", 0.5),
+                ("fire", 1.0),
+                ("normal", 0.25),
+                ("electric", 0.0),
+                ("grass", 2.0),
+            ]
+            .into_iter()
+            .map(|(t, m)| (String::from(t), m))
+            .collect(),
+        );
+        chart.set_label("golem");
+        let display = DisplayComponent::new(
+            TypeChartComponent {
+                type_chart: &chart,
+                bars: true,
+            },
+            Some(false),
+        );
+
+        assert_eq!(
+            "golem defense\nwater     ########  x4\ngrass     ####      x2\nfire      ##        x1\nrock      #         x0.5\nnormal    .         x0.25\nelectric            x0",
+            display.to_string()
+        );
+    }
+
+    #[test]
+    fn display_super_effective() {
+        let chart = |label: &str, relations: &[(&str, f32)]| {
+            let mut chart = OffenseTypeChart::new_struct(
+                relations
+                    .iter()
+                    .map(|(t, m)| (String::from(*t), *m))
+                    .collect(),
+            );
+            chart.set_label(label);
+            chart
+        };
+        let ground = chart(
+            "ground",
+            &[("rock", 2.0), ("fire", 2.0), ("grass", 0.5), ("water", 1.0)],
+        );
+        let water = chart("water", &[("rock", 2.0), ("fire", 2.0), ("ground", 2.0)]);
+        let normal = chart("normal", &[("rock", 0.5), ("ghost", 0.0)]);
+
+        let dual: [&dyn TypeChart; 2] = [&ground, &water];
+        let display =
+            DisplayComponent::new(SuperEffectiveComponent { type_charts: &dual }, Some(false));
+        assert_eq!(
+            "ground water super effective: fire ground rock",
+            display.to_string()
+        );
+
+        let single: [&dyn TypeChart; 1] = [&normal];
+        let display = DisplayComponent::new(
+            SuperEffectiveComponent {
+                type_charts: &single,
+            },
+            Some(false),
+        );
+        assert_eq!("normal super effective: none", display.to_string());
+    }
+
+    #[test]
+    fn display_type_score() {
+        let score = TypeScore {
+            defense: -3,
+            offense: 4,
+        };
+        let display = DisplayComponent::new(&score, Some(false));
+
+        assert_eq!("score\ndefense: -3\noffense: 4", display.to_string());
+    }
+
+    #[test]
+    fn display_type_coverage() {
+        let types = [String::from("fire"), String::from("ground")];
+        let uncovered = [
+            (vec![String::from("flying")], 1.0),
+            (vec![String::from("water"), String::from("flying")], 0.5),
+        ];
+        let display = DisplayComponent::new(
+            TypeCoverageComponent {
+                types: &types,
+                uncovered: &uncovered,
+            },
+            Some(false),
+        );
+
+        assert_eq!(
+            "fire ground coverage\nwater flying: x0.5\nflying: x1",
+            display.to_string()
+        );
+    }
+
+    #[test]
+    fn display_type_matchup() {
+        let types = [String::from("fire")];
+        let vs = [String::from("water"), String::from("ground")];
+        let display = DisplayComponent::new(
+            TypeMatchupComponent {
+                types: &types,
+                vs: &vs,
+                offense: &[0.5],
+                defense: &[2.0, 2.0],
+            },
+            Some(false),
+        );
+        assert_eq!(
+            "offense\nfire vs water ground: x0.5\ndefense\nwater vs fire: x2\nground vs fire: x2",
+            display.to_string()
+        );
+    }
+}
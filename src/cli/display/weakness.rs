@@ -21,12 +21,12 @@ pub trait WeaknessDisplay<T> {
             if let Some(result) = cb(element) {
                 let (item, multiplier) = result;
                 match multiplier {
-                    x if x == 4.0 => groups.quad.push(item),
-                    x if x == 2.0 => groups.double.push(item),
-                    x if x == 1.0 => groups.neutral.push(item),
-                    x if x == 0.5 => groups.half.push(item),
-                    x if x == 0.25 => groups.quarter.push(item),
-                    x if x == 0.0 => groups.zero.push(item),
+                    4.0 => groups.quad.push(item),
+                    2.0 => groups.double.push(item),
+                    1.0 => groups.neutral.push(item),
+                    0.5 => groups.half.push(item),
+                    0.25 => groups.quarter.push(item),
+                    0.0 => groups.zero.push(item),
                     _ => groups.other.push(item),
                 }
             }
@@ -80,6 +80,15 @@ pub trait WeaknessDisplay<T> {
     fn format_group(&self, label: &'static str, group: Vec<T>, color: Colors) -> String;
 }
 
+// Snaps a modified multiplier (e.g. 1.5 from weather) to the nearest bucket
+// Values outside of the quarter to quad range are left for the "other" group
+pub fn nearest_multiplier(multiplier: f32) -> f32 {
+    if multiplier == 0.0 || !(0.25..=4.0).contains(&multiplier) {
+        return multiplier;
+    }
+    multiplier.log2().round().exp2()
+}
+
 pub struct WeaknessGroups<T> {
     pub quad: Vec<T>,
     pub double: Vec<T>,
@@ -89,3 +98,21 @@ pub struct WeaknessGroups<T> {
     pub zero: Vec<T>,
     pub other: Vec<T>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_multiplier_snaps_to_bucket() {
+        assert_eq!(4.0, nearest_multiplier(4.0));
+        assert_eq!(4.0, nearest_multiplier(2.0 * 1.5));
+        assert_eq!(2.0, nearest_multiplier(1.5));
+        assert_eq!(2.0, nearest_multiplier(2.0 * 1.3));
+        assert_eq!(1.0, nearest_multiplier(1.3));
+        assert_eq!(1.0, nearest_multiplier(2.0 * 0.5));
+        assert_eq!(0.5, nearest_multiplier(2.0 / 3.0));
+        assert_eq!(0.0, nearest_multiplier(0.0));
+        assert_eq!(6.0, nearest_multiplier(4.0 * 1.5));
+    }
+}
@@ -1,19 +1,52 @@
 use crate::models::Pokemon;
-use std::io::{stdout, IsTerminal};
+use std::io::{stdout, IsTerminal, Result as IoResult, Write};
+
+use terminal_size::{terminal_size, Width};
 
 pub fn is_color_enabled() -> bool {
-    if let Ok(force_color) = std::env::var("FORCE_COLOR") {
-        if is_env_affirmative(&force_color) {
-            return true;
-        }
-    };
-    if let Ok(no_color) = std::env::var("NO_COLOR") {
-        if is_env_affirmative(&no_color) {
-            return false;
-        }
-    };
+    color_override(None, None, env_var).unwrap_or_else(is_terminal)
+}
+
+// Precedence: --color/--no-color flags, then the environment, then the config file.
+// None leaves the decision to terminal detection.
+pub fn color_override(
+    flag: Option<bool>,
+    config: Option<bool>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<bool> {
+    flag.or_else(|| env_color(env)).or(config)
+}
+
+// FORCE_COLOR and CLICOLOR_FORCE win over NO_COLOR, which wins over CLICOLOR=0
+fn env_color(env: impl Fn(&str) -> Option<String>) -> Option<bool> {
+    let forced = ["FORCE_COLOR", "CLICOLOR_FORCE"]
+        .into_iter()
+        .any(|key| env(key).is_some_and(|value| is_env_affirmative(&value)));
+    if forced {
+        return Some(true);
+    }
+
+    if env("NO_COLOR").is_some_and(|value| is_env_affirmative(&value)) {
+        return Some(false);
+    }
+    if env("CLICOLOR").is_some_and(|value| is_env_negative(&value)) {
+        return Some(false);
+    }
+
+    None
+}
+
+// An explicit --flag or --no-flag wins over the config default
+pub fn flag_override(on: bool, off: bool, default: bool) -> bool {
+    match (on, off) {
+        (true, _) => true,
+        (_, true) => false,
+        _ => default,
+    }
+}
 
-    is_terminal()
+pub fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok()
 }
 
 pub fn is_env_negative(value: &str) -> bool {
@@ -29,10 +62,199 @@ pub fn is_terminal() -> bool {
     stdout().is_terminal()
 }
 
+pub fn terminal_width() -> usize {
+    if is_terminal() {
+        if let Some((Width(width), _)) = terminal_size() {
+            return width as usize;
+        }
+    }
+
+    80
+}
+
+// Custom Pokémon may override or add a third type, so the effective typing is checked
 pub fn is_stab(type_: &str, pokemon: &Pokemon) -> bool {
-    if let Some(secondary_type) = &pokemon.secondary_type {
-        type_ == pokemon.primary_type || type_ == secondary_type
-    } else {
-        type_ == pokemon.primary_type
+    pokemon.get_types().into_iter().any(|t| t == type_)
+}
+
+// Swaps characters that some terminals mangle for ASCII equivalents, e.g. Pokémon for Pokemon
+pub fn to_ascii(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            c if c.is_ascii() => ascii.push(c),
+            'é' | 'è' | 'ê' => ascii.push('e'),
+            'É' => ascii.push('E'),
+            '’' | '‘' => ascii.push('\''),
+            '“' | '”' => ascii.push('"'),
+            '—' | '–' => ascii.push('-'),
+            '×' => ascii.push('x'),
+            '█' => ascii.push('#'),
+            '♀' => ascii.push('F'),
+            '♂' => ascii.push('M'),
+            _ => ascii.push('?'),
+        }
+    }
+    ascii
+}
+
+// Applies to_ascii to everything written; a character split across writes waits for its remainder
+pub struct AsciiWriter<W: Write> {
+    inner: W,
+    pending: Vec<u8>,
+}
+impl<W: Write> AsciiWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            pending: vec![],
+        }
+    }
+}
+impl<W: Write> Write for AsciiWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.pending.extend_from_slice(buf);
+        let valid_len = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            // Invalid bytes are written as they are rather than held back forever
+            Err(e) if e.error_len().is_some() => self.pending.len(),
+            Err(e) => e.valid_up_to(),
+        };
+
+        let written = self.pending.drain(..valid_len).collect::<Vec<u8>>();
+        self.inner
+            .write_all(to_ascii(&String::from_utf8_lossy(&written)).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            self.inner
+                .write_all(to_ascii(&String::from_utf8_lossy(&pending)).as_bytes())?;
+        }
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::database::{PokemonRow, Validate};
+    use crate::models::FromCustom;
+    use crate::resource::custom::{CustomPokemon, CustomStats, CustomTypes};
+    use crate::resource::database::DatabaseFile;
+    use rusqlite::Connection;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        |key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn flag_beats_config_default() {
+        assert!(!flag_override(false, true, true));
+        assert!(flag_override(true, false, false));
+        assert!(flag_override(false, false, true));
+        assert!(!flag_override(false, false, false));
+    }
+
+    #[test]
+    fn color_flag_beats_everything() {
+        let vars = [("FORCE_COLOR", "1")];
+        assert_eq!(
+            Some(false),
+            color_override(Some(false), Some(true), env(&vars))
+        );
+
+        let vars = [("NO_COLOR", "1")];
+        assert_eq!(
+            Some(true),
+            color_override(Some(true), Some(false), env(&vars))
+        );
+    }
+
+    #[test]
+    fn color_env_beats_config() {
+        assert_eq!(
+            Some(false),
+            color_override(None, Some(true), env(&[("NO_COLOR", "1")]))
+        );
+        assert_eq!(
+            Some(true),
+            color_override(None, Some(false), env(&[("CLICOLOR_FORCE", "1")]))
+        );
+        assert_eq!(
+            Some(false),
+            color_override(None, Some(true), env(&[("CLICOLOR", "0")]))
+        );
+
+        // Forcing wins over disabling within the environment
+        let vars = [("NO_COLOR", "1"), ("FORCE_COLOR", "true")];
+        assert_eq!(Some(true), color_override(None, None, env(&vars)));
+    }
+
+    #[test]
+    fn color_config_beats_terminal() {
+        assert_eq!(Some(true), color_override(None, Some(true), env(&[])));
+
+        // Negative or non-disabling values leave the decision to the config
+        let vars = [("NO_COLOR", "false"), ("CLICOLOR", "1")];
+        assert_eq!(Some(false), color_override(None, Some(false), env(&vars)));
+    }
+
+    #[test]
+    fn color_falls_back_to_terminal() {
+        assert_eq!(None, color_override(None, None, env(&[])));
+    }
+
+    #[test]
+    fn stab_from_custom_types() {
+        let db = DatabaseFile::default().connect().unwrap();
+        let custom = CustomPokemon {
+            nickname: String::from("sprout"),
+            base: String::from("dudunsparce"),
+            generation: 9,
+            moves: vec![],
+            types: Some(CustomTypes::Triple(
+                String::from("grass"),
+                Some(String::from("fire")),
+                Some(String::from("water")),
+            )),
+            stats: CustomStats::default(),
+        };
+        let sprout = Pokemon::from_custom(&custom, &db).unwrap();
+
+        assert!(is_stab("grass", &sprout));
+        assert!(is_stab("water", &sprout));
+        // Dudunsparce's own normal typing is replaced
+        assert!(!is_stab("normal", &sprout));
+    }
+
+    #[test]
+    fn ascii_not_found_message() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(include_str!("../sql/create_schema.sql"))
+            .unwrap();
+
+        let err = Validate::<PokemonRow>::validate(&db, "pikahcu").unwrap_err();
+        let message = to_ascii(&err.to_string());
+        assert!(message.is_ascii());
+        assert_eq!("Pokemon 'pikahcu' not found.", message);
+    }
+
+    #[test]
+    fn ascii_writer_joins_split_characters() {
+        let mut writer = AsciiWriter::new(vec![]);
+        let text = "Pokémon — Nidoran♀".as_bytes();
+        // Splits the é between writes
+        writer.write_all(&text[..4]).unwrap();
+        writer.write_all(&text[4..]).unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(b"Pokemon - NidoranF", writer.inner.as_slice());
     }
 }
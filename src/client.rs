@@ -0,0 +1,129 @@
+use crate::api::game_to_gen;
+use crate::models::database::{GameRow, Validate};
+use crate::models::{
+    Ability, DefenseTypeChart, FromName, FromNameCustom, Move, Pokemon, Type, TypeChart,
+};
+use crate::resource::custom::CustomCollection;
+use crate::resource::database::DatabaseFile;
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use rusqlite::Connection;
+
+/// Entry point for using dunspars as a library.
+///
+/// Owns the database connection and the generation resolved from a game, so lookups only need a name.
+/// Every value returned is owned and does not borrow from `Dunspars`; it may be dropped while they are kept.
+/// Lookup failures such as unknown names carry a [`crate::DunsparsError`] that can be downcast from the error.
+pub struct Dunspars {
+    db: Connection,
+    custom: CustomCollection,
+    game: String,
+    generation: u8,
+}
+impl Dunspars {
+    /// Opens the database set up by `dunspars setup`. Uses the latest game if `game` is `None`.
+    pub fn open(game: Option<&str>) -> Result<Self> {
+        Self::from_file(DatabaseFile::default(), game)
+    }
+
+    /// Opens a database at a specific path. Uses the latest game if `game` is `None`.
+    pub fn open_path(path: PathBuf, game: Option<&str>) -> Result<Self> {
+        Self::from_file(DatabaseFile::new(path), game)
+    }
+
+    fn from_file(file: DatabaseFile, game: Option<&str>) -> Result<Self> {
+        let db = file.connect()?;
+        let game = match game {
+            Some(game) => Validate::<GameRow>::validate(&db, game)?,
+            None => GameRow::select_latest(&db)?.ok_or(anyhow!("Cannot find the latest game"))?,
+        };
+        let generation = game_to_gen(&game, &db);
+
+        Ok(Self {
+            db,
+            custom: CustomCollection::default(),
+            game,
+            generation,
+        })
+    }
+
+    /// Makes custom Pokémon available to `pokemon` and `matchup` via their nicknames.
+    pub fn with_custom(mut self, custom: CustomCollection) -> Self {
+        self.custom = custom;
+        self
+    }
+
+    pub fn game(&self) -> &str {
+        &self.game
+    }
+
+    pub fn generation(&self) -> u8 {
+        self.generation
+    }
+
+    /// Borrows the underlying connection for the lower level `models` functions.
+    pub fn connection(&self) -> &Connection {
+        &self.db
+    }
+
+    pub fn pokemon(&self, name: &str) -> Result<Pokemon> {
+        Pokemon::from_name(name, self.generation, &self.db, &self.db, &self.custom)
+    }
+
+    pub fn move_(&self, name: &str) -> Result<Move> {
+        Move::from_name(name, self.generation, &self.db, &self.db)
+    }
+
+    pub fn type_(&self, name: &str) -> Result<Type> {
+        Type::from_name(name, self.generation, &self.db, &self.db)
+    }
+
+    pub fn ability(&self, name: &str) -> Result<Ability> {
+        Ability::from_name(name, self.generation, &self.db, &self.db)
+    }
+
+    pub fn matchup(&self, attacker: &str, defender: &str) -> Result<Matchup> {
+        let attacker = self.pokemon(attacker)?;
+        let defender = self.pokemon(defender)?;
+        let defender_chart = defender.get_defense_chart(&self.db)?;
+
+        Ok(Matchup {
+            attacker,
+            defender,
+            defender_chart,
+        })
+    }
+}
+
+pub struct Matchup {
+    pub attacker: Pokemon,
+    pub defender: Pokemon,
+    pub defender_chart: DefenseTypeChart,
+}
+impl Matchup {
+    /// Damage multiplier of each of the attacker's types against the defender
+    pub fn type_multipliers(&self) -> Vec<(String, f32)> {
+        self.attacker
+            .get_types()
+            .into_iter()
+            .map(|type_| (type_.clone(), self.defender_chart.get_multiplier(type_)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_missing_database() {
+        let path = std::env::temp_dir().join("dunspars-missing-database.db");
+        let err = Dunspars::open_path(path, None).err().unwrap();
+        assert_eq!(
+            "Database not set up. Run `dunspars setup` first.",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt;
+
+/// Domain errors that callers may want to tell apart, e.g. to suggest a fix.
+///
+/// They are returned within `anyhow::Error`; use `downcast_ref::<DunsparsError>()` to match on them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DunsparsError {
+    /// A name that does not match any entry of a resource, with similar names if any
+    NotFound {
+        kind: &'static str,
+        name: String,
+        matches: Vec<String>,
+    },
+    /// An entry that exists but is absent from the generation, with similar names present in it
+    NotInGeneration {
+        kind: &'static str,
+        name: String,
+        generation: u8,
+        matches: Vec<String>,
+    },
+    DatabaseMissing,
+    DatabaseMalformed,
+    VersionMismatch {
+        program: String,
+        database: String,
+    },
+    /// A database built with another table layout; None if it predates schema versions
+    SchemaMismatch {
+        program: u32,
+        database: Option<u32>,
+    },
+    /// Optional data left out of setup, e.g. encounters without `setup --with-encounters`
+    SetupDataMissing {
+        data: &'static str,
+        flag: &'static str,
+    },
+}
+
+impl fmt::Display for DunsparsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DunsparsError::NotFound {
+                kind,
+                name,
+                matches,
+            } => {
+                write!(f, "{kind} '{name}' not found.")?;
+                write_matches(f, matches)
+            }
+            DunsparsError::NotInGeneration {
+                kind,
+                name,
+                generation,
+                matches,
+            } => {
+                write!(f, "{kind} '{name}' is not present in generation {generation}")?;
+                if matches.is_empty() {
+                    return Ok(());
+                }
+                write!(f, ".")?;
+                write_matches(f, matches)
+            }
+            DunsparsError::DatabaseMissing => {
+                write!(f, "Database not set up. Run `dunspars setup` first.")
+            }
+            DunsparsError::DatabaseMalformed => {
+                write!(f, "Database malformed. Run `dunspars setup` again.")
+            }
+            DunsparsError::VersionMismatch { program, database } => write!(
+                f,
+                "Database version mismatch. Program version: {program}; Database version: {database}. Run `dunspars setup` again."
+            ),
+            DunsparsError::SchemaMismatch { program, database } => write!(
+                f,
+                "Database schema outdated. Program schema: {program}; Database schema: {}. Run `dunspars setup` again.",
+                database.map_or(String::from("none"), |schema| schema.to_string())
+            ),
+            DunsparsError::SetupDataMissing { data, flag } => write!(
+                f,
+                "{data} data not set up. Run `dunspars setup {flag}` first."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DunsparsError {}
+
+fn write_matches(f: &mut fmt::Formatter, matches: &[String]) -> fmt::Result {
+    if matches.len() > 20 {
+        write!(f, " Potential matches found; too many to display.")
+    } else if !matches.is_empty() {
+        write!(f, " Potential matches: {}.", matches.join(" "))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_messages() {
+        let not_found = DunsparsError::NotFound {
+            kind: "Move",
+            name: String::from("flamethrowr"),
+            matches: vec![String::from("flamethrower")],
+        };
+        assert_eq!(
+            "Move 'flamethrowr' not found. Potential matches: flamethrower.",
+            not_found.to_string()
+        );
+
+        let not_in_generation = DunsparsError::NotInGeneration {
+            kind: "Pokémon",
+            name: String::from("sprigatito"),
+            generation: 8,
+            matches: vec![],
+        };
+        assert_eq!(
+            "Pokémon 'sprigatito' is not present in generation 8",
+            not_in_generation.to_string()
+        );
+
+        let with_matches = DunsparsError::NotInGeneration {
+            kind: "Move",
+            name: String::from("earth-power"),
+            generation: 3,
+            matches: vec![String::from("earthquake")],
+        };
+        assert_eq!(
+            "Move 'earth-power' is not present in generation 3. Potential matches: earthquake.",
+            with_matches.to_string()
+        );
+
+        let missing = DunsparsError::SetupDataMissing {
+            data: "Encounter",
+            flag: "--with-encounters",
+        };
+        assert_eq!(
+            "Encounter data not set up. Run `dunspars setup --with-encounters` first.",
+            missing.to_string()
+        );
+
+        let schema = DunsparsError::SchemaMismatch {
+            program: 2,
+            database: None,
+        };
+        assert_eq!(
+            "Database schema outdated. Program schema: 2; Database schema: none. Run `dunspars setup` again.",
+            schema.to_string()
+        );
+
+        // The error survives being wrapped by anyhow
+        let err = anyhow::Error::from(DunsparsError::DatabaseMissing);
+        assert_eq!(
+            Some(&DunsparsError::DatabaseMissing),
+            err.downcast_ref::<DunsparsError>()
+        );
+    }
+}
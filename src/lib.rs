@@ -2,5 +2,10 @@ pub const VERSION: &str = env!("DUNSPARS_VERSION");
 
 pub mod api;
 pub mod cli;
+mod client;
+pub mod error;
 pub mod models;
 pub mod resource;
+
+pub use client::{Dunspars, Matchup};
+pub use error::DunsparsError;
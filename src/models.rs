@@ -1,17 +1,23 @@
 pub mod database;
+pub mod db;
 
+use crate::error::DunsparsError;
 use crate::resource::custom::{CustomCollection, CustomPokemon};
+use crate::resource::type_chart::TypeChartCollection;
 use database::{
-    AbilityRow, EvolutionRow, FromRow, GameRow, MoveChangeRow, MoveRow, PokemonAbilityRow,
-    PokemonMoveRow, PokemonRow, PokemonTypeChangeRow, SelectAllNames, SelectChangeRow, SelectRow,
-    SpeciesRow, TypeChangeRow, TypeRow, Validate,
+    AbilityRow, EncounterRow, EvolutionRow, FromRow, GameRow, MachineRow, MetaRow, MoveChangeRow,
+    MoveMetaRow, MoveRow, PokemonAbilityRow, PokemonMoveRow, PokemonRow, PokemonTypeChangeRow,
+    SelectAllNames, SelectChangeRow, SelectRow, SpeciesRow, TableRow, TypeChangeRow, TypeRow,
+    Validate, ValidateGeneration,
 };
 
-use std::collections::HashMap;
-use std::ops::Add;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::ops::{Add, RangeInclusive};
+use std::str::FromStr;
 
 use anyhow::{bail, Result};
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
 pub trait FromDb: Sized {
@@ -23,8 +29,13 @@ pub trait FromCustom<T>: Sized {
 }
 
 pub trait FromName<T: SelectAllNames>: FromDb {
-    fn from_name(name: &str, generation: u8, db: &Connection) -> Result<Self> {
-        let name = Validate::<T>::validate(db, name)?;
+    fn from_name(
+        name: &str,
+        generation: u8,
+        db: &Connection,
+        names: &impl ValidateGeneration<T>,
+    ) -> Result<Self> {
+        let name = names.validate_in_generation(&normalize_name(name), generation)?;
         Self::from_db(&name, generation, db)
     }
 }
@@ -34,6 +45,7 @@ pub trait FromNameCustom<T: SelectAllNames>: FromDb {
         name: &str,
         generation: u8,
         db: &Connection,
+        names: &(impl ValidateGeneration<T> + ValidateGeneration<MoveRow> + Validate<TypeRow>),
         custom: &CustomCollection,
     ) -> Result<Self>;
 }
@@ -44,6 +56,7 @@ pub struct Pokemon {
     pub nickname: String,
     pub primary_type: String,
     pub secondary_type: Option<String>,
+    pub tertiary_type: Option<String>,
     pub learnable_moves: Vec<(String, String, i64)>,
     pub moves: Vec<String>,
     pub group: PokemonGroup,
@@ -51,8 +64,65 @@ pub struct Pokemon {
     pub stats: Stats,
     pub abilities: Vec<(String, bool)>,
     pub species: String,
+    pub details: PokemonDetails,
+    // Combined from the Pokémon's types on first use
+    defense_chart: OnceCell<DefenseTypeChart>,
 }
 impl Pokemon {
+    // A hypothetical Pokémon with only a typing, e.g. to check what its STAB hits
+    pub fn from_types(types: &[Type], generation: u8) -> Result<Self> {
+        let names = types
+            .iter()
+            .map(|t| t.name.clone())
+            .collect::<Vec<String>>();
+        let Some(primary_type) = names.first().cloned() else {
+            bail!("At least one type is required");
+        };
+        if names.len() > 2 {
+            bail!("At most two types may be combined");
+        }
+        if names.len() == 2 && names[0] == names[1] {
+            bail!("Type '{primary_type}' is listed twice");
+        }
+
+        let defense_chart = OnceCell::new();
+        let _ = defense_chart
+            .set(DefenseTypeChart::fold(types.iter().map(|t| t.defense_chart.clone())).unwrap());
+        let name = names.join("/");
+
+        Ok(Self {
+            name: name.clone(),
+            nickname: name,
+            primary_type,
+            secondary_type: names.get(1).cloned(),
+            tertiary_type: None,
+            learnable_moves: vec![],
+            moves: vec![],
+            group: PokemonGroup::Regular,
+            generation,
+            stats: Stats {
+                generation,
+                ..Stats::default()
+            },
+            abilities: vec![],
+            species: String::from(""),
+            details: PokemonDetails {
+                capture_rate: 0,
+                base_experience: None,
+                growth_rate: String::from(""),
+                height: 0,
+                weight: 0,
+                sprite: None,
+            },
+            defense_chart,
+        })
+    }
+
+    // Pokémon from the database always have moves to learn, unlike ones built from_types
+    pub fn is_typing_only(&self) -> bool {
+        self.learnable_moves.is_empty()
+    }
+
     pub fn get_move_list(&self, db: &Connection) -> Result<MoveList> {
         MoveList::try_new(&self.moves, self.generation, db)
     }
@@ -66,16 +136,69 @@ impl Pokemon {
         MoveList::try_new(&move_list, self.generation, db)
     }
 
+    pub fn get_move_generations(&self, move_name: &str, db: &Connection) -> Result<Vec<u8>> {
+        let pokemon_row = PokemonRow::select_by_name(&self.name, db)?;
+        Ok(PokemonMoveRow::select_generations(
+            pokemon_row.id,
+            move_name,
+            db,
+        )?)
+    }
+
+    pub fn get_types(&self) -> Vec<&String> {
+        let mut types = vec![&self.primary_type];
+        types.extend(self.secondary_type.iter());
+        types.extend(self.tertiary_type.iter());
+        types
+    }
+
     pub fn get_defense_chart(&self, db: &Connection) -> Result<DefenseTypeChart> {
-        let primary_type = Type::from_db(&self.primary_type, self.generation, db)?;
+        self.get_custom_defense_chart(&TypeChartCollection::default(), db)
+    }
 
-        if let Some(secondary_type) = &self.secondary_type {
-            let secondary_type = Type::from_db(secondary_type, self.generation, db)?;
+    // The first chart requested is kept, so custom type charts must be applied before any other use
+    pub fn get_custom_defense_chart(
+        &self,
+        type_charts: &TypeChartCollection,
+        db: &Connection,
+    ) -> Result<DefenseTypeChart> {
+        if let Some(chart) = self.defense_chart.get() {
+            return Ok(chart.clone());
+        }
 
-            Ok(primary_type.defense_chart + secondary_type.defense_chart)
-        } else {
-            Ok(primary_type.defense_chart)
+        let mut charts = vec![];
+        for type_ in self.get_types() {
+            let type_ = Type::from_db(type_, self.generation, db)?.with_custom_chart(type_charts);
+            charts.push(type_.defense_chart);
+        }
+
+        let chart = DefenseTypeChart::fold(charts).unwrap();
+        Ok(self.defense_chart.get_or_init(|| chart).clone())
+    }
+
+    pub fn get_forms(&self, db: &Connection) -> Result<Vec<String>> {
+        let species_row = SpeciesRow::select_by_name(&self.species, db)?;
+        let forms = PokemonRow::select_by_species(species_row.id, db)?
+            .into_iter()
+            .map(|row| row.name)
+            .collect();
+        Ok(forms)
+    }
+
+    pub fn get_encounters(&self, game: &str, db: &Connection) -> Result<Vec<Encounter>> {
+        let encounters_meta = MetaRow::select_by_name("encounters", db);
+        if !encounters_meta.is_ok_and(|meta| meta.value == "true") {
+            bail!(DunsparsError::SetupDataMissing {
+                data: "Encounter",
+                flag: "--with-encounters",
+            });
         }
+
+        let encounters = EncounterRow::select_by_pokemon(&self.name, game, db)?
+            .into_iter()
+            .map(Encounter::from)
+            .collect();
+        Ok(encounters)
     }
 
     pub fn get_evolution_steps(&self, db: &Connection) -> Result<EvolutionStep> {
@@ -95,16 +218,59 @@ impl FromNameCustom<PokemonRow> for Pokemon {
         name: &str,
         generation: u8,
         db: &Connection,
+        names: &(impl ValidateGeneration<PokemonRow> + ValidateGeneration<MoveRow> + Validate<TypeRow>),
         custom: &CustomCollection,
     ) -> Result<Self> {
+        // A custom Pokémon's own generation takes precedence over the requested one
         if let Some(custom_pokemon) = custom.find_pokemon(name) {
+            validate_custom(custom_pokemon, names)?;
             Self::from_custom(custom_pokemon, db)
         } else {
-            let name = Validate::<PokemonRow>::validate(db, name)?;
+            let name = ValidateGeneration::<PokemonRow>::validate_in_generation(
+                names,
+                &normalize_name(name),
+                generation,
+            )?;
             Self::from_db(&name, generation, db)
         }
     }
 }
+// Reports every invalid move and type of a custom Pokémon at once rather than failing on first use
+pub fn validate_custom(
+    custom: &CustomPokemon,
+    names: &(impl ValidateGeneration<MoveRow> + Validate<TypeRow>),
+) -> Result<()> {
+    let mut errors = vec![];
+
+    for move_ in custom.moves.iter() {
+        let move_ =
+            ValidateGeneration::<MoveRow>::validate_in_generation(names, move_, custom.generation);
+        if let Err(e) = move_ {
+            errors.push(e.to_string());
+        }
+    }
+
+    if let Some(types) = &custom.types {
+        let (primary_type, secondary_type, tertiary_type) = types.get_types();
+        let types = [Some(primary_type), secondary_type, tertiary_type];
+        for type_ in types.iter().flatten() {
+            if let Err(e) = Validate::<TypeRow>::validate(names, &resolve_type_alias(type_)) {
+                errors.push(e.to_string());
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!(
+            "Custom Pokémon '{nickname}' is invalid:\n{errors}",
+            nickname = custom.nickname,
+            errors = errors.join("\n")
+        );
+    }
+
+    Ok(())
+}
+
 impl FromCustom<CustomPokemon> for Pokemon {
     fn from_custom(custom: &CustomPokemon, db: &Connection) -> Result<Self> {
         let pokemon_row = PokemonRow::select_by_name(&custom.base, db)?;
@@ -112,24 +278,47 @@ impl FromCustom<CustomPokemon> for Pokemon {
 
         let mut primary_type = db_pokemon.primary_type;
         let mut secondary_type = db_pokemon.secondary_type;
+        let mut tertiary_type = db_pokemon.tertiary_type;
 
-        if let Some((primary, secondary)) = &custom.types {
-            primary_type = primary.clone();
-            secondary_type = secondary.clone();
+        if let Some(types) = &custom.types {
+            let (primary, secondary, tertiary) = types.get_types();
+            primary_type = resolve_type_alias(&primary);
+            secondary_type = secondary.as_deref().map(resolve_type_alias);
+            tertiary_type = tertiary.as_deref().map(resolve_type_alias);
         }
 
+        let base_stats = db_pokemon.stats;
+        let stats = Stats {
+            hp: custom.stats.hp.unwrap_or(base_stats.hp),
+            attack: custom.stats.attack.unwrap_or(base_stats.attack),
+            defense: custom.stats.defense.unwrap_or(base_stats.defense),
+            special_attack: custom
+                .stats
+                .special_attack
+                .unwrap_or(base_stats.special_attack),
+            special_defense: custom
+                .stats
+                .special_defense
+                .unwrap_or(base_stats.special_defense),
+            speed: custom.stats.speed.unwrap_or(base_stats.speed),
+            generation: base_stats.generation,
+        };
+
         Ok(Pokemon {
             name: db_pokemon.name,
             nickname: custom.nickname.clone(),
             primary_type,
             secondary_type,
+            tertiary_type,
             learnable_moves: db_pokemon.learnable_moves,
             moves: custom.moves.clone(),
             group: db_pokemon.group,
             generation: db_pokemon.generation,
-            stats: db_pokemon.stats,
+            stats,
             abilities: db_pokemon.abilities,
             species: db_pokemon.species,
+            details: db_pokemon.details,
+            defense_chart: OnceCell::new(),
         })
     }
 }
@@ -146,6 +335,10 @@ impl FromRow<PokemonRow> for Pokemon {
             special_attack,
             special_defense,
             speed,
+            base_experience,
+            height,
+            weight,
+            sprite,
             species_id,
         } = value;
 
@@ -154,9 +347,12 @@ impl FromRow<PokemonRow> for Pokemon {
         // However, the proliferation of various pokemon forms has rendered that value inaccurate.
         // A better way is to check if they have any moves in the specified generation.
         if learn_moves.is_empty() {
-            bail!(format!(
-                "Pokémon '{name}' is not present in generation {current_gen}"
-            ));
+            bail!(DunsparsError::NotInGeneration {
+                kind: PokemonRow::label(),
+                name,
+                generation: current_gen,
+                matches: vec![],
+            });
         }
 
         let stats = Stats {
@@ -166,6 +362,7 @@ impl FromRow<PokemonRow> for Pokemon {
             special_attack,
             special_defense,
             speed,
+            generation: current_gen,
         };
 
         let type_change_row = PokemonTypeChangeRow::select_by_fk(id, current_gen, db)?;
@@ -176,6 +373,14 @@ impl FromRow<PokemonRow> for Pokemon {
 
         let species_row = SpeciesRow::select_by_id(species_id, db)?;
         let species = species_row.name.clone();
+        let details = PokemonDetails {
+            capture_rate: species_row.capture_rate,
+            base_experience,
+            growth_rate: species_row.growth_rate.clone(),
+            height,
+            weight,
+            sprite,
+        };
         let group = PokemonGroup::from(species_row);
 
         let abilities = PokemonAbilityRow::select_by_pokemon(id, db)?;
@@ -185,6 +390,7 @@ impl FromRow<PokemonRow> for Pokemon {
             name,
             primary_type,
             secondary_type,
+            tertiary_type: None,
             learnable_moves: learn_moves,
             moves: vec![],
             group,
@@ -192,10 +398,69 @@ impl FromRow<PokemonRow> for Pokemon {
             stats,
             abilities,
             species,
+            details,
+            defense_chart: OnceCell::new(),
         })
     }
 }
 
+// Playthrough and asset details; these are the same across generations
+#[derive(Debug, Clone)]
+pub struct PokemonDetails {
+    pub capture_rate: i64,
+    pub base_experience: Option<i64>,
+    pub growth_rate: String,
+    pub height: i64,
+    pub weight: i64,
+    pub sprite: Option<String>,
+}
+impl PokemonDetails {
+    // Heights are stored in decimetres, e.g. 17 is 1.7 m or 5'07"
+    pub fn format_height(&self, units: Units) -> String {
+        match units {
+            Units::Metric => format!("{:.1} m", self.height as f64 / 10.0),
+            Units::Imperial => {
+                let inches = (self.height as f64 * 10.0 / 2.54).round() as i64;
+                format!("{}'{:02}\"", inches / 12, inches % 12)
+            }
+        }
+    }
+
+    // Weights are stored in hectograms, e.g. 60 is 6.0 kg or 13.2 lbs
+    pub fn format_weight(&self, units: Units) -> String {
+        match units {
+            Units::Metric => format!("{:.1} kg", self.weight as f64 / 10.0),
+            Units::Imperial => format!("{:.1} lbs", self.weight as f64 * 0.220462),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Units {
+    #[default]
+    Metric,
+    Imperial,
+}
+impl FromStr for Units {
+    type Err = anyhow::Error;
+
+    fn from_str(units: &str) -> Result<Self> {
+        match units {
+            "metric" => Ok(Units::Metric),
+            "imperial" => Ok(Units::Imperial),
+            _ => bail!("Units must be either 'metric' or 'imperial'"),
+        }
+    }
+}
+impl std::fmt::Display for Units {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Units::Metric => write!(f, "metric"),
+            Units::Imperial => write!(f, "imperial"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum PokemonGroup {
     Mythical,
@@ -237,9 +502,210 @@ pub struct Stats {
     pub special_attack: i64,
     pub special_defense: i64,
     pub speed: i64,
+    pub generation: u8,
 }
+impl Stats {
+    // Generation 1 had a single Special stat instead of separate special attack and defense
+    pub fn is_gen1_special(&self) -> bool {
+        self.generation == 1
+    }
+
+    pub fn get(&self, stat: Stat) -> i64 {
+        match stat {
+            Stat::Hp => self.hp,
+            Stat::Attack => self.attack,
+            Stat::Defense => self.defense,
+            Stat::SpecialAttack => self.special_attack,
+            Stat::SpecialDefense => self.special_defense,
+            Stat::Speed => self.speed,
+            Stat::Total if self.is_gen1_special() => {
+                self.hp + self.attack + self.defense + self.special_attack + self.speed
+            }
+            Stat::Total => {
+                self.hp
+                    + self.attack
+                    + self.defense
+                    + self.special_attack
+                    + self.special_defense
+                    + self.speed
+            }
+        }
+    }
 
-#[derive(Debug)]
+    // Max out the role's main stat, then speed for fast Pokémon or HP otherwise.
+    // Walls shore up their weaker defense; natures lower the unused attacking stat.
+    pub fn suggest_spread(&self, role: Role) -> EvSpread {
+        // Base 80 speed is roughly where investing in speed starts to pay off
+        let fast = self.speed >= 80;
+        let (evs, nature) = match role {
+            Role::Physical if fast => ([Stat::Attack, Stat::Speed, Stat::Hp], "Jolly"),
+            Role::Physical => ([Stat::Hp, Stat::Attack, Stat::SpecialDefense], "Adamant"),
+            Role::Special if fast => ([Stat::SpecialAttack, Stat::Speed, Stat::Hp], "Timid"),
+            Role::Special => (
+                [Stat::Hp, Stat::SpecialAttack, Stat::SpecialDefense],
+                "Modest",
+            ),
+            Role::Bulky => {
+                let physical = self.attack >= self.special_attack;
+                if self.defense <= self.special_defense {
+                    let nature = if physical { "Impish" } else { "Bold" };
+                    ([Stat::Hp, Stat::Defense, Stat::SpecialDefense], nature)
+                } else {
+                    let nature = if physical { "Careful" } else { "Calm" };
+                    ([Stat::Hp, Stat::SpecialDefense, Stat::Defense], nature)
+                }
+            }
+        };
+
+        let [first, second, rest] = evs;
+        let mut evs = vec![(first, 252), (second, 252), (rest, 4)];
+        evs.sort_by_key(|(stat, _)| Stat::ALL.iter().position(|s| s == stat));
+        EvSpread { evs, nature }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Role {
+    Physical,
+    Special,
+    Bulky,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EvSpread {
+    // In Showdown order: hp, atk, def, satk, sdef, spd
+    pub evs: Vec<(Stat, i64)>,
+    pub nature: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stat {
+    Hp,
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
+    Total,
+}
+impl Stat {
+    pub const ALL: [Stat; 7] = [
+        Stat::Hp,
+        Stat::Attack,
+        Stat::Defense,
+        Stat::SpecialAttack,
+        Stat::SpecialDefense,
+        Stat::Speed,
+        Stat::Total,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Stat::Hp => "hp",
+            Stat::Attack => "atk",
+            Stat::Defense => "def",
+            Stat::SpecialAttack => "satk",
+            Stat::SpecialDefense => "sdef",
+            Stat::Speed => "spd",
+            Stat::Total => "total",
+        }
+    }
+
+    // SQL expression over the pokemon table's columns; generation 1 totals follow Stats::get
+    pub fn column(&self, generation: u8) -> &'static str {
+        match self {
+            Stat::Hp => "hp",
+            Stat::Attack => "attack",
+            Stat::Defense => "defense",
+            Stat::SpecialAttack => "special_attack",
+            Stat::SpecialDefense => "special_defense",
+            Stat::Speed => "speed",
+            Stat::Total if generation == 1 => "hp + attack + defense + special_attack + speed",
+            Stat::Total => "hp + attack + defense + special_attack + special_defense + speed",
+        }
+    }
+}
+impl std::fmt::Display for Stat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+impl FromStr for Stat {
+    type Err = anyhow::Error;
+
+    fn from_str(stat: &str) -> Result<Self> {
+        let stat = StatNames.validate(stat)?;
+        let stat = Self::ALL.into_iter().find(|s| s.as_str() == stat).unwrap();
+        Ok(stat)
+    }
+}
+
+// A stat and value such as "spd=100", used as a minimum or maximum when searching by stats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatBound {
+    pub stat: Stat,
+    pub value: i64,
+}
+impl StatBound {
+    // Folds any number of minimums and maximums into one inclusive range per stat
+    pub fn ranges(
+        min: &[StatBound],
+        max: &[StatBound],
+    ) -> Result<Vec<(Stat, RangeInclusive<i64>)>> {
+        let mut ranges = vec![];
+        for stat in Stat::ALL {
+            let bounds = |bounds: &[StatBound]| {
+                bounds
+                    .iter()
+                    .filter(|bound| bound.stat == stat)
+                    .map(|bound| bound.value)
+                    .collect::<Vec<i64>>()
+            };
+            let (mins, maxes) = (bounds(min), bounds(max));
+            if mins.is_empty() && maxes.is_empty() {
+                continue;
+            }
+
+            let from = mins.into_iter().max().unwrap_or(0);
+            let to = maxes.into_iter().min().unwrap_or(i64::MAX);
+            if from > to {
+                bail!("The minimum {stat} of {from} is above its maximum of {to}");
+            }
+            ranges.push((stat, from..=to));
+        }
+        Ok(ranges)
+    }
+}
+impl FromStr for StatBound {
+    type Err = anyhow::Error;
+
+    fn from_str(bound: &str) -> Result<Self> {
+        let Some((stat, value)) = bound.split_once('=') else {
+            bail!("Invalid stat bound '{bound}'; expected e.g. spd=100");
+        };
+        let stat = Stat::from_str(stat.trim())?;
+        let Ok(value) = value.trim().parse::<u16>() else {
+            bail!("Invalid stat bound '{bound}'; expected e.g. spd=100");
+        };
+        Ok(Self {
+            stat,
+            value: value.into(),
+        })
+    }
+}
+
+pub struct StatNames;
+impl Validate<Stat> for StatNames {
+    fn get_resource(&self) -> Result<Vec<String>> {
+        Ok(Stat::ALL.iter().map(|s| s.to_string()).collect())
+    }
+
+    fn label() -> &'static str {
+        "Stat"
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Type {
     pub name: String,
     pub offense_chart: OffenseTypeChart,
@@ -261,6 +727,31 @@ impl Type {
         chart
     }
 
+    // Each matchup set by the file replaces the database's, whichever type it is listed under
+    pub fn with_custom_chart(mut self, type_charts: &TypeChartCollection) -> Self {
+        let mut offense = self.offense_chart.get_chart().clone();
+        for (defender, multiplier) in offense.iter_mut() {
+            if let Some(custom) = type_charts.multiplier(&self.name, defender) {
+                *multiplier = custom;
+            }
+        }
+        let mut defense = self.defense_chart.get_chart().clone();
+        for (attacker, multiplier) in defense.iter_mut() {
+            if let Some(custom) = type_charts.multiplier(attacker, &self.name) {
+                *multiplier = custom;
+            }
+        }
+
+        let label = self.offense_chart.get_label();
+        self.offense_chart = OffenseTypeChart::new_struct(offense);
+        self.offense_chart.set_label(&label);
+        let label = self.defense_chart.get_label();
+        self.defense_chart = DefenseTypeChart::new_struct(defense);
+        self.defense_chart.set_label(&label);
+
+        self
+    }
+
     fn split_and_insert(chart: &mut HashMap<String, f32>, damage_relation: &str, value: f32) {
         damage_relation
             .split(',')
@@ -279,7 +770,17 @@ impl FromDb for Type {
         Type::from_row(type_row, generation, db)
     }
 }
-impl FromName<TypeRow> for Type {}
+impl FromName<TypeRow> for Type {
+    fn from_name(
+        name: &str,
+        generation: u8,
+        db: &Connection,
+        names: &impl ValidateGeneration<TypeRow>,
+    ) -> Result<Self> {
+        let name = names.validate_in_generation(&resolve_type_alias(name), generation)?;
+        Self::from_db(&name, generation, db)
+    }
+}
 impl FromRow<TypeRow> for Type {
     fn from_row(value: TypeRow, current_gen: u8, db: &Connection) -> Result<Self> {
         let TypeRow {
@@ -295,9 +796,12 @@ impl FromRow<TypeRow> for Type {
         } = value;
 
         if current_gen < generation {
-            bail!(format!(
-                "Type '{name}' is not present in generation {current_gen}"
-            ));
+            bail!(DunsparsError::NotInGeneration {
+                kind: TypeRow::label(),
+                name,
+                generation: current_gen,
+                matches: vec![],
+            });
         }
 
         let change_row = TypeChangeRow::select_by_fk(id, current_gen, db)?;
@@ -339,6 +843,179 @@ pub const TYPES: [&str; 19] = [
     "ground", "psychic", "rock", "ice", "bug", "dragon", "ghost", "dark", "steel", "fairy",
 ];
 
+// Defending typings, single and dual, that no attacking type hits super effectively,
+// paired with the best multiplier any of the attacking types gets against them
+pub fn uncovered_typings(
+    offense: &[&OffenseTypeChart],
+    defending_types: &[String],
+) -> Vec<(Vec<String>, f32)> {
+    // One row per defending type with each attacking type's multiplier against it
+    let multipliers = defending_types
+        .iter()
+        .map(|type_| {
+            offense
+                .iter()
+                .map(|chart| chart.get_multiplier(type_))
+                .collect::<Vec<f32>>()
+        })
+        .collect::<Vec<Vec<f32>>>();
+    let best = |rows: &[&Vec<f32>]| {
+        (0..offense.len())
+            .map(|attacker| rows.iter().map(|row| row[attacker]).product::<f32>())
+            .fold(0.0, f32::max)
+    };
+
+    let mut uncovered = vec![];
+    for (i, first) in multipliers.iter().enumerate() {
+        let single = best(&[first]);
+        if single <= 1.0 {
+            uncovered.push((vec![defending_types[i].clone()], single));
+        }
+
+        for (j, second) in multipliers.iter().enumerate().skip(i + 1) {
+            let dual = best(&[first, second]);
+            if dual <= 1.0 {
+                let typing = vec![defending_types[i].clone(), defending_types[j].clone()];
+                uncovered.push((typing, dual));
+            }
+        }
+    }
+
+    uncovered
+}
+
+// One-number summaries of a typing for team building.
+// defense: resistances minus weaknesses, where x4, x0.25, and immunities count double,
+//   i.e. x0 and x0.25 score +2, x0.5 +1, x2 -1, and x4 -2
+// offense: how many types at least one of the typing's STAB types hits super effectively
+#[derive(Debug, PartialEq)]
+pub struct TypeScore {
+    pub defense: i32,
+    pub offense: usize,
+}
+impl TypeScore {
+    pub fn new(defense: &DefenseTypeChart, offense: &[&OffenseTypeChart]) -> Self {
+        let defense = defense
+            .get_chart()
+            .values()
+            .map(|multiplier| match *multiplier {
+                m if m >= 4.0 => -2,
+                m if m >= 2.0 => -1,
+                m if m <= 0.25 => 2,
+                m if m <= 0.5 => 1,
+                _ => 0,
+            })
+            .sum();
+
+        let offense = offense
+            .iter()
+            .flat_map(|chart| chart.get_chart().iter())
+            .filter(|(_, multiplier)| **multiplier >= 2.0)
+            .map(|(type_, _)| type_)
+            .collect::<HashSet<&String>>()
+            .len();
+
+        Self { defense, offense }
+    }
+}
+
+// How well a Pokémon's partner covers it: each of its weaknesses paired with whether the
+// partner resists it, and each type that resists all of its STAB types paired with whether
+// the partner's STAB hits it super effectively
+#[derive(Debug, PartialEq)]
+pub struct Synergy {
+    pub weaknesses: Vec<(String, bool)>,
+    pub walls: Vec<(String, bool)>,
+}
+impl Synergy {
+    pub fn new(
+        defense: &DefenseTypeChart,
+        offense: &[&OffenseTypeChart],
+        partner_defense: &DefenseTypeChart,
+        partner_offense: &[&OffenseTypeChart],
+    ) -> Self {
+        let best = |charts: &[&OffenseTypeChart], type_: &str| {
+            charts
+                .iter()
+                .map(|chart| chart.get_multiplier(type_))
+                .fold(0.0, f32::max)
+        };
+
+        let types = defense
+            .get_sorted_chart()
+            .into_iter()
+            .map(|(type_, _)| type_)
+            .collect::<Vec<String>>();
+
+        let weaknesses = types
+            .iter()
+            .filter(|type_| defense.get_multiplier(type_) >= 2.0)
+            .map(|type_| {
+                let resisted = partner_defense.get_multiplier(type_) <= 0.5;
+                (type_.to_string(), resisted)
+            })
+            .collect();
+        let walls = types
+            .iter()
+            .filter(|type_| best(offense, type_) <= 0.5)
+            .map(|type_| {
+                let covered = best(partner_offense, type_) >= 2.0;
+                (type_.to_string(), covered)
+            })
+            .collect();
+
+        Self { weaknesses, walls }
+    }
+}
+
+// Names as the games print them, e.g. "Mr. Mime" or "Farfetch'd", turned into PokéAPI's
+// identifiers; anything left unmatched is still spellchecked by validation
+pub fn normalize_name(name: &str) -> String {
+    let mut identifier = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        match c {
+            ' ' | '_' | '-' => {
+                if !identifier.is_empty() && !identifier.ends_with('-') {
+                    identifier.push('-');
+                }
+            }
+            '\'' | '’' | '.' | ':' => {}
+            'é' => identifier.push('e'),
+            '♀' | '♂' => {
+                if !identifier.ends_with('-') {
+                    identifier.push('-');
+                }
+                identifier.push(if c == '♀' { 'f' } else { 'm' });
+            }
+            c => identifier.push(c),
+        }
+    }
+    identifier.trim_end_matches('-').to_string()
+}
+
+// Common type abbreviations; anything else is left for validation to spellcheck
+pub fn resolve_type_alias(name: &str) -> String {
+    let name = normalize_name(name);
+    let type_ = match name.as_str() {
+        "norm" => "normal",
+        "fight" | "fig" => "fighting",
+        "fly" => "flying",
+        "poi" | "pois" => "poison",
+        "grd" | "gro" => "ground",
+        "gho" => "ghost",
+        "stl" | "ste" => "steel",
+        "wat" => "water",
+        "gra" => "grass",
+        "elec" | "ele" => "electric",
+        "psy" => "psychic",
+        "drag" | "dra" => "dragon",
+        "drk" => "dark",
+        "fai" | "fae" => "fairy",
+        _ => return name,
+    };
+    String::from(type_)
+}
+
 fn default_chart() -> HashMap<String, f32> {
     let mut chart = HashMap::new();
 
@@ -375,6 +1052,17 @@ pub trait TypeChart {
         *self.get_chart().get(type_).unwrap()
     }
 
+    // The chart in alphabetical order of types, so output never depends on HashMap iteration order
+    fn get_sorted_chart(&self) -> Vec<(String, f32)> {
+        let mut chart = self
+            .get_chart()
+            .iter()
+            .map(|(type_, multiplier)| (type_.clone(), *multiplier))
+            .collect::<Vec<(String, f32)>>();
+        chart.sort_by(|a, b| a.0.cmp(&b.0));
+        chart
+    }
+
     fn get_chart(&self) -> &HashMap<String, f32>;
     fn get_type(&self) -> TypeCharts;
     fn get_label(&self) -> String;
@@ -396,7 +1084,7 @@ pub trait NewTypeChart: Sized {
     fn new_struct(chart: HashMap<String, f32>) -> Self;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OffenseTypeChart {
     chart: HashMap<String, f32>,
     label: String,
@@ -427,7 +1115,7 @@ impl TypeChart for OffenseTypeChart {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DefenseTypeChart {
     chart: HashMap<String, f32>,
     label: String,
@@ -457,6 +1145,13 @@ impl TypeChart for DefenseTypeChart {
         self.label = String::from(label);
     }
 }
+impl DefenseTypeChart {
+    pub fn fold(charts: impl IntoIterator<Item = DefenseTypeChart>) -> Option<DefenseTypeChart> {
+        charts
+            .into_iter()
+            .reduce(|combined, chart| combined + chart)
+    }
+}
 impl Add for DefenseTypeChart {
     type Output = DefenseTypeChart;
     fn add(self, rhs: Self) -> Self::Output {
@@ -466,7 +1161,7 @@ impl Add for DefenseTypeChart {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Move {
     pub name: String,
     pub accuracy: Option<i64>,
@@ -475,6 +1170,7 @@ pub struct Move {
     pub damage_class: String,
     pub type_: String,
     pub effect: String,
+    pub short_effect: String,
     pub effect_chance: Option<i64>,
     pub generation: u8,
 }
@@ -482,7 +1178,277 @@ impl Move {
     pub fn is_combat(&self) -> bool {
         self.damage_class != "status"
     }
+
+    // PokéAPI effect text uses $effect_chance as a placeholder for the chance percentage
+    pub fn resolved_effect(&self) -> String {
+        self.resolve_effect_chance(&self.effect)
+    }
+
+    pub fn resolved_short_effect(&self) -> String {
+        self.resolve_effect_chance(&self.short_effect)
+    }
+
+    fn resolve_effect_chance(&self, effect: &str) -> String {
+        if let Some(chance) = self.effect_chance {
+            effect.replace("$effect_chance", &chance.to_string())
+        } else {
+            effect.to_string()
+        }
+    }
+
+    // Each change row holds the values the move had up to and including its generation.
+    // With a range of generations, spans are clipped to it and those outside it are left out
+    pub fn get_history(
+        &self,
+        generations: Option<&RangeInclusive<u8>>,
+        db: &Connection,
+    ) -> Result<Vec<MoveHistory>> {
+        let move_row = MoveRow::select_by_name(&self.name, db)?;
+        let changes = MoveChangeRow::select_all_by_fk(move_row.id, db)?;
+
+        let mut history: Vec<MoveHistory> = vec![];
+        let mut push = |entry: MoveHistory| match history.last_mut() {
+            Some(last) if last.same_values(&entry) => last.to = entry.to,
+            _ => history.push(entry),
+        };
+
+        let mut from = move_row.generation;
+        for change in changes {
+            push(MoveHistory {
+                from,
+                to: Some(change.generation),
+                power: change.power.or(move_row.power),
+                accuracy: change.accuracy.or(move_row.accuracy),
+                pp: change.pp.or(move_row.pp),
+                type_: change.type_.unwrap_or(move_row.type_.clone()),
+            });
+            from = change.generation + 1;
+        }
+        push(MoveHistory {
+            from,
+            to: None,
+            power: move_row.power,
+            accuracy: move_row.accuracy,
+            pp: move_row.pp,
+            type_: move_row.type_,
+        });
+
+        if let Some(generations) = generations {
+            history.retain(|h| {
+                h.from <= *generations.end() && h.to.is_none_or(|to| to >= *generations.start())
+            });
+            for h in &mut history {
+                h.from = h.from.max(*generations.start());
+                h.to = Some(h.to.map_or(*generations.end(), |to| to.min(*generations.end())));
+            }
+        }
+
+        Ok(history)
+    }
+
+    // Meta data is only kept for the move's latest version, so it ignores the generation.
+    // None if PokéAPI has none for the move
+    pub fn get_meta(&self, db: &Connection) -> Result<Option<MoveMeta>> {
+        let move_row = MoveRow::select_by_name(&self.name, db)?;
+        let Some(meta_row) = MoveMetaRow::select_by_id(move_row.id, db).optional()? else {
+            return Ok(None);
+        };
+        let stat_changes = serde_json::from_str(&meta_row.stat_changes)?;
+
+        Ok(Some(MoveMeta {
+            drain: meta_row.drain,
+            healing: meta_row.healing,
+            crit_rate: meta_row.crit_rate,
+            flinch_chance: meta_row.flinch_chance,
+            stat_chance: meta_row.stat_chance,
+            hits: meta_row.min_hits.zip(meta_row.max_hits),
+            stat_changes,
+            stat_changes_user: meta_row.stat_changes_user,
+        }))
+    }
+
+    // Pokémon that learn this move in the given generation, optionally by a single learn method
+    pub fn get_learners(
+        &self,
+        generation: u8,
+        learn_method: Option<&str>,
+        db: &Connection,
+    ) -> Result<Vec<String>> {
+        let move_row = MoveRow::select_by_name(&self.name, db)?;
+        let pokemon = PokemonMoveRow::select_by_move(move_row.id, generation, learn_method, db)?;
+        Ok(pokemon)
+    }
+}
+// Battle-only and regional forms that PokéAPI keeps as separate Pokémon, e.g. charizard-mega-x
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormModifier {
+    // X or Y, for Pokémon with two mega forms
+    Mega(Option<String>),
+    Gmax,
+    Region(Region),
 }
+impl FormModifier {
+    // Picks the form out of the species' forms; the Pokémon's own forms win over those
+    // of its species' other forms, e.g. urshifu-rapid-strike-gmax for urshifu-rapid-strike
+    pub fn resolve(&self, base: &str, forms: &[String]) -> Result<String> {
+        let candidates = forms
+            .iter()
+            .filter(|form| match self {
+                FormModifier::Mega(None) => form.ends_with("-mega") || form.contains("-mega-"),
+                FormModifier::Mega(Some(variant)) => {
+                    form.ends_with(&format!("-mega-{}", variant.to_lowercase()))
+                }
+                FormModifier::Gmax => form.ends_with("-gmax"),
+                FormModifier::Region(region) => region.is_form(form),
+            })
+            .collect::<Vec<&String>>();
+        let own = candidates
+            .iter()
+            .filter(|form| form.starts_with(&format!("{base}-")))
+            .copied()
+            .collect::<Vec<&String>>();
+        let candidates = if own.is_empty() { candidates } else { own };
+
+        let label = match self {
+            FormModifier::Mega(_) => "mega",
+            FormModifier::Gmax => "gigantamax",
+            FormModifier::Region(region) => region.as_str(),
+        };
+        match candidates.as_slice() {
+            [form] => Ok(form.to_string()),
+            [] => match self {
+                FormModifier::Region(_) => {
+                    let regional = forms
+                        .iter()
+                        .filter(|form| Region::ALL.iter().any(|region| region.is_form(form)))
+                        .map(|form| form.as_str())
+                        .collect::<Vec<&str>>();
+                    if regional.is_empty() {
+                        bail!("{base} has no {label} form, nor any other regional form")
+                    }
+                    bail!(
+                        "{base} has no {label} form; its regional forms are: {}",
+                        regional.join(", ")
+                    )
+                }
+                _ => bail!("{base} has no {label} form"),
+            },
+            forms => {
+                let forms = forms
+                    .iter()
+                    .map(|form| form.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ");
+                match self {
+                    FormModifier::Mega(_) => bail!(
+                        "{base} has more than one {label} form: {forms}; pick one with --mega x or --mega y"
+                    ),
+                    FormModifier::Gmax => bail!(
+                        "{base} has more than one {label} form: {forms}; name the base form instead"
+                    ),
+                    FormModifier::Region(_) => bail!(
+                        "{base} has more than one {label} form: {forms}; name the form instead"
+                    ),
+                }
+            }
+        }
+    }
+}
+
+// A span of generations such as "3-6"; either end may be left open, e.g. "3-" or "-6",
+// and a single generation such as "4" covers just that one
+#[derive(Debug, PartialEq)]
+pub struct GenerationRange {
+    from: Option<u8>,
+    to: Option<u8>,
+}
+impl GenerationRange {
+    // Open ends are filled with the first and last generations in the database
+    pub fn resolve(&self, first: u8, last: u8) -> Result<RangeInclusive<u8>> {
+        let from = self.from.unwrap_or(first);
+        let to = self.to.unwrap_or(last);
+        if from > to {
+            bail!("Generation range {from}-{to} starts after it ends");
+        }
+        if from < first || to > last {
+            bail!("Generation range {from}-{to} is outside of the available generations {first}-{last}");
+        }
+        Ok(from..=to)
+    }
+}
+impl FromStr for GenerationRange {
+    type Err = anyhow::Error;
+
+    fn from_str(range: &str) -> Result<Self> {
+        let parse = |generation: &str| -> Result<Option<u8>> {
+            match generation.trim() {
+                "" => Ok(None),
+                generation => match generation.parse::<u8>() {
+                    Ok(generation) => Ok(Some(generation)),
+                    Err(_) => bail!("Invalid generation range '{range}'; expected e.g. 3-6"),
+                },
+            }
+        };
+
+        let (from, to) = match range.split_once('-') {
+            Some((from, to)) => (parse(from)?, parse(to)?),
+            None => {
+                let generation = parse(range)?;
+                (generation, generation)
+            }
+        };
+        if from.is_none() && to.is_none() {
+            bail!("Invalid generation range '{range}'; expected e.g. 3-6");
+        }
+        Ok(Self { from, to })
+    }
+}
+
+// A move's combat values over a span of generations; `to` is None for the current values
+#[derive(Debug, PartialEq)]
+pub struct MoveHistory {
+    pub from: u8,
+    pub to: Option<u8>,
+    pub power: Option<i64>,
+    pub accuracy: Option<i64>,
+    pub pp: Option<i64>,
+    pub type_: String,
+}
+impl MoveHistory {
+    fn same_values(&self, other: &MoveHistory) -> bool {
+        self.power == other.power
+            && self.accuracy == other.accuracy
+            && self.pp == other.pp
+            && self.type_ == other.type_
+    }
+}
+
+// Battle details beyond a move's stats; zero values mean the move has no such effect
+#[derive(Debug, Default, PartialEq)]
+pub struct MoveMeta {
+    // Percent of the damage dealt; negative for recoil
+    pub drain: i64,
+    // Percent of the user's max HP
+    pub healing: i64,
+    pub crit_rate: i64,
+    pub flinch_chance: i64,
+    // Zero when the stat changes always happen
+    pub stat_chance: i64,
+    pub hits: Option<(i64, i64)>,
+    pub stat_changes: Vec<(String, i64)>,
+    pub stat_changes_user: bool,
+}
+impl MoveMeta {
+    pub fn is_empty(&self) -> bool {
+        self.drain == 0
+            && self.healing == 0
+            && self.crit_rate == 0
+            && self.flinch_chance == 0
+            && self.hits.is_none()
+            && self.stat_changes.is_empty()
+    }
+}
+
 impl FromDb for Move {
     fn from_db(move_name: &str, generation: u8, db: &Connection) -> Result<Self> {
         let move_row = MoveRow::select_by_name(move_name, db)?;
@@ -492,26 +1458,62 @@ impl FromDb for Move {
 impl FromName<MoveRow> for Move {}
 impl FromRow<MoveRow> for Move {
     fn from_row(value: MoveRow, current_gen: u8, db: &Connection) -> Result<Self> {
+        let change_row = MoveChangeRow::select_by_fk(value.id, current_gen, db)?;
+        Move::from_rows(value, change_row, current_gen)
+    }
+}
+impl Move {
+    // Loads every move with one query for the moves and one for their changes,
+    // e.g. for a learnset of over a hundred moves
+    pub fn from_db_batch(names: &[String], generation: u8, db: &Connection) -> Result<Vec<Self>> {
+        let move_rows = MoveRow::select_by_names(names, db)?;
+        if let Some(missing) = names
+            .iter()
+            .find(|name| !move_rows.iter().any(|row| &row.name == *name))
+        {
+            // Reports a missing move the same way a single lookup would
+            MoveRow::select_by_name(missing, db)?;
+        }
+
+        let ids = move_rows.iter().map(|row| row.id).collect::<Vec<i64>>();
+        let mut changes = MoveChangeRow::select_by_fks(&ids, generation, db)?;
+        move_rows
+            .into_iter()
+            .map(|row| {
+                let change_row = changes.remove(&row.id);
+                Move::from_rows(row, change_row, generation)
+            })
+            .collect()
+    }
+
+    fn from_rows(
+        value: MoveRow,
+        change_row: Option<MoveChangeRow>,
+        current_gen: u8,
+    ) -> Result<Self> {
         let MoveRow {
-            id,
+            id: _,
             name,
             mut power,
             mut accuracy,
             mut pp,
             mut effect_chance,
             effect,
+            short_effect,
             mut type_,
             damage_class,
             generation,
         } = value;
 
         if current_gen < generation {
-            bail!(format!(
-                "Move '{name}' is not present in generation {current_gen}"
-            ));
+            bail!(DunsparsError::NotInGeneration {
+                kind: MoveRow::label(),
+                name,
+                generation: current_gen,
+                matches: vec![],
+            });
         }
 
-        let change_row = MoveChangeRow::select_by_fk(id, current_gen, db)?;
         if let Some(change) = change_row {
             power = change.power.or(power);
             accuracy = change.accuracy.or(accuracy);
@@ -531,21 +1533,109 @@ impl FromRow<MoveRow> for Move {
             damage_class,
             type_,
             effect,
+            short_effect,
             effect_chance,
             generation,
         })
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Region {
+    Alola,
+    Galar,
+    Hisui,
+    Paldea,
+}
+impl Region {
+    pub const ALL: [Region; 4] = [Region::Alola, Region::Galar, Region::Hisui, Region::Paldea];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Region::Alola => "alola",
+            Region::Galar => "galar",
+            Region::Hisui => "hisui",
+            Region::Paldea => "paldea",
+        }
+    }
+
+    // Regional forms may carry a further form name, e.g. darmanitan-galar-zen
+    pub fn is_form(&self, form: &str) -> bool {
+        let region = self.as_str();
+        form.ends_with(&format!("-{region}")) || form.contains(&format!("-{region}-"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Weather {
+    Rain,
+    Sun,
+    Sand,
+    Snow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Terrain {
+    Electric,
+    Grassy,
+    Psychic,
+    Misty,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BattleConditions {
+    pub weather: Option<Weather>,
+    pub terrain: Option<Terrain>,
+}
+impl BattleConditions {
+    // Multipliers applied on top of type effectiveness; the attacker and target are assumed grounded.
+    // rain: water 1.5, fire 0.5 (gen 2+)           sun: fire 1.5, water 0.5 (gen 2+)
+    // sand: special vs rock 2/3, 1.5 sdef (gen 4+)  snow: physical vs ice 2/3, 1.5 def (gen 9+)
+    // electric/grassy terrain (gen 6+), psychic terrain (gen 7+): matching move type 1.5, 1.3 from gen 8
+    // misty terrain: dragon 0.5 (gen 6+)
+    // The generation is the game's; a move's own generation is the one it was introduced in
+    pub fn modifier(&self, move_: &Move, defender_types: &[&String], generation: u8) -> f32 {
+        let type_ = move_.type_.as_str();
+        let defender_is = |t: &str| defender_types.iter().any(|d| *d == t);
+
+        let weather = match (self.weather, type_) {
+            _ if generation < 2 => 1.0,
+            (Some(Weather::Rain), "water") | (Some(Weather::Sun), "fire") => 1.5,
+            (Some(Weather::Rain), "fire") | (Some(Weather::Sun), "water") => 0.5,
+            (Some(Weather::Sand), _)
+                if generation >= 4 && move_.damage_class == "special" && defender_is("rock") =>
+            {
+                2.0 / 3.0
+            }
+            (Some(Weather::Snow), _)
+                if generation >= 9 && move_.damage_class == "physical" && defender_is("ice") =>
+            {
+                2.0 / 3.0
+            }
+            _ => 1.0,
+        };
+
+        let terrain_boost = if generation >= 8 { 1.3 } else { 1.5 };
+        let terrain = match (self.terrain, type_) {
+            _ if generation < 6 => 1.0,
+            (Some(Terrain::Electric), "electric") | (Some(Terrain::Grassy), "grass") => {
+                terrain_boost
+            }
+            (Some(Terrain::Psychic), "psychic") if generation >= 7 => terrain_boost,
+            (Some(Terrain::Misty), "dragon") => 0.5,
+            _ => 1.0,
+        };
+
+        weather * terrain
+    }
+}
+
 pub struct MoveList(HashMap<String, Move>);
 impl MoveList {
     pub fn try_new(move_list: &[String], generation: u8, db: &Connection) -> Result<Self> {
-        let mut move_data = HashMap::new();
-        for move_ in move_list {
-            let move_ = Move::from_db(move_, generation, db)?;
-            move_data.insert(move_.name.clone(), move_);
-        }
-        Ok(Self(move_data))
+        Ok(Move::from_db_batch(move_list, generation, db)?
+            .into_iter()
+            .collect())
     }
 
     pub fn get_move(&self, move_name: &str) -> Option<&Move> {
@@ -559,12 +1649,93 @@ impl MoveList {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    // Approximates a real moveset: the most powerful damaging move of each type, strongest first.
+    // Moves without a fixed power, e.g. OHKO moves, are left out
+    pub fn strongest_by_type(&self, count: usize, exclude: &HashSet<String>) -> Vec<&Move> {
+        let mut moves = self
+            .0
+            .values()
+            .filter(|move_| move_.is_combat() && move_.power.is_some())
+            .filter(|move_| !exclude.contains(&move_.name))
+            .collect::<Vec<&Move>>();
+        moves.sort_by(|a, b| b.power.cmp(&a.power).then_with(|| a.name.cmp(&b.name)));
+
+        let mut types = HashSet::new();
+        moves
+            .into_iter()
+            .filter(|move_| types.insert(move_.type_.clone()))
+            .take(count)
+            .collect()
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    fn from_iter<I: IntoIterator<Item = Move>>(moves: I) -> Self {
+        Self(
+            moves
+                .into_iter()
+                .map(|move_| (move_.name.clone(), move_))
+                .collect(),
+        )
+    }
+}
+
+// Base stat totals of every Pokémon in a generation, highest first
+#[derive(Debug, Clone)]
+pub struct StatTotals(Vec<i64>);
+impl StatTotals {
+    pub fn try_new(generation: u8, db: &Connection) -> Result<Self> {
+        Ok(Self(PokemonRow::select_totals_by_generation(
+            generation, db,
+        )?))
+    }
+
+    // Share of Pokémon with a total at least this high, rounded up to a whole percent
+    pub fn top_percent(&self, total: i64) -> u8 {
+        if self.0.is_empty() {
+            return 100;
+        }
+
+        let rank = self.0.iter().filter(|t| **t >= total).count().max(1);
+        (rank * 100).div_ceil(self.0.len()) as u8
+    }
+}
+
+// Machine names such as TM35, keyed by the move they teach in a game
+pub struct Machines(HashMap<String, String>);
+impl Machines {
+    pub fn try_new(game: &str, db: &Connection) -> Result<Self> {
+        let machines_meta = MetaRow::select_by_name("machines", db);
+        if !machines_meta.is_ok_and(|meta| meta.value == "true") {
+            bail!(DunsparsError::SetupDataMissing {
+                data: "Machine",
+                flag: "--with-machines",
+            });
+        }
+
+        Ok(MachineRow::select_by_game(game, db)?.into_iter().collect())
+    }
+
+    pub fn get_machine(&self, move_name: &str) -> Option<&String> {
+        self.0.get(move_name)
+    }
+}
+impl FromIterator<(String, String)> for Machines {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(machines: I) -> Self {
+        let machines = machines
+            .into_iter()
+            .map(|(move_, machine)| (move_, machine.to_uppercase()))
+            .collect();
+        Self(machines)
+    }
 }
 
 #[derive(Debug)]
 pub struct Ability {
     pub name: String,
     pub effect: String,
+    pub short_effect: String,
     pub generation: u8,
 }
 impl FromDb for Ability {
@@ -574,29 +1745,71 @@ impl FromDb for Ability {
     }
 }
 impl FromName<AbilityRow> for Ability {}
+impl Ability {
+    pub fn get_pokemon(&self, hidden_only: bool, db: &Connection) -> Result<Vec<(String, bool)>> {
+        let ability_row = AbilityRow::select_by_name(&self.name, db)?;
+        let pokemon = PokemonAbilityRow::select_by_ability(ability_row.id, hidden_only, db)?;
+        Ok(pokemon)
+    }
+}
 impl FromRow<AbilityRow> for Ability {
     fn from_row(value: AbilityRow, current_gen: u8, _db: &Connection) -> Result<Self> {
         let AbilityRow {
             name,
             effect,
+            short_effect,
             generation,
             ..
         } = value;
 
         if current_gen < generation {
-            bail!(format!(
-                "Ability '{name}' is not present in generation {current_gen}"
-            ));
+            bail!(DunsparsError::NotInGeneration {
+                kind: AbilityRow::label(),
+                name,
+                generation: current_gen,
+                matches: vec![],
+            });
         }
 
         Ok(Self {
             name,
             effect,
+            short_effect,
             generation,
         })
     }
 }
 
+#[derive(Debug)]
+pub struct Encounter {
+    pub location: String,
+    pub method: String,
+    pub version: String,
+    pub min_level: i64,
+    pub max_level: i64,
+}
+
+impl From<EncounterRow> for Encounter {
+    fn from(row: EncounterRow) -> Self {
+        let EncounterRow {
+            location,
+            method,
+            version,
+            min_level,
+            max_level,
+            ..
+        } = row;
+
+        Self {
+            location,
+            method,
+            version,
+            min_level,
+            max_level,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EvolutionStep {
     pub name: String,
@@ -616,6 +1829,16 @@ impl EvolutionStep {
             evolves_to,
         }
     }
+
+    // The species at the root of each evolution chain, in chain order
+    pub fn select_base_names(db: &Connection) -> Result<Vec<String>> {
+        let mut names = vec![];
+        for row in EvolutionRow::select_all(db)? {
+            let step: EvolutionStep = serde_json::from_str(&row.evolution)?;
+            names.push(step.name);
+        }
+        Ok(names)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -776,6 +1999,7 @@ impl From<GameRow> for Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resource::custom::{CustomStats, CustomTypes};
     use crate::resource::database::DatabaseFile;
 
     fn db() -> Connection {
@@ -783,6 +2007,193 @@ mod tests {
         db_file.connect().unwrap()
     }
 
+    fn stats(base: [i64; 6]) -> Stats {
+        let [hp, attack, defense, special_attack, special_defense, speed] = base;
+        Stats {
+            hp,
+            attack,
+            defense,
+            special_attack,
+            special_defense,
+            speed,
+            generation: 9,
+        }
+    }
+
+    #[test]
+    fn suggest_spread() {
+        // garchomp
+        let garchomp = stats([108, 130, 95, 80, 85, 102]);
+        assert_eq!(
+            EvSpread {
+                evs: vec![(Stat::Hp, 4), (Stat::Attack, 252), (Stat::Speed, 252)],
+                nature: "Jolly"
+            },
+            garchomp.suggest_spread(Role::Physical)
+        );
+
+        // snorlax
+        let snorlax = stats([160, 110, 65, 65, 110, 30]);
+        assert_eq!(
+            EvSpread {
+                evs: vec![
+                    (Stat::Hp, 252),
+                    (Stat::Attack, 252),
+                    (Stat::SpecialDefense, 4)
+                ],
+                nature: "Adamant"
+            },
+            snorlax.suggest_spread(Role::Physical)
+        );
+
+        // blissey
+        let blissey = stats([255, 10, 10, 75, 135, 55]);
+        assert_eq!(
+            EvSpread {
+                evs: vec![
+                    (Stat::Hp, 252),
+                    (Stat::Defense, 252),
+                    (Stat::SpecialDefense, 4)
+                ],
+                nature: "Bold"
+            },
+            blissey.suggest_spread(Role::Bulky)
+        );
+
+        // skarmory
+        let skarmory = stats([65, 80, 140, 40, 70, 70]);
+        assert_eq!(
+            EvSpread {
+                evs: vec![
+                    (Stat::Hp, 252),
+                    (Stat::Defense, 4),
+                    (Stat::SpecialDefense, 252)
+                ],
+                nature: "Careful"
+            },
+            skarmory.suggest_spread(Role::Bulky)
+        );
+    }
+
+    #[test]
+    fn normalize_names() {
+        assert_eq!("farfetchd", normalize_name("Farfetch'd"));
+        assert_eq!("sirfetchd", normalize_name("Sirfetch’d"));
+        assert_eq!("mr-mime", normalize_name("Mr. Mime"));
+        assert_eq!("mr-mime", normalize_name("MR_MIME"));
+        assert_eq!("kings-shield", normalize_name("King's  Shield"));
+        assert_eq!("type-null", normalize_name("Type: Null"));
+        assert_eq!("nidoran-f", normalize_name("Nidoran ♀"));
+        assert_eq!("flabebe", normalize_name("Flabébé"));
+
+        // PokéAPI identifiers pass through untouched
+        assert_eq!("porygon-z", normalize_name("porygon-z"));
+    }
+
+    #[test]
+    fn convert_height_weight() {
+        let details = |height, weight| PokemonDetails {
+            capture_rate: 0,
+            base_experience: None,
+            growth_rate: String::new(),
+            height,
+            weight,
+            sprite: None,
+        };
+
+        let charizard = details(17, 905);
+        assert_eq!("1.7 m", charizard.format_height(Units::Metric));
+        assert_eq!("5'07\"", charizard.format_height(Units::Imperial));
+        assert_eq!("90.5 kg", charizard.format_weight(Units::Metric));
+        assert_eq!("199.5 lbs", charizard.format_weight(Units::Imperial));
+
+        let onix = details(88, 2100);
+        assert_eq!("28'10\"", onix.format_height(Units::Imperial));
+        assert_eq!("463.0 lbs", onix.format_weight(Units::Imperial));
+
+        assert_eq!(Units::Imperial, Units::from_str("imperial").unwrap());
+        assert!(Units::from_str("kg").is_err());
+    }
+
+    #[test]
+    fn type_aliases() {
+        assert_eq!("fighting", resolve_type_alias("fight"));
+        assert_eq!("psychic", resolve_type_alias("PSY"));
+        assert_eq!("electric", resolve_type_alias("elec"));
+
+        // Full names and unknown values pass through for validation
+        assert_eq!("psychic", resolve_type_alias("psychic"));
+        assert_eq!("fyre", resolve_type_alias("Fyre"));
+    }
+
+    #[test]
+    fn stat_totals_top_percent() {
+        let totals = StatTotals(vec![720, 600, 600, 500, 480, 405, 300, 250, 200, 180]);
+        assert_eq!(10, totals.top_percent(720));
+        assert_eq!(30, totals.top_percent(600));
+        assert_eq!(50, totals.top_percent(480));
+        assert_eq!(100, totals.top_percent(180));
+
+        // Totals above every other Pokémon, e.g. custom stats, still rank first
+        assert_eq!(10, totals.top_percent(800));
+        assert_eq!(100, StatTotals(vec![]).top_percent(600));
+
+        let db = db();
+        let totals = StatTotals::try_new(9, &db).unwrap();
+        let koraidon = Pokemon::from_db("koraidon", 9, &db).unwrap();
+        assert!(totals.top_percent(koraidon.stats.get(Stat::Total)) <= 3);
+
+        // Generation 1 totals leave out special defense on both sides
+        let totals = StatTotals::try_new(1, &db).unwrap();
+        let mewtwo = Pokemon::from_db("mewtwo", 1, &db).unwrap();
+        assert_eq!(totals.0[0], mewtwo.stats.get(Stat::Total));
+    }
+
+    #[test]
+    fn move_resolved_effect() {
+        let mut flamethrower = Move {
+            name: String::from("flamethrower"),
+            accuracy: Some(100),
+            power: Some(90),
+            pp: Some(15),
+            damage_class: String::from("special"),
+            type_: String::from("fire"),
+            effect: String::from(
+                "Inflicts regular damage.  Has a $effect_chance% chance to burn the target.",
+            ),
+            short_effect: String::from("Has a $effect_chance% chance to burn the target."),
+            effect_chance: Some(30),
+            generation: 9,
+        };
+        assert_eq!(
+            "Inflicts regular damage.  Has a 30% chance to burn the target.",
+            flamethrower.resolved_effect()
+        );
+        assert_eq!(
+            "Has a 30% chance to burn the target.",
+            flamethrower.resolved_short_effect()
+        );
+
+        // Without a chance, the text is left as is
+        flamethrower.effect_chance = None;
+        assert_eq!(
+            "Has a $effect_chance% chance to burn the target.",
+            flamethrower.resolved_short_effect()
+        );
+    }
+
+    #[test]
+    fn stat_from_str() {
+        assert_eq!(Stat::SpecialDefense, Stat::from_str("sdef").unwrap());
+        assert_eq!(Stat::Total, Stat::from_str("TOTAL").unwrap());
+
+        let err = Stat::from_str("sped").unwrap_err();
+        assert_eq!(
+            "Stat 'sped' not found. Potential matches: satk sdef spd.",
+            err.to_string()
+        );
+    }
+
     #[test]
     fn get_pokemon_by_name() {
         let db = db();
@@ -805,6 +2216,11 @@ mod tests {
         assert_eq!(0.25, golem_defense.get_multiplier("poison"));
         assert_eq!(0.0, golem_defense.get_multiplier("electric"));
 
+        // The combined chart is reused on later calls
+        assert!(golem.defense_chart.get().is_some());
+        let golem_cached = golem.get_defense_chart(&db).unwrap();
+        assert_eq!(golem_defense.get_chart(), golem_cached.get_chart());
+
         // Clefairy was Normal type until gen 6
         let clefairy_gen_5 = Pokemon::from_db("clefairy", 5, &db).unwrap();
         assert_eq!("normal", clefairy_gen_5.primary_type);
@@ -812,6 +2228,33 @@ mod tests {
         assert_eq!("fairy", clefairy_gen_6.primary_type);
     }
 
+    #[test]
+    fn validate_custom_pokemon() {
+        let db = db();
+        let custom = CustomPokemon {
+            nickname: String::from("typo"),
+            base: String::from("charmander"),
+            generation: 3,
+            moves: vec![
+                String::from("ember"),
+                String::from("flamethrowr"),
+                String::from("fire-fang"),
+            ],
+            types: Some(CustomTypes::Dual(
+                String::from("fire"),
+                Some(String::from("dragn")),
+            )),
+            stats: CustomStats::default(),
+        };
+
+        let err = validate_custom(&custom, &db).unwrap_err().to_string();
+        assert!(err.starts_with("Custom Pokémon 'typo' is invalid:\n"));
+        assert!(err.contains("Move 'flamethrowr' not found."));
+        assert!(err.contains("Move 'fire-fang' is not present in generation 3"));
+        assert!(err.contains("Type 'dragn' not found."));
+        assert!(!err.contains("'ember'"));
+    }
+
     #[test]
     fn get_pokemon_evolution() {
         let db = db();
@@ -835,6 +2278,16 @@ mod tests {
         insta::assert_yaml_snapshot!(politoed);
     }
 
+    #[test]
+    fn select_evolution_base_names() {
+        let db = db();
+
+        let names = EvolutionStep::select_base_names(&db).unwrap();
+        assert_eq!("bulbasaur", names[0]);
+        assert!(names.contains(&String::from("applin")));
+        assert!(!names.contains(&String::from("ivysaur")));
+    }
+
     #[test]
     fn get_type_by_name() {
         let db = db();
@@ -876,6 +2329,194 @@ mod tests {
         let tackle_gen_7 = Move::from_db("tackle", 7, &db).unwrap();
         assert_eq!(40, tackle_gen_7.power.unwrap());
         assert_eq!(100, tackle_gen_7.accuracy.unwrap());
+
+        let history = tackle_gen_4.get_history(None, &db).unwrap();
+        let spans = history
+            .iter()
+            .map(|h| (h.from, h.to, h.power, h.accuracy))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (1, Some(4), Some(35), Some(95)),
+                (5, Some(6), Some(50), Some(100)),
+                (7, None, Some(40), Some(100)),
+            ],
+            spans
+        );
+
+        let history = tackle_gen_4.get_history(Some(&(3..=6)), &db).unwrap();
+        let spans = history
+            .iter()
+            .map(|h| (h.from, h.to, h.power))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(3, Some(4), Some(35)), (5, Some(6), Some(50))], spans);
+    }
+
+    #[test]
+    fn resolve_forms() {
+        let forms = |forms: &[&str]| forms.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        let charizard = forms(&[
+            "charizard",
+            "charizard-mega-x",
+            "charizard-mega-y",
+            "charizard-gmax",
+        ]);
+        let mega = |variant: Option<&str>| FormModifier::Mega(variant.map(String::from));
+
+        assert_eq!(
+            "charizard-mega-x",
+            mega(Some("X")).resolve("charizard", &charizard).unwrap()
+        );
+        assert_eq!(
+            "charizard-gmax",
+            FormModifier::Gmax.resolve("charizard", &charizard).unwrap()
+        );
+        let error = mega(None).resolve("charizard", &charizard).unwrap_err();
+        assert!(error.to_string().contains("--mega x or --mega y"));
+
+        let venusaur = forms(&["venusaur", "venusaur-mega", "venusaur-gmax"]);
+        assert_eq!(
+            "venusaur-mega",
+            mega(None).resolve("venusaur", &venusaur).unwrap()
+        );
+        mega(Some("x")).resolve("venusaur", &venusaur).unwrap_err();
+
+        let pikachu = forms(&["pikachu", "pikachu-gmax"]);
+        let error = mega(None).resolve("pikachu", &pikachu).unwrap_err();
+        assert_eq!("pikachu has no mega form", error.to_string());
+
+        let urshifu = forms(&[
+            "urshifu-single-strike",
+            "urshifu-rapid-strike",
+            "urshifu-single-strike-gmax",
+            "urshifu-rapid-strike-gmax",
+        ]);
+        assert_eq!(
+            "urshifu-rapid-strike-gmax",
+            FormModifier::Gmax
+                .resolve("urshifu-rapid-strike", &urshifu)
+                .unwrap()
+        );
+
+        let meowth = forms(&["meowth", "meowth-alola", "meowth-galar", "meowth-gmax"]);
+        assert_eq!(
+            "meowth-galar",
+            FormModifier::Region(Region::Galar)
+                .resolve("meowth", &meowth)
+                .unwrap()
+        );
+        let error = FormModifier::Region(Region::Hisui)
+            .resolve("meowth", &meowth)
+            .unwrap_err();
+        assert_eq!(
+            "meowth has no hisui form; its regional forms are: meowth-alola, meowth-galar",
+            error.to_string()
+        );
+
+        let tauros = forms(&[
+            "tauros",
+            "tauros-paldea-combat-breed",
+            "tauros-paldea-blaze-breed",
+        ]);
+        let error = FormModifier::Region(Region::Paldea)
+            .resolve("tauros", &tauros)
+            .unwrap_err();
+        assert!(error.to_string().contains("tauros-paldea-blaze-breed"));
+    }
+
+    #[test]
+    fn strongest_moves_by_type() {
+        let move_ = |name: &str, type_: &str, power: Option<i64>, damage_class: &str| Move {
+            name: String::from(name),
+            accuracy: Some(100),
+            power,
+            pp: Some(10),
+            damage_class: String::from(damage_class),
+            type_: String::from(type_),
+            effect: String::new(),
+            short_effect: String::new(),
+            effect_chance: None,
+            generation: 9,
+        };
+        let move_list = MoveList(
+            [
+                move_("flamethrower", "fire", Some(90), "special"),
+                move_("fire-blast", "fire", Some(110), "special"),
+                move_("air-slash", "flying", Some(75), "special"),
+                move_("dragon-pulse", "dragon", Some(85), "special"),
+                move_("solar-beam", "grass", Some(120), "special"),
+                move_("fissure", "ground", None, "physical"),
+                move_("dragon-dance", "dragon", None, "status"),
+            ]
+            .into_iter()
+            .map(|move_| (move_.name.clone(), move_))
+            .collect(),
+        );
+        let names = |moves: Vec<&Move>| moves.iter().map(|m| m.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec!["solar-beam", "fire-blast", "dragon-pulse"],
+            names(move_list.strongest_by_type(3, &HashSet::new()))
+        );
+
+        let exclude = HashSet::from([String::from("solar-beam")]);
+        assert_eq!(
+            vec!["fire-blast", "dragon-pulse", "air-slash"],
+            names(move_list.strongest_by_type(4, &exclude))
+        );
+    }
+
+    #[test]
+    fn generation_ranges() {
+        let range = |range: &str| GenerationRange::from_str(range).unwrap();
+        assert_eq!(3..=6, range("3-6").resolve(1, 9).unwrap());
+        assert_eq!(4..=4, range("4").resolve(1, 9).unwrap());
+        assert_eq!(3..=9, range("3-").resolve(1, 9).unwrap());
+        assert_eq!(1..=6, range("-6").resolve(1, 9).unwrap());
+
+        range("6-3").resolve(1, 9).unwrap_err();
+        range("3-10").resolve(1, 9).unwrap_err();
+        GenerationRange::from_str("-").unwrap_err();
+        GenerationRange::from_str("three").unwrap_err();
+    }
+
+    #[test]
+    fn stat_bound_ranges() {
+        let bound = |bound: &str| StatBound::from_str(bound).unwrap();
+        assert_eq!(
+            StatBound {
+                stat: Stat::Speed,
+                value: 130
+            },
+            bound("spd=130")
+        );
+        StatBound::from_str("spd").unwrap_err();
+        StatBound::from_str("spd=-5").unwrap_err();
+        StatBound::from_str("speed=100").unwrap_err();
+
+        let ranges = StatBound::ranges(
+            &[bound("spd=100"), bound("spd=110"), bound("total=500")],
+            &[bound("hp=50")],
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                (Stat::Hp, 0..=50),
+                (Stat::Speed, 110..=i64::MAX),
+                (Stat::Total, 500..=i64::MAX)
+            ],
+            ranges
+        );
+
+        let err = StatBound::ranges(&[bound("hp=100")], &[bound("hp=50")]).unwrap_err();
+        assert_eq!(
+            "The minimum hp of 100 is above its maximum of 50",
+            err.to_string()
+        );
+
+        // Generation 1 totals have no special defense, as in Stats::get
+        assert!(!Stat::Total.column(1).contains("special_defense"));
+        assert!(Stat::Total.column(2).contains("special_defense"));
     }
 
     #[test]
@@ -887,6 +2528,240 @@ mod tests {
         Ability::from_db("beads-of-ruin", 9, &db).unwrap();
     }
 
+    #[test]
+    fn sorted_charts_are_identical() {
+        let relations = [
+            ("water", 2.0),
+            ("grass", 4.0),
+            ("electric", 0.0),
+            ("fire", 0.5),
+        ];
+        // Each HashMap is seeded differently, so their iteration orders usually differ
+        let chart = |relations: Vec<(&str, f32)>| {
+            DefenseTypeChart::new(
+                relations
+                    .into_iter()
+                    .map(|(t, m)| (String::from(t), m))
+                    .collect(),
+            )
+        };
+        let forward = chart(relations.to_vec());
+        let backward = chart(relations.into_iter().rev().collect());
+
+        let serialize = |chart: &DefenseTypeChart| format!("{:?}", chart.get_sorted_chart());
+        assert_eq!(serialize(&forward), serialize(&backward));
+
+        let sorted = forward.get_sorted_chart();
+        assert!(sorted.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn type_scores() {
+        let chart = |relations: &[(&str, f32)]| -> HashMap<String, f32> {
+            relations
+                .iter()
+                .map(|(t, m)| (String::from(*t), *m))
+                .collect()
+        };
+
+        let steel_fairy = TypeScore::new(
+            &DefenseTypeChart::new(chart(&[
+                ("poison", 0.0),
+                ("dragon", 0.0),
+                ("bug", 0.25),
+                ("normal", 0.5),
+                ("grass", 0.5),
+                ("ice", 0.5),
+                ("flying", 0.5),
+                ("psychic", 0.5),
+                ("rock", 0.5),
+                ("dark", 0.5),
+                ("fairy", 0.5),
+                ("fire", 2.0),
+                ("ground", 2.0),
+            ])),
+            &[
+                &OffenseTypeChart::new(chart(&[
+                    ("ice", 2.0),
+                    ("rock", 2.0),
+                    ("fairy", 2.0),
+                    ("steel", 0.5),
+                ])),
+                &OffenseTypeChart::new(chart(&[
+                    ("fighting", 2.0),
+                    ("dragon", 2.0),
+                    ("dark", 2.0),
+                    ("steel", 0.5),
+                ])),
+            ],
+        );
+        assert_eq!(
+            TypeScore {
+                defense: 12,
+                offense: 6
+            },
+            steel_fairy
+        );
+
+        let grass_ice = TypeScore::new(
+            &DefenseTypeChart::new(chart(&[
+                ("fire", 4.0),
+                ("steel", 4.0),
+                ("fighting", 2.0),
+                ("poison", 2.0),
+                ("flying", 2.0),
+                ("bug", 2.0),
+                ("rock", 2.0),
+                ("water", 0.5),
+                ("electric", 0.5),
+                ("grass", 0.5),
+                ("ground", 0.5),
+            ])),
+            &[
+                &OffenseTypeChart::new(chart(&[("water", 2.0), ("ground", 2.0), ("rock", 2.0)])),
+                // Types both STABs hit super effectively only count once
+                &OffenseTypeChart::new(chart(&[
+                    ("grass", 2.0),
+                    ("ground", 2.0),
+                    ("flying", 2.0),
+                    ("dragon", 2.0),
+                ])),
+            ],
+        );
+        assert_eq!(
+            TypeScore {
+                defense: -5,
+                offense: 6
+            },
+            grass_ice
+        );
+        assert!(steel_fairy.defense > grass_ice.defense);
+    }
+
+    #[test]
+    fn fire_water_synergy() {
+        let chart = |relations: &[(&str, f32)]| -> HashMap<String, f32> {
+            relations
+                .iter()
+                .map(|(t, m)| (String::from(*t), *m))
+                .collect()
+        };
+        let fire_defense = DefenseTypeChart::new(chart(&[
+            ("water", 2.0),
+            ("ground", 2.0),
+            ("rock", 2.0),
+            ("fire", 0.5),
+            ("grass", 0.5),
+        ]));
+        let fire_offense = OffenseTypeChart::new(chart(&[
+            ("grass", 2.0),
+            ("fire", 0.5),
+            ("water", 0.5),
+            ("rock", 0.5),
+        ]));
+        let water_defense = DefenseTypeChart::new(chart(&[
+            ("grass", 2.0),
+            ("electric", 2.0),
+            ("fire", 0.5),
+            ("water", 0.5),
+        ]));
+        let water_offense = OffenseTypeChart::new(chart(&[
+            ("fire", 2.0),
+            ("ground", 2.0),
+            ("rock", 2.0),
+            ("water", 0.5),
+            ("grass", 0.5),
+        ]));
+
+        let fire = Synergy::new(
+            &fire_defense,
+            &[&fire_offense],
+            &water_defense,
+            &[&water_offense],
+        );
+        let owned = |checks: &[(&str, bool)]| {
+            checks
+                .iter()
+                .map(|(t, c)| (String::from(*t), *c))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            owned(&[("ground", false), ("rock", false), ("water", true)]),
+            fire.weaknesses
+        );
+        assert_eq!(
+            owned(&[("fire", true), ("rock", true), ("water", false)]),
+            fire.walls
+        );
+
+        let water = Synergy::new(
+            &water_defense,
+            &[&water_offense],
+            &fire_defense,
+            &[&fire_offense],
+        );
+        assert_eq!(
+            owned(&[("electric", false), ("grass", true)]),
+            water.weaknesses
+        );
+        assert_eq!(owned(&[("grass", true), ("water", false)]), water.walls);
+    }
+
+    #[test]
+    fn uncovered_by_fire_ground() {
+        let chart = |relations: &[(&str, f32)]| {
+            OffenseTypeChart::new(
+                relations
+                    .iter()
+                    .map(|(t, m)| (String::from(*t), *m))
+                    .collect(),
+            )
+        };
+        let fire = chart(&[
+            ("grass", 2.0),
+            ("ice", 2.0),
+            ("bug", 2.0),
+            ("steel", 2.0),
+            ("fire", 0.5),
+            ("water", 0.5),
+            ("rock", 0.5),
+            ("dragon", 0.5),
+        ]);
+        let ground = chart(&[
+            ("fire", 2.0),
+            ("electric", 2.0),
+            ("poison", 2.0),
+            ("rock", 2.0),
+            ("steel", 2.0),
+            ("grass", 0.5),
+            ("bug", 0.5),
+            ("flying", 0.0),
+        ]);
+        let defending_types = ["water", "flying", "fire", "dragon", "grass"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<String>>();
+
+        let uncovered = uncovered_typings(&[&fire, &ground], &defending_types);
+        let find = |typing: &[&str]| {
+            uncovered
+                .iter()
+                .find(|(t, _)| t == typing)
+                .map(|(_, multiplier)| *multiplier)
+        };
+
+        assert_eq!(Some(0.5), find(&["water", "flying"]));
+        assert_eq!(Some(0.5), find(&["flying", "dragon"]));
+        assert_eq!(Some(1.0), find(&["water", "dragon"]));
+        assert_eq!(Some(1.0), find(&["flying"]));
+        // Water cancels out fire's advantage over grass
+        assert_eq!(Some(1.0), find(&["water", "grass"]));
+        // Ground hits fire, and fire hits grass
+        assert_eq!(None, find(&["fire"]));
+        assert_eq!(None, find(&["grass"]));
+        assert_eq!(None, find(&["flying", "grass"]));
+    }
+
     #[test]
     fn combine_charts_test() {
         let mut chart1 = HashMap::new();
@@ -906,4 +2781,29 @@ mod tests {
         assert_eq!(combined.get("steel"), Some(&0.0));
         assert_eq!(combined.get("ice"), Some(&1.0));
     }
+
+    #[test]
+    fn fold_charts_test() {
+        let mut chart1 = HashMap::new();
+        chart1.insert("fire".to_string(), 2.0);
+        chart1.insert("water".to_string(), 0.5);
+
+        let mut chart2 = HashMap::new();
+        chart2.insert("fire".to_string(), 2.0);
+        chart2.insert("water".to_string(), 0.5);
+
+        let mut chart3 = HashMap::new();
+        chart3.insert("fire".to_string(), 2.0);
+        chart3.insert("ground".to_string(), 0.0);
+
+        let charts = [chart1, chart2, chart3].map(DefenseTypeChart::new);
+        let folded = DefenseTypeChart::fold(charts).unwrap();
+
+        assert_eq!(8.0, folded.get_multiplier("fire"));
+        assert_eq!(0.25, folded.get_multiplier("water"));
+        assert_eq!(0.0, folded.get_multiplier("ground"));
+        assert_eq!(1.0, folded.get_multiplier("ice"));
+
+        assert!(DefenseTypeChart::fold(vec![]).is_none());
+    }
 }
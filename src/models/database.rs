@@ -1,5 +1,10 @@
+use crate::error::DunsparsError;
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
 use anyhow::{bail, Result};
-use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult, Row};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Result as SqlResult, Row};
 
 pub trait FromRow<T>: Sized {
     fn from_row(value: T, current_gen: u8, db: &Connection) -> Result<Self>;
@@ -40,6 +45,23 @@ pub trait SelectChangeRow: TableRow + Sized {
             .optional()
     }
 
+    fn select_all_by_fk(fk_id: i64, db: &Connection) -> SqlResult<Vec<Self>> {
+        let query = format!(
+            "SELECT * FROM {table} WHERE {fk} = ?1 ORDER BY generation ASC",
+            table = Self::table(),
+            fk = Self::fk()
+        );
+        let mut statement = db.prepare_cached(&query)?;
+        let rows = statement.query_map([fk_id], Self::on_hit)?;
+
+        let mut changes = vec![];
+        for row in rows {
+            changes.push(row?);
+        }
+
+        Ok(changes)
+    }
+
     fn fk() -> &'static str;
     fn on_hit(row: &Row<'_>) -> SqlResult<Self>;
 }
@@ -61,6 +83,31 @@ pub trait SelectAllNames: TableRow {
     }
 }
 
+// For tables whose generation is the one the entry was introduced in
+pub trait SelectGenerationNames: TableRow {
+    fn select_names_up_to_generation(generation: u8, db: &Connection) -> SqlResult<Vec<String>> {
+        let mut statement = db.prepare_cached(&format!(
+            "SELECT name FROM {table} WHERE generation <= ?1 ORDER BY id",
+            table = Self::table()
+        ))?;
+        let rows = statement.query_map([generation], |row| row.get(0))?;
+
+        let mut names = vec![];
+        for row in rows {
+            names.push(row?);
+        }
+
+        Ok(names)
+    }
+}
+
+// Stays well under SQLite's limit on the number of query parameters
+const BATCH_SIZE: usize = 500;
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
 pub enum ResourceResult {
     Valid,
     Invalid(Vec<String>),
@@ -71,6 +118,7 @@ pub struct GameRow {
     pub name: String,
     pub order: u8,
     pub generation: u8,
+    pub versions: String,
 }
 impl TableRow for GameRow {
     fn table() -> &'static str {
@@ -87,16 +135,54 @@ impl SelectRow for GameRow {
             name: row.get(1)?,
             order: row.get(2)?,
             generation: row.get(3)?,
+            versions: row.get(4)?,
         })
     }
 }
 impl InsertRow for GameRow {
     fn insert(&self, db: &Connection) -> SqlResult<usize> {
         let mut statement = db.prepare_cached(include_str!("../sql/insert_game.sql"))?;
-        statement.execute(params![self.id, self.name, self.order, self.generation])
+        statement.execute(params![
+            self.id,
+            self.name,
+            self.order,
+            self.generation,
+            self.versions
+        ])
     }
 }
 impl SelectAllNames for GameRow {}
+impl SelectGenerationNames for GameRow {}
+impl GameRow {
+    // Ids follow PokéAPI's insertion order, which is not necessarily chronological
+    pub fn select_latest(db: &Connection) -> SqlResult<Option<String>> {
+        let query = format!(
+            "SELECT name FROM {table} ORDER BY [generation] DESC, [order] DESC LIMIT 1",
+            table = Self::table()
+        );
+        db.query_row(&query, [], |row| row.get(0)).optional()
+    }
+
+    // Number of games along with the first and last generations they cover
+    pub fn select_coverage(db: &Connection) -> SqlResult<(i64, Option<u8>, Option<u8>)> {
+        let query = format!(
+            "SELECT COUNT(*), MIN([generation]), MAX([generation]) FROM {table}",
+            table = Self::table()
+        );
+        db.query_row(&query, [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+    }
+
+    pub fn select_by_version(version: &str, db: &Connection) -> SqlResult<Option<Self>> {
+        let query = format!(
+            "SELECT * FROM {table} WHERE ',' || versions || ',' LIKE '%,' || ?1 || ',%'",
+            table = Self::table()
+        );
+        db.query_row(&query, [version], <Self as SelectRow>::on_hit)
+            .optional()
+    }
+}
 
 pub struct MoveRow {
     pub id: i64,
@@ -106,6 +192,7 @@ pub struct MoveRow {
     pub pp: Option<i64>,
     pub effect_chance: Option<i64>,
     pub effect: String,
+    pub short_effect: String,
     pub type_: String,
     pub damage_class: String,
     pub generation: u8,
@@ -131,6 +218,7 @@ impl SelectRow for MoveRow {
             type_: row.get(7)?,
             damage_class: row.get(8)?,
             generation: row.get(9)?,
+            short_effect: row.get(10)?,
         })
     }
 }
@@ -147,11 +235,60 @@ impl InsertRow for MoveRow {
             self.type_,
             self.effect,
             self.effect_chance,
-            self.generation
+            self.generation,
+            self.short_effect
         ])
     }
 }
 impl SelectAllNames for MoveRow {}
+impl SelectGenerationNames for MoveRow {}
+impl MoveRow {
+    // One query per chunk rather than per move; names that are not found are left out
+    pub fn select_by_names(names: &[String], db: &Connection) -> SqlResult<Vec<Self>> {
+        let mut moves = vec![];
+        for chunk in names.chunks(BATCH_SIZE) {
+            let query = format!(
+                "SELECT * FROM {table} WHERE name IN ({placeholders})",
+                table = Self::table(),
+                placeholders = placeholders(chunk.len())
+            );
+            let mut statement = db.prepare(&query)?;
+            let rows = statement.query_map(params_from_iter(chunk), <Self as SelectRow>::on_hit)?;
+            for row in rows {
+                moves.push(row?);
+            }
+        }
+
+        Ok(moves)
+    }
+
+    // Searches the latest effect text; past effects kept in move_changes are not searched
+    pub fn select_names_by_effect(
+        keyword: &str,
+        generation: u8,
+        db: &Connection,
+    ) -> SqlResult<Vec<String>> {
+        let query = format!(
+            "SELECT name FROM {table} WHERE generation <= ?1 AND effect LIKE ?2 ESCAPE '\\' ORDER BY name",
+            table = Self::table()
+        );
+        let keyword = keyword
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let mut statement = db.prepare_cached(&query)?;
+        let rows = statement.query_map(params![generation, format!("%{keyword}%")], |row| {
+            row.get(0)
+        })?;
+
+        let mut names = vec![];
+        for row in rows {
+            names.push(row?);
+        }
+
+        Ok(names)
+    }
+}
 
 pub struct MoveChangeRow {
     pub id: Option<i64>,
@@ -191,6 +328,34 @@ impl SelectChangeRow for MoveChangeRow {
         "move_id"
     }
 }
+impl MoveChangeRow {
+    // The change row that applies to each move in the generation, like select_by_fk
+    pub fn select_by_fks(
+        move_ids: &[i64],
+        generation: u8,
+        db: &Connection,
+    ) -> SqlResult<HashMap<i64, Self>> {
+        let mut changes = HashMap::new();
+        for chunk in move_ids.chunks(BATCH_SIZE) {
+            let query = format!(
+                "SELECT * FROM {table} WHERE {fk} IN ({placeholders}) AND generation >= ? ORDER BY generation ASC",
+                table = Self::table(),
+                fk = Self::fk(),
+                placeholders = placeholders(chunk.len())
+            );
+            let mut statement = db.prepare(&query)?;
+            let params = chunk.iter().copied().chain([generation as i64]);
+            let rows =
+                statement.query_map(params_from_iter(params), <Self as SelectChangeRow>::on_hit)?;
+            for row in rows {
+                let row = row?;
+                changes.entry(row.move_id).or_insert(row);
+            }
+        }
+
+        Ok(changes)
+    }
+}
 impl InsertRow for MoveChangeRow {
     fn insert(&self, db: &Connection) -> SqlResult<usize> {
         let mut statement = db.prepare_cached(include_str!("../sql/insert_move_change.sql"))?;
@@ -208,15 +373,76 @@ impl InsertRow for MoveChangeRow {
     }
 }
 
+// Battle details of a move's latest version; the id is the move's
+pub struct MoveMetaRow {
+    pub id: i64,
+    // Percent of the damage dealt; negative for recoil
+    pub drain: i64,
+    // Percent of the user's max HP
+    pub healing: i64,
+    pub crit_rate: i64,
+    pub flinch_chance: i64,
+    pub stat_chance: i64,
+    pub min_hits: Option<i64>,
+    pub max_hits: Option<i64>,
+    // JSON list of stat and stage change pairs, e.g. [["attack",2]]
+    pub stat_changes: String,
+    // Whether the stat changes apply to the user rather than the target
+    pub stat_changes_user: bool,
+}
+impl TableRow for MoveMetaRow {
+    fn table() -> &'static str {
+        "move_meta"
+    }
+    fn label() -> &'static str {
+        "Move Meta"
+    }
+}
+impl InsertRow for MoveMetaRow {
+    fn insert(&self, db: &Connection) -> SqlResult<usize> {
+        let mut statement = db.prepare_cached(include_str!("../sql/insert_move_meta.sql"))?;
+        statement.execute(params![
+            self.id,
+            self.drain,
+            self.healing,
+            self.crit_rate,
+            self.flinch_chance,
+            self.stat_chance,
+            self.min_hits,
+            self.max_hits,
+            self.stat_changes,
+            self.stat_changes_user,
+        ])
+    }
+}
+impl SelectRow for MoveMetaRow {
+    fn on_hit(row: &Row<'_>) -> SqlResult<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            drain: row.get(1)?,
+            healing: row.get(2)?,
+            crit_rate: row.get(3)?,
+            flinch_chance: row.get(4)?,
+            stat_chance: row.get(5)?,
+            min_hits: row.get(6)?,
+            max_hits: row.get(7)?,
+            stat_changes: row.get(8)?,
+            stat_changes_user: row.get(9)?,
+        })
+    }
+}
+
 pub enum MoveRowGroup {
     MoveRow(MoveRow),
     MoveChangeRow(MoveChangeRow),
+    MoveMetaRow(MoveMetaRow),
 }
 impl InsertRow for MoveRowGroup {
     fn insert(&self, db: &Connection) -> SqlResult<usize> {
         match self {
             MoveRowGroup::MoveChangeRow(row) => row.insert(db),
             MoveRowGroup::MoveRow(row) => row.insert(db),
+            MoveRowGroup::MoveMetaRow(row) => row.insert(db),
         }
     }
 }
@@ -272,6 +498,7 @@ impl InsertRow for TypeRow {
     }
 }
 impl SelectAllNames for TypeRow {}
+impl SelectGenerationNames for TypeRow {}
 
 pub struct TypeChangeRow {
     pub id: Option<i64>,
@@ -345,6 +572,7 @@ pub struct AbilityRow {
     pub id: i64,
     pub name: String,
     pub effect: String,
+    pub short_effect: String,
     pub generation: u8,
 }
 impl TableRow for AbilityRow {
@@ -362,16 +590,24 @@ impl SelectRow for AbilityRow {
             name: row.get(1)?,
             effect: row.get(2)?,
             generation: row.get(3)?,
+            short_effect: row.get(4)?,
         })
     }
 }
 impl InsertRow for AbilityRow {
     fn insert(&self, db: &Connection) -> SqlResult<usize> {
         let mut statement = db.prepare_cached(include_str!("../sql/insert_ability.sql"))?;
-        statement.execute(params![self.id, self.name, self.effect, self.generation])
+        statement.execute(params![
+            self.id,
+            self.name,
+            self.effect,
+            self.generation,
+            self.short_effect
+        ])
     }
 }
 impl SelectAllNames for AbilityRow {}
+impl SelectGenerationNames for AbilityRow {}
 
 pub struct EvolutionRow {
     pub id: i64,
@@ -399,6 +635,20 @@ impl SelectRow for EvolutionRow {
         })
     }
 }
+impl EvolutionRow {
+    pub fn select_all(db: &Connection) -> SqlResult<Vec<Self>> {
+        let query = format!("SELECT * FROM {table} ORDER BY id", table = Self::table());
+        let mut statement = db.prepare_cached(&query)?;
+        let rows = statement.query_map([], <Self as SelectRow>::on_hit)?;
+
+        let mut evolutions = vec![];
+        for row in rows {
+            evolutions.push(row?);
+        }
+
+        Ok(evolutions)
+    }
+}
 
 pub struct SpeciesRow {
     pub id: i64,
@@ -406,6 +656,8 @@ pub struct SpeciesRow {
     pub is_baby: bool,
     pub is_legendary: bool,
     pub is_mythical: bool,
+    pub capture_rate: i64,
+    pub growth_rate: String,
     pub evolution_id: Option<i64>,
 }
 impl TableRow for SpeciesRow {
@@ -425,6 +677,8 @@ impl InsertRow for SpeciesRow {
             self.is_baby,
             self.is_legendary,
             self.is_mythical,
+            self.capture_rate,
+            self.growth_rate,
             self.evolution_id,
         ])
     }
@@ -437,7 +691,9 @@ impl SelectRow for SpeciesRow {
             is_baby: row.get(2)?,
             is_legendary: row.get(3)?,
             is_mythical: row.get(4)?,
-            evolution_id: row.get(5)?,
+            capture_rate: row.get(5)?,
+            growth_rate: row.get(6)?,
+            evolution_id: row.get(7)?,
         })
     }
 }
@@ -453,6 +709,12 @@ pub struct PokemonRow {
     pub special_attack: i64,
     pub special_defense: i64,
     pub speed: i64,
+    pub base_experience: Option<i64>,
+    // Decimetres and hectograms, as PokéAPI stores them
+    pub height: i64,
+    pub weight: i64,
+    // Default front sprite URL; some forms have none
+    pub sprite: Option<String>,
     pub species_id: i64,
 }
 impl TableRow for PokemonRow {
@@ -477,6 +739,10 @@ impl InsertRow for PokemonRow {
             self.special_attack,
             self.special_defense,
             self.speed,
+            self.base_experience,
+            self.height,
+            self.weight,
+            self.sprite,
             self.species_id,
         ])
     }
@@ -494,11 +760,138 @@ impl SelectRow for PokemonRow {
             special_attack: row.get(7)?,
             special_defense: row.get(8)?,
             speed: row.get(9)?,
-            species_id: row.get(10)?,
+            base_experience: row.get(10)?,
+            height: row.get(11)?,
+            weight: row.get(12)?,
+            sprite: row.get(13)?,
+            species_id: row.get(14)?,
         })
     }
 }
 impl SelectAllNames for PokemonRow {}
+impl PokemonRow {
+    pub fn select_by_species(species_id: i64, db: &Connection) -> SqlResult<Vec<Self>> {
+        let query = format!(
+            "SELECT * FROM {table} WHERE species_id = ?1 ORDER BY id",
+            table = Self::table()
+        );
+        let mut statement = db.prepare_cached(&query)?;
+        let rows = statement.query_map([species_id], <Self as SelectRow>::on_hit)?;
+
+        let mut forms = vec![];
+        for row in rows {
+            forms.push(row?);
+        }
+
+        Ok(forms)
+    }
+
+    pub fn select_all(db: &Connection) -> SqlResult<Vec<Self>> {
+        let query = format!("SELECT * FROM {table} ORDER BY id", table = Self::table());
+        let mut statement = db.prepare_cached(&query)?;
+        let rows = statement.query_map([], <Self as SelectRow>::on_hit)?;
+
+        let mut pokemon = vec![];
+        for row in rows {
+            pokemon.push(row?);
+        }
+
+        Ok(pokemon)
+    }
+
+    pub fn base_stat_total(&self) -> i64 {
+        self.hp
+            + self.attack
+            + self.defense
+            + self.special_attack
+            + self.special_defense
+            + self.speed
+    }
+
+    // Generations in which the Pokémon has moves, paired with each of their games
+    pub fn select_availability(name: &str, db: &Connection) -> SqlResult<Vec<(u8, String)>> {
+        let mut statement =
+            db.prepare_cached(include_str!("../sql/select_pokemon_availability.sql"))?;
+        let rows = statement.query_map([name], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        let mut games = vec![];
+        for row in rows {
+            games.push(row?);
+        }
+
+        Ok(games)
+    }
+
+    // Base stat totals of the Pokémon present in the generation, highest first
+    pub fn select_totals_by_generation(generation: u8, db: &Connection) -> SqlResult<Vec<i64>> {
+        let mut statement =
+            db.prepare_cached(include_str!("../sql/select_generation_totals.sql"))?;
+        let rows = statement.query_map([generation], |row| row.get(0))?;
+
+        let mut totals = vec![];
+        for row in rows {
+            totals.push(row?);
+        }
+
+        Ok(totals)
+    }
+
+    // Each range bounds a SQL expression over the stat columns, e.g. speed
+    pub fn select_names_by_stats(
+        generation: u8,
+        ranges: &[(&str, RangeInclusive<i64>)],
+        db: &Connection,
+    ) -> SqlResult<Vec<String>> {
+        let mut conditions = String::new();
+        let mut values = vec![];
+        for (column, range) in ranges {
+            conditions += &format!(
+                " AND ({column}) BETWEEN ?{} AND ?{}",
+                values.len() + 2,
+                values.len() + 3
+            );
+            values.extend([*range.start(), *range.end()]);
+        }
+        let query = format!(
+            "SELECT p.[name] FROM {table} AS p
+            WHERE EXISTS (
+                SELECT 1 FROM pokemon_moves AS m
+                WHERE m.[pokemon_id] = p.[id] AND m.[generation] = ?1
+            ){conditions}
+            ORDER BY p.[id]",
+            table = Self::table()
+        );
+
+        let mut statement = db.prepare(&query)?;
+        let params = std::iter::once(i64::from(generation)).chain(values);
+        let rows = statement.query_map(params_from_iter(params), |row| row.get(0))?;
+
+        let mut names = vec![];
+        for row in rows {
+            names.push(row?);
+        }
+
+        Ok(names)
+    }
+
+    // Pokémon with moves in the generation are considered present, as in Pokemon::from_row
+    pub fn select_names_by_generation(
+        generation: u8,
+        include_legendary: bool,
+        db: &Connection,
+    ) -> SqlResult<Vec<String>> {
+        let mut statement =
+            db.prepare_cached(include_str!("../sql/select_generation_pokemon.sql"))?;
+        let rows = statement.query_map(params![generation, include_legendary], |row| row.get(0))?;
+
+        let mut names = vec![];
+        for row in rows {
+            names.push(row?);
+        }
+
+        Ok(names)
+    }
+}
 
 pub struct PokemonMoveRow {
     pub id: Option<i64>,
@@ -548,6 +941,58 @@ impl PokemonMoveRow {
 
         Ok(moves)
     }
+
+    // Generations in which the Pokémon learns the move by any method, oldest first
+    pub fn select_generations(
+        pokemon_id: i64,
+        move_name: &str,
+        db: &Connection,
+    ) -> SqlResult<Vec<u8>> {
+        let mut statement =
+            db.prepare_cached(include_str!("../sql/select_pokemon_move_generations.sql"))?;
+        let rows = statement.query_map(params![pokemon_id, move_name], |row| row.get(0))?;
+
+        let mut generations = vec![];
+        for row in rows {
+            generations.push(row?);
+        }
+
+        Ok(generations)
+    }
+
+    pub fn select_by_move(
+        move_id: i64,
+        generation: u8,
+        learn_method: Option<&str>,
+        db: &Connection,
+    ) -> SqlResult<Vec<String>> {
+        let mut statement = db.prepare_cached(include_str!("../sql/select_move_pokemon.sql"))?;
+        let rows =
+            statement.query_map(params![move_id, generation, learn_method], |row| row.get(0))?;
+
+        let mut pokemon = vec![];
+        for row in rows {
+            pokemon.push(row?);
+        }
+
+        Ok(pokemon)
+    }
+
+    // Every learn method present in the database, e.g. level-up, machine, egg, tutor
+    pub fn select_learn_methods(db: &Connection) -> SqlResult<Vec<String>> {
+        let mut statement = db.prepare_cached(&format!(
+            "SELECT DISTINCT learn_method FROM {table} ORDER BY learn_method",
+            table = Self::table()
+        ))?;
+        let rows = statement.query_map([], |row| row.get(0))?;
+
+        let mut methods = vec![];
+        for row in rows {
+            methods.push(row?);
+        }
+
+        Ok(methods)
+    }
 }
 
 pub struct PokemonAbilityRow {
@@ -578,6 +1023,24 @@ impl InsertRow for PokemonAbilityRow {
     }
 }
 impl PokemonAbilityRow {
+    pub fn select_by_ability(
+        ability_id: i64,
+        hidden_only: bool,
+        db: &Connection,
+    ) -> SqlResult<Vec<(String, bool)>> {
+        let mut statement = db.prepare_cached(include_str!("../sql/select_ability_pokemon.sql"))?;
+        let rows = statement.query_map(params![ability_id, hidden_only], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+
+        let mut pokemon = vec![];
+        for row in rows {
+            pokemon.push(row?);
+        }
+
+        Ok(pokemon)
+    }
+
     pub fn select_by_pokemon(pokemon_id: i64, db: &Connection) -> SqlResult<Vec<(String, bool)>> {
         let mut statement =
             db.prepare_cached(include_str!("../sql/select_pokemon_abilities.sql"))?;
@@ -653,6 +1116,112 @@ impl InsertRow for PokemonRowGroup {
     }
 }
 
+pub struct EncounterRow {
+    pub id: Option<i64>,
+    pub location: String,
+    pub method: String,
+    pub version: String,
+    pub game: String,
+    pub min_level: i64,
+    pub max_level: i64,
+    pub pokemon_id: i64,
+}
+impl TableRow for EncounterRow {
+    fn table() -> &'static str {
+        "encounters"
+    }
+    fn label() -> &'static str {
+        "Encounter"
+    }
+}
+impl InsertRow for EncounterRow {
+    fn insert(&self, db: &Connection) -> SqlResult<usize> {
+        let mut statement = db.prepare_cached(include_str!("../sql/insert_encounter.sql"))?;
+        statement.execute(params![
+            self.location,
+            self.method,
+            self.version,
+            self.game,
+            self.min_level,
+            self.max_level,
+            self.pokemon_id,
+        ])
+    }
+}
+impl SelectRow for EncounterRow {
+    fn on_hit(row: &Row<'_>) -> SqlResult<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            location: row.get(1)?,
+            method: row.get(2)?,
+            version: row.get(3)?,
+            game: row.get(4)?,
+            min_level: row.get(5)?,
+            max_level: row.get(6)?,
+            pokemon_id: row.get(7)?,
+        })
+    }
+}
+impl EncounterRow {
+    pub fn select_by_pokemon(pokemon: &str, game: &str, db: &Connection) -> SqlResult<Vec<Self>> {
+        let mut statement =
+            db.prepare_cached(include_str!("../sql/select_pokemon_encounters.sql"))?;
+        let rows = statement.query_map([pokemon, game], <Self as SelectRow>::on_hit)?;
+
+        let mut encounters = vec![];
+        for row in rows {
+            encounters.push(row?);
+        }
+
+        Ok(encounters)
+    }
+}
+
+pub struct MachineRow {
+    pub id: i64,
+    pub name: String,
+    pub game: String,
+    pub move_id: i64,
+}
+impl TableRow for MachineRow {
+    fn table() -> &'static str {
+        "machines"
+    }
+    fn label() -> &'static str {
+        "Machine"
+    }
+}
+impl InsertRow for MachineRow {
+    fn insert(&self, db: &Connection) -> SqlResult<usize> {
+        let mut statement = db.prepare_cached(include_str!("../sql/insert_machine.sql"))?;
+        statement.execute(params![self.id, self.name, self.game, self.move_id])
+    }
+}
+impl SelectRow for MachineRow {
+    fn on_hit(row: &Row<'_>) -> SqlResult<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            game: row.get(2)?,
+            move_id: row.get(3)?,
+        })
+    }
+}
+impl MachineRow {
+    // Pairs of move name and machine name, e.g. ("flamethrower", "tm35")
+    pub fn select_by_game(game: &str, db: &Connection) -> SqlResult<Vec<(String, String)>> {
+        let mut statement = db.prepare_cached(include_str!("../sql/select_game_machines.sql"))?;
+        let rows = statement.query_map([game], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        let mut machines = vec![];
+        for row in rows {
+            machines.push(row?);
+        }
+
+        Ok(machines)
+    }
+}
+
 pub struct MetaRow {
     pub name: String,
     pub value: String,
@@ -683,64 +1252,80 @@ impl SelectRow for MetaRow {
 pub trait Validate<T> {
     fn validate(&self, value: &str) -> Result<String> {
         let value = value.to_lowercase();
-        match self.check(&value) {
+        match self.check(&value)? {
             ResourceResult::Valid => Ok(value),
-            ResourceResult::Invalid(matches) => bail!(Self::invalid_message(&value, &matches)),
+            ResourceResult::Invalid(matches) => bail!(DunsparsError::NotFound {
+                kind: Self::label(),
+                name: value,
+                matches,
+            }),
         }
     }
 
-    fn check(&self, value: &str) -> ResourceResult {
-        let matches = self.get_matches(value);
+    fn check(&self, value: &str) -> Result<ResourceResult> {
+        let matches = self.get_matches(value)?;
         if matches.iter().any(|m| *m == value) {
-            ResourceResult::Valid
+            Ok(ResourceResult::Valid)
         } else {
-            ResourceResult::Invalid(matches)
+            Ok(ResourceResult::Invalid(matches))
         }
     }
 
-    fn get_matches(&self, value: &str) -> Vec<String> {
-        self.get_resource()
-            .iter()
-            .filter_map(|r| {
-                let close_enough = if !r.is_empty() && !value.is_empty() {
-                    let first_r = r.chars().next().unwrap();
-                    let first_value = value.chars().next().unwrap();
-
-                    // Only perform spellcheck on first character match; potentially expensive
-                    first_r == first_value && strsim::levenshtein(r, value) < 4
-                } else {
-                    false
-                };
-
-                if r.contains(value) || close_enough {
-                    Some(r.clone())
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<String>>()
+    fn get_matches(&self, value: &str) -> Result<Vec<String>> {
+        Ok(find_matches(&self.get_resource()?, value))
     }
 
-    fn invalid_message(value: &str, matches: &[String]) -> String {
-        let resource_name = Self::label();
-        let mut message = format!("{resource_name} '{value}' not found.");
+    fn get_resource(&self) -> Result<Vec<String>>;
+    fn label() -> &'static str;
+}
 
-        if matches.len() > 20 {
-            message += " Potential matches found; too many to display.";
-        } else if !matches.is_empty() {
-            message += &format!(" Potential matches: {}.", matches.join(" "));
+// Validation that also rejects names absent from a generation, suggesting ones present in it.
+// The resource command lists entries across generations and keeps to plain `Validate`.
+pub trait ValidateGeneration<T>: Validate<T> {
+    fn validate_in_generation(&self, value: &str, generation: u8) -> Result<String> {
+        let value = self.validate(value)?;
+        let names = self.get_generation_resource(generation)?;
+        if names.contains(&value) {
+            return Ok(value);
         }
 
-        message
+        bail!(DunsparsError::NotInGeneration {
+            kind: Self::label(),
+            matches: find_matches(&names, &value),
+            name: value,
+            generation,
+        })
     }
 
-    fn get_resource(&self) -> Vec<String>;
-    fn label() -> &'static str;
+    fn get_generation_resource(&self, generation: u8) -> Result<Vec<String>>;
+}
+
+fn find_matches(names: &[String], value: &str) -> Vec<String> {
+    names
+        .iter()
+        .filter_map(|r| {
+            let close_enough = if !r.is_empty() && !value.is_empty() {
+                let first_r = r.chars().next().unwrap();
+                let first_value = value.chars().next().unwrap();
+
+                // Only perform spellcheck on first character match; potentially expensive
+                first_r == first_value && strsim::levenshtein(r, value) < 4
+            } else {
+                false
+            };
+
+            if r.contains(value) || close_enough {
+                Some(r.clone())
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<String>>()
 }
 
 impl<T: SelectAllNames> Validate<T> for Connection {
-    fn get_resource(&self) -> Vec<String> {
-        T::select_all_names(self).unwrap()
+    fn get_resource(&self) -> Result<Vec<String>> {
+        Ok(T::select_all_names(self)?)
     }
 
     fn label() -> &'static str {
@@ -748,6 +1333,31 @@ impl<T: SelectAllNames> Validate<T> for Connection {
     }
 }
 
+impl<T: SelectAllNames + SelectGenerationNames> ValidateGeneration<T> for Connection {
+    fn get_generation_resource(&self, generation: u8) -> Result<Vec<String>> {
+        Ok(T::select_names_up_to_generation(generation, self)?)
+    }
+}
+
+impl Validate<PokemonMoveRow> for Connection {
+    fn get_resource(&self) -> Result<Vec<String>> {
+        Ok(PokemonMoveRow::select_learn_methods(self)?)
+    }
+
+    fn label() -> &'static str {
+        "Learn method"
+    }
+}
+
+// Pokémon have no introduction generation of their own; presence is decided by their moves
+impl ValidateGeneration<PokemonRow> for Connection {
+    fn get_generation_resource(&self, generation: u8) -> Result<Vec<String>> {
+        Ok(PokemonRow::select_names_by_generation(
+            generation, true, self,
+        )?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -765,17 +1375,27 @@ mod tests {
 
     struct MockResource;
     impl Validate<MockRow> for MockResource {
-        fn get_resource(&self) -> Vec<String> {
-            vec!["orangutan", "cricket", "ocelot", "toucan", "wendigo"]
+        fn get_resource(&self) -> Result<Vec<String>> {
+            Ok(vec!["orangutan", "cricket", "ocelot", "toucan", "wendigo"]
                 .into_iter()
                 .map(String::from)
-                .collect()
+                .collect())
         }
 
         fn label() -> &'static str {
             MockRow::label()
         }
     }
+    impl ValidateGeneration<MockRow> for MockResource {
+        fn get_generation_resource(&self, generation: u8) -> Result<Vec<String>> {
+            let names = if generation < 2 {
+                vec!["orangutan", "ocelot"]
+            } else {
+                vec!["orangutan", "cricket", "ocelot", "toucan", "wendigo"]
+            };
+            Ok(names.into_iter().map(String::from).collect())
+        }
+    }
 
     #[test]
     fn resource_validates() {
@@ -796,6 +1416,14 @@ mod tests {
             String::from("Row 'toucannon' not found. Potential matches: toucan."),
             err.to_string()
         );
+        assert_eq!(
+            Some(&DunsparsError::NotFound {
+                kind: "Row",
+                name: String::from("toucannon"),
+                matches: vec![String::from("toucan")],
+            }),
+            err.downcast_ref::<DunsparsError>()
+        );
 
         let ok = resource
             .validate("cricket")
@@ -807,4 +1435,206 @@ mod tests {
             .expect("Wendigo should be valid; validate is case-insensitive");
         assert_eq!(String::from("wendigo"), ok);
     }
+
+    #[test]
+    fn resource_validates_in_generation() {
+        let resource = MockResource;
+
+        let ok = resource.validate_in_generation("Ocelot", 1).unwrap();
+        assert_eq!(String::from("ocelot"), ok);
+
+        let err = resource.validate_in_generation("ocelots", 1).unwrap_err();
+        assert_eq!(
+            "Row 'ocelots' not found. Potential matches: ocelot.",
+            err.to_string()
+        );
+
+        // Suggestions only come from the names present in the generation
+        let err = resource.validate_in_generation("toucan", 1).unwrap_err();
+        assert_eq!(
+            Some(&DunsparsError::NotInGeneration {
+                kind: "Row",
+                name: String::from("toucan"),
+                generation: 1,
+                matches: vec![],
+            }),
+            err.downcast_ref::<DunsparsError>()
+        );
+        let err = resource
+            .validate_in_generation("orangutang", 1)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Row 'orangutang' not found."));
+
+        assert!(resource.validate_in_generation("toucan", 2).is_ok());
+    }
+
+    #[test]
+    fn game_select_latest() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(include_str!("../sql/create_schema.sql"))
+            .unwrap();
+        assert_eq!(None, GameRow::select_latest(&db).unwrap());
+
+        let games = [
+            (1, "scarlet-violet", 25, 9),
+            (2, "red-blue", 1, 1),
+            (3, "the-isle-of-armor", 21, 8),
+            (4, "the-indigo-disk", 27, 9),
+        ];
+        for (id, name, order, generation) in games {
+            GameRow {
+                id,
+                name: String::from(name),
+                order,
+                generation,
+                versions: String::from(name),
+            }
+            .insert(&db)
+            .unwrap();
+        }
+
+        assert_eq!(
+            Some(String::from("the-indigo-disk")),
+            GameRow::select_latest(&db).unwrap()
+        );
+    }
+
+    #[test]
+    fn learn_methods_validate() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(include_str!("../sql/create_schema.sql"))
+            .unwrap();
+
+        let learn_moves = [(1, "level-up", 1), (2, "machine", 0), (3, "level-up", 26)];
+        for (move_id, learn_method, learn_level) in learn_moves {
+            PokemonMoveRow {
+                id: None,
+                move_id,
+                learn_method: String::from(learn_method),
+                learn_level,
+                generation: 9,
+                pokemon_id: 25,
+            }
+            .insert(&db)
+            .unwrap();
+        }
+
+        assert_eq!(
+            vec!["level-up", "machine"],
+            PokemonMoveRow::select_learn_methods(&db).unwrap()
+        );
+        assert_eq!(
+            String::from("machine"),
+            Validate::<PokemonMoveRow>::validate(&db, "Machine").unwrap()
+        );
+        let err = Validate::<PokemonMoveRow>::validate(&db, "levl-up").unwrap_err();
+        assert_eq!(
+            "Learn method 'levl-up' not found. Potential matches: level-up.",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn move_select_by_names() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(include_str!("../sql/create_schema.sql"))
+            .unwrap();
+
+        let moves = [(1, "tackle", 40), (2, "surf", 90), (3, "ember", 40)];
+        for (id, name, power) in moves {
+            MoveRow {
+                id,
+                name: String::from(name),
+                power: Some(power),
+                accuracy: Some(100),
+                pp: Some(15),
+                effect_chance: None,
+                effect: String::new(),
+                short_effect: String::new(),
+                type_: String::from("normal"),
+                damage_class: String::from("physical"),
+                generation: 1,
+            }
+            .insert(&db)
+            .unwrap();
+        }
+        // Tackle changed after generations 4 and 6; surf after generation 5
+        let changes = [(1, 35, 4), (1, 50, 6), (2, 95, 5)];
+        for (move_id, power, generation) in changes {
+            MoveChangeRow {
+                id: None,
+                power: Some(power),
+                accuracy: None,
+                pp: None,
+                effect_chance: None,
+                effect: None,
+                type_: None,
+                generation,
+                move_id,
+            }
+            .insert(&db)
+            .unwrap();
+        }
+
+        let names = ["tackle", "surf", "splash"].map(String::from);
+        let mut rows = MoveRow::select_by_names(&names, &db).unwrap();
+        rows.sort_by_key(|row| row.id);
+        assert_eq!(
+            vec!["tackle", "surf"],
+            rows.iter().map(|row| row.name.as_str()).collect::<Vec<_>>()
+        );
+
+        // Each move gets its earliest change at or after the generation, like select_by_fk
+        let changes = MoveChangeRow::select_by_fks(&[1, 2, 3], 5, &db).unwrap();
+        assert_eq!(Some(50), changes[&1].power);
+        assert_eq!(Some(95), changes[&2].power);
+        assert!(!changes.contains_key(&3));
+        for move_id in [1, 2, 3] {
+            assert_eq!(
+                MoveChangeRow::select_by_fk(move_id, 5, &db)
+                    .unwrap()
+                    .map(|change| change.power),
+                changes.get(&move_id).map(|change| change.power)
+            );
+        }
+    }
+
+    #[test]
+    fn move_select_names_by_effect() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(include_str!("../sql/create_schema.sql"))
+            .unwrap();
+
+        let moves = [
+            (1, "thunder-wave", "Paralyzes the target.", 1),
+            (2, "ember", "Has a 10% chance to burn the target.", 1),
+            (3, "nuzzle", "Has a 100% chance to paralyze the target.", 6),
+        ];
+        for (id, name, effect, generation) in moves {
+            MoveRow {
+                id,
+                name: String::from(name),
+                power: None,
+                accuracy: Some(100),
+                pp: Some(20),
+                effect_chance: None,
+                effect: String::from(effect),
+                short_effect: String::new(),
+                type_: String::from("electric"),
+                damage_class: String::from("status"),
+                generation,
+            }
+            .insert(&db)
+            .unwrap();
+        }
+
+        let search = |keyword, generation| {
+            MoveRow::select_names_by_effect(keyword, generation, &db).unwrap()
+        };
+        assert_eq!(vec!["nuzzle", "thunder-wave"], search("PARALYZE", 9));
+        assert_eq!(vec!["thunder-wave"], search("paralyze", 5));
+        // Wildcards in the keyword are matched literally
+        assert_eq!(vec!["ember"], search("10%", 9));
+        assert!(search("_", 9).is_empty());
+    }
 }
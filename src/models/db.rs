@@ -0,0 +1,120 @@
+use super::{FromDb, Move, MoveList, Pokemon, Type};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+type Cache<T> = RefCell<HashMap<(String, u8), T>>;
+
+// Owns the connection and caches the entries that coverage and match look up repeatedly,
+// e.g. the same move for every matchup. Derefs to the connection for everything else.
+pub struct Db {
+    connection: Connection,
+    moves: Cache<Move>,
+    types: Cache<Type>,
+}
+impl Db {
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            connection,
+            moves: RefCell::new(HashMap::new()),
+            types: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn pokemon(&self, name: &str, generation: u8) -> Result<Pokemon> {
+        Pokemon::from_db(name, generation, &self.connection)
+    }
+
+    pub fn move_(&self, name: &str, generation: u8) -> Result<Move> {
+        self.cached(&self.moves, name, generation)
+    }
+
+    // Types are cached before any custom type chart is applied
+    pub fn type_(&self, name: &str, generation: u8) -> Result<Type> {
+        self.cached(&self.types, name, generation)
+    }
+
+    // Moves missing from the cache are loaded together rather than one query each
+    pub fn move_list(&self, names: &[String], generation: u8) -> Result<MoveList> {
+        let uncached = names
+            .iter()
+            .filter(|name| {
+                let key = ((*name).clone(), generation);
+                !self.moves.borrow().contains_key(&key)
+            })
+            .cloned()
+            .collect::<Vec<String>>();
+        for move_ in Move::from_db_batch(&uncached, generation, &self.connection)? {
+            let key = (move_.name.clone(), generation);
+            self.moves.borrow_mut().insert(key, move_);
+        }
+
+        let mut moves = HashMap::new();
+        for name in names {
+            let move_ = self.move_(name, generation)?;
+            moves.insert(move_.name.clone(), move_);
+        }
+        Ok(MoveList(moves))
+    }
+
+    // The Pokémon's chosen moves, or every learnable move when it has none, e.g. non-custom Pokémon
+    pub fn attacking_moves(&self, pokemon: &Pokemon) -> Result<MoveList> {
+        let move_list = self.move_list(&pokemon.moves, pokemon.generation)?;
+        if !move_list.is_empty() {
+            return Ok(move_list);
+        }
+
+        let learnable_moves = pokemon
+            .learnable_moves
+            .iter()
+            .map(|(name, _, _)| name.clone())
+            .collect::<Vec<String>>();
+        self.move_list(&learnable_moves, pokemon.generation)
+    }
+
+    fn cached<T: FromDb + Clone>(&self, cache: &Cache<T>, name: &str, generation: u8) -> Result<T> {
+        let key = (String::from(name), generation);
+        if let Some(entry) = cache.borrow().get(&key) {
+            return Ok(entry.clone());
+        }
+
+        let entry = T::from_db(name, generation, &self.connection)?;
+        cache.borrow_mut().insert(key, entry.clone());
+        Ok(entry)
+    }
+}
+impl Deref for Db {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.connection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::database::DatabaseFile;
+
+    fn db() -> Db {
+        Db::new(DatabaseFile::default().connect().unwrap())
+    }
+
+    #[test]
+    fn move_is_cached_per_generation() {
+        let db = db();
+        let surf = db.move_("surf", 9).unwrap();
+        let surf_again = db.move_("surf", 9).unwrap();
+        assert_eq!(surf.power, surf_again.power);
+        assert_eq!(1, db.moves.borrow().len());
+
+        // Stats differ across generations, e.g. surf had 95 power before generation 6
+        let old_surf = db.move_("surf", 5).unwrap();
+        assert_eq!(Some(95), old_surf.power);
+        assert_eq!(2, db.moves.borrow().len());
+    }
+}
@@ -1,14 +1,16 @@
 pub mod config;
 pub mod custom;
 pub mod database;
+pub mod type_chart;
 
-use config::ConfigFile;
+use crate::models::Units;
+use config::{ConfigFile, ConfigKey};
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::str::FromStr;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
 enum AppDirectories {
     Data,
@@ -53,11 +55,7 @@ pub trait AppFile: Default {
     }
 
     fn path_exists(path: &Path) -> bool {
-        if let Ok(exists) = path.try_exists() {
-            exists
-        } else {
-            false
-        }
+        path.try_exists().unwrap_or_default()
     }
 
     fn path(&self) -> &PathBuf;
@@ -84,45 +82,76 @@ pub trait YamlFile: AppFile {
     }
 }
 
+// Paths for CI and containers; the --config option and config file values win over them
+const ENV_CONFIG_PATH: &str = "DUNSPARS_CONFIG_PATH";
+const ENV_DB_PATH: &str = "DUNSPARS_DB_PATH";
+const ENV_CUSTOM_PATH: &str = "DUNSPARS_CUSTOM_PATH";
+
 #[derive(Default)]
 pub struct ConfigBuilder {
     game: Option<String>,
     color_enabled: Option<bool>,
+    quiet: bool,
     config_path: Option<PathBuf>,
     db_path: Option<PathBuf>,
     custom_path: Option<PathBuf>,
+    type_chart_path: Option<PathBuf>,
+    flag_defaults: HashMap<ConfigKey, bool>,
+    units: Option<Units>,
 }
 impl ConfigBuilder {
-    pub fn from_file(path: Option<PathBuf>) -> Result<Self> {
+    pub fn from_file(
+        path: Option<PathBuf>,
+        profile: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
         let mut builder = ConfigBuilder::default();
+        let env_path = |key| env(key).filter(|path| !path.is_empty()).map(PathBuf::from);
 
-        let config_file = if let Some(path) = path {
+        let config_file = if let Some(path) = path.or_else(|| env_path(ENV_CONFIG_PATH)) {
             builder = builder.config_path(path.clone());
             ConfigFile::new(path)
         } else {
             ConfigFile::default()
         };
-        let config = config_file.read()?;
+        let config = config_file.read()?.with_profile(profile)?;
 
-        if let Some(color) = config.get_value("color") {
+        if let Some(color) = config.get(ConfigKey::Color.as_str()) {
             if let Ok(color) = color.parse::<bool>() {
                 builder = builder.color_enabled(color);
             }
         }
 
-        if let Some(game) = config.get_value("game") {
+        if let Some(game) = config.get(ConfigKey::Game.as_str()) {
             builder = builder.game(String::from(game));
         }
 
-        if let Some(db_path) = config.get_value("db_path") {
-            if let Ok(path) = PathBuf::from_str(db_path) {
-                builder = builder.db_path(path);
-            }
+        let db_path = config.get(ConfigKey::DbPath.as_str()).map(PathBuf::from);
+        if let Some(db_path) = db_path.or_else(|| env_path(ENV_DB_PATH)) {
+            builder = builder.db_path(db_path);
+        }
+
+        let custom_path = config
+            .get(ConfigKey::CustomPath.as_str())
+            .map(PathBuf::from);
+        if let Some(custom_path) = custom_path.or_else(|| env_path(ENV_CUSTOM_PATH)) {
+            builder = builder.custom_path(custom_path);
         }
 
-        if let Some(custom_path) = config.get_value("custom_path") {
-            if let Ok(path) = PathBuf::from_str(custom_path) {
-                builder = builder.custom_path(path);
+        if let Some(type_chart_path) = config.get(ConfigKey::TypeChartPath.as_str()) {
+            builder = builder.type_chart_path(PathBuf::from(type_chart_path));
+        }
+
+        if let Some(units) = config.get(ConfigKey::Units.as_str()) {
+            builder = builder.units(units.parse()?);
+        }
+
+        for key in ConfigKey::FLAGS {
+            if let Some(value) = config.get(key.as_str()) {
+                let value = value.parse::<bool>().map_err(|_| {
+                    anyhow!("Config '{}' must be either 'true' or 'false'", key.as_str())
+                })?;
+                builder = builder.flag_default(key, value);
             }
         }
 
@@ -140,6 +169,15 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn get_color_enabled(&self) -> Option<bool> {
+        self.color_enabled
+    }
+
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
     pub fn config_path(mut self, path: PathBuf) -> Self {
         self.config_path = Some(path);
         self
@@ -155,13 +193,32 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn type_chart_path(mut self, path: PathBuf) -> Self {
+        self.type_chart_path = Some(path);
+        self
+    }
+
+    pub fn flag_default(mut self, key: ConfigKey, value: bool) -> Self {
+        self.flag_defaults.insert(key, value);
+        self
+    }
+
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = Some(units);
+        self
+    }
+
     pub fn build(self) -> Result<Config> {
         Ok(Config {
             game: self.game,
             color_enabled: self.color_enabled,
+            quiet: self.quiet,
             config_path: self.config_path,
             db_path: self.db_path,
             custom_path: self.custom_path,
+            type_chart_path: self.type_chart_path,
+            flag_defaults: self.flag_defaults,
+            units: self.units,
         })
     }
 }
@@ -170,7 +227,120 @@ impl ConfigBuilder {
 pub struct Config {
     pub game: Option<String>,
     pub color_enabled: Option<bool>,
+    pub quiet: bool,
     pub config_path: Option<PathBuf>,
     pub db_path: Option<PathBuf>,
     pub custom_path: Option<PathBuf>,
+    pub type_chart_path: Option<PathBuf>,
+    pub flag_defaults: HashMap<ConfigKey, bool>,
+    pub units: Option<Units>,
+}
+impl Config {
+    // Flags left out of the config default to off
+    pub fn flag_default(&self, key: ConfigKey) -> bool {
+        self.flag_defaults.get(&key).copied().unwrap_or(false)
+    }
+
+    pub fn get_value(&self, key: ConfigKey) -> Option<String> {
+        match key {
+            ConfigKey::Game => self.game.clone(),
+            ConfigKey::Color => self.color_enabled.map(|c| c.to_string()),
+            ConfigKey::DbPath => self.db_path.as_ref().map(|p| p.display().to_string()),
+            ConfigKey::CustomPath => self.custom_path.as_ref().map(|p| p.display().to_string()),
+            ConfigKey::TypeChartPath => self
+                .type_chart_path
+                .as_ref()
+                .map(|p| p.display().to_string()),
+            ConfigKey::PokemonMoves
+            | ConfigKey::PokemonEvolution
+            | ConfigKey::MatchVerbose
+            | ConfigKey::MatchStabOnly => self.flag_defaults.get(&key).map(|f| f.to_string()),
+            ConfigKey::Units => self.units.map(|u| u.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        |key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    fn config_file(name: &str, contents: &str) -> String {
+        let mut path = std::env::temp_dir();
+        path.push(format!("dunspars_{name}/config.yaml"));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path.display().to_string()
+    }
+
+    #[test]
+    fn env_paths_beat_defaults() {
+        let config_path = config_file("env_paths_beat_defaults", "config: {}\n");
+        let vars = [
+            (ENV_CONFIG_PATH, config_path.as_str()),
+            (ENV_DB_PATH, "/data/dunspars.db"),
+            (ENV_CUSTOM_PATH, ""),
+        ];
+        let config = ConfigBuilder::from_file(None, None, env(&vars))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(Some(PathBuf::from(&config_path)), config.config_path);
+        assert_eq!(Some(PathBuf::from("/data/dunspars.db")), config.db_path);
+        // Empty values are treated as unset
+        assert_eq!(None, config.custom_path);
+    }
+
+    #[test]
+    fn config_paths_beat_env() {
+        let config_path = config_file(
+            "config_paths_beat_env",
+            "config:\n  db_path: /config/dunspars.db\n",
+        );
+        let vars = [
+            (ENV_CONFIG_PATH, "/env/config.yaml"),
+            (ENV_DB_PATH, "/env/dunspars.db"),
+            (ENV_CUSTOM_PATH, "/env/custom.yaml"),
+        ];
+        let config = ConfigBuilder::from_file(Some(PathBuf::from(&config_path)), None, env(&vars))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(Some(PathBuf::from(&config_path)), config.config_path);
+        assert_eq!(Some(PathBuf::from("/config/dunspars.db")), config.db_path);
+        assert_eq!(Some(PathBuf::from("/env/custom.yaml")), config.custom_path);
+    }
+
+    #[test]
+    fn invalid_config_values_are_reported() {
+        let config_path = config_file(
+            "invalid_config_values_are_reported",
+            "config:\n  pokemon.moves: yes please\n",
+        );
+        let err = ConfigBuilder::from_file(Some(PathBuf::from(&config_path)), None, env(&[]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Config 'pokemon.moves' must be either 'true' or 'false'",
+            err.to_string()
+        );
+
+        let config_path = config_file("invalid_config_units", "config:\n  units: furlongs\n");
+        let err = ConfigBuilder::from_file(Some(PathBuf::from(&config_path)), None, env(&[]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Units must be either 'metric' or 'imperial'",
+            err.to_string()
+        );
+    }
 }
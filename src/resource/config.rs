@@ -1,7 +1,15 @@
+use super::custom::CustomFile;
+use super::database::DatabaseFile;
+use super::type_chart::TypeChartFile;
 use super::{app_config_directory, AppFile, YamlFile};
+use crate::models::database::Validate;
+use crate::models::Units;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
 
 pub struct ConfigFile {
     path: PathBuf,
@@ -28,8 +36,25 @@ impl Default for ConfigFile {
 #[derive(Default, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ConfigCollection {
     config: HashMap<String, String>,
+    // Named sets of values that override the base config; absent in older config files
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    profiles: HashMap<String, HashMap<String, String>>,
 }
 impl ConfigCollection {
+    // Base config with the selected profile's values merged over it
+    pub fn with_profile(&self, profile: Option<&str>) -> Result<HashMap<String, String>> {
+        let mut values = self.config.clone();
+
+        if let Some(profile) = profile {
+            let Some(profile_values) = self.profiles.get(profile) else {
+                bail!("Profile '{profile}' not found in config");
+            };
+            values.extend(profile_values.clone());
+        }
+
+        Ok(values)
+    }
+
     pub fn get_collection(&self) -> &HashMap<String, String> {
         &self.config
     }
@@ -46,3 +71,150 @@ impl ConfigCollection {
         self.config.remove(key)
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfigKey {
+    Game,
+    Color,
+    DbPath,
+    CustomPath,
+    TypeChartPath,
+    PokemonMoves,
+    PokemonEvolution,
+    MatchVerbose,
+    MatchStabOnly,
+    Units,
+}
+impl ConfigKey {
+    pub const ALL: [ConfigKey; 10] = [
+        ConfigKey::Game,
+        ConfigKey::Color,
+        ConfigKey::DbPath,
+        ConfigKey::CustomPath,
+        ConfigKey::TypeChartPath,
+        ConfigKey::PokemonMoves,
+        ConfigKey::PokemonEvolution,
+        ConfigKey::MatchVerbose,
+        ConfigKey::MatchStabOnly,
+        ConfigKey::Units,
+    ];
+
+    // Defaults for command flags; an explicit flag or its --no- counterpart still wins
+    pub const FLAGS: [ConfigKey; 4] = [
+        ConfigKey::PokemonMoves,
+        ConfigKey::PokemonEvolution,
+        ConfigKey::MatchVerbose,
+        ConfigKey::MatchStabOnly,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigKey::Game => "game",
+            ConfigKey::Color => "color",
+            ConfigKey::DbPath => "db_path",
+            ConfigKey::CustomPath => "custom_path",
+            ConfigKey::TypeChartPath => "type_chart_path",
+            ConfigKey::PokemonMoves => "pokemon.moves",
+            ConfigKey::PokemonEvolution => "pokemon.evolution",
+            ConfigKey::MatchVerbose => "match.verbose",
+            ConfigKey::MatchStabOnly => "match.stab_only",
+            ConfigKey::Units => "units",
+        }
+    }
+
+    pub fn default_value(&self) -> String {
+        match self {
+            ConfigKey::Game => String::from("latest game"),
+            ConfigKey::Color => String::from("auto"),
+            ConfigKey::DbPath => DatabaseFile::default().path().display().to_string(),
+            ConfigKey::CustomPath => CustomFile::default().path().display().to_string(),
+            ConfigKey::TypeChartPath => TypeChartFile::default().path().display().to_string(),
+            ConfigKey::PokemonMoves
+            | ConfigKey::PokemonEvolution
+            | ConfigKey::MatchVerbose
+            | ConfigKey::MatchStabOnly => String::from("false"),
+            ConfigKey::Units => Units::default().to_string(),
+        }
+    }
+}
+impl std::fmt::Display for ConfigKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+impl FromStr for ConfigKey {
+    type Err = anyhow::Error;
+
+    fn from_str(key: &str) -> Result<Self> {
+        let key = ConfigKeys.validate(key)?;
+        let config_key = Self::ALL.into_iter().find(|k| k.as_str() == key).unwrap();
+        Ok(config_key)
+    }
+}
+
+pub struct ConfigKeys;
+impl Validate<ConfigKey> for ConfigKeys {
+    fn get_resource(&self) -> Result<Vec<String>> {
+        Ok(ConfigKey::ALL.iter().map(|k| k.to_string()).collect())
+    }
+
+    fn label() -> &'static str {
+        "Config key"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_key_from_str() {
+        assert_eq!(ConfigKey::DbPath, ConfigKey::from_str("db_path").unwrap());
+
+        let err = ConfigKey::from_str("gmae").unwrap_err();
+        assert_eq!(
+            String::from("Config key 'gmae' not found. Potential matches: game."),
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn config_flag_keys() {
+        assert_eq!(
+            ConfigKey::MatchStabOnly,
+            ConfigKey::from_str("match.stab_only").unwrap()
+        );
+        assert!(ConfigKey::FLAGS
+            .iter()
+            .all(|key| ConfigKey::ALL.contains(key)));
+    }
+
+    #[test]
+    fn config_profile_overrides() {
+        let collection: ConfigCollection = serde_yaml::from_str(
+            "
+            config:
+              game: sword-shield
+              color: 'false'
+            profiles:
+              vgc:
+                game: scarlet-violet
+            ",
+        )
+        .unwrap();
+
+        let base = collection.with_profile(None).unwrap();
+        assert_eq!("sword-shield", base["game"]);
+
+        let vgc = collection.with_profile(Some("vgc")).unwrap();
+        assert_eq!("scarlet-violet", vgc["game"]);
+        assert_eq!("false", vgc["color"]);
+
+        let err = collection.with_profile(Some("smogon")).unwrap_err();
+        assert_eq!("Profile 'smogon' not found in config", err.to_string());
+
+        // Flat configs without profiles are still read
+        let flat: ConfigCollection = serde_yaml::from_str("config:\n  game: x-y\n").unwrap();
+        assert_eq!("x-y", flat.with_profile(None).unwrap()["game"]);
+    }
+}
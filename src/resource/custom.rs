@@ -1,7 +1,10 @@
-use super::{app_config_directory, AppFile, YamlFile};
+use super::{app_config_directory, AppFile, Config, YamlFile};
+use crate::error::DunsparsError;
 
 use std::path::PathBuf;
 
+use anyhow::{bail, Result};
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct CustomFile {
     path: PathBuf,
@@ -10,6 +13,13 @@ impl CustomFile {
     pub fn new(path: PathBuf) -> Self {
         Self { path }
     }
+
+    pub fn from_config(config: &Config) -> Self {
+        match &config.custom_path {
+            Some(path) => Self::new(path.clone()),
+            None => Self::default(),
+        }
+    }
 }
 impl AppFile for CustomFile {
     fn path(&self) -> &PathBuf {
@@ -35,6 +45,34 @@ impl CustomCollection {
             .iter()
             .find(|p| p.nickname.to_lowercase() == nickname.to_lowercase())
     }
+
+    pub fn pokemon(&self) -> &[CustomPokemon] {
+        &self.pokemon
+    }
+
+    // Nicknames are matched without case, so two entries must not differ by case alone
+    pub fn add(&mut self, pokemon: CustomPokemon) -> Result<()> {
+        if self.find_pokemon(&pokemon.nickname).is_some() {
+            bail!("Custom Pokémon '{}' already exists", pokemon.nickname);
+        }
+        self.pokemon.push(pokemon);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, nickname: &str) -> Result<CustomPokemon> {
+        let position = self
+            .pokemon
+            .iter()
+            .position(|p| p.nickname.to_lowercase() == nickname.to_lowercase());
+        match position {
+            Some(position) => Ok(self.pokemon.remove(position)),
+            None => bail!(DunsparsError::NotFound {
+                kind: "Custom Pokémon",
+                name: String::from(nickname),
+                matches: vec![],
+            }),
+        }
+    }
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -43,5 +81,62 @@ pub struct CustomPokemon {
     pub base: String,
     pub generation: u8,
     pub moves: Vec<String>,
-    pub types: Option<(String, Option<String>)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types: Option<CustomTypes>,
+    #[serde(default)]
+    pub stats: CustomStats,
+}
+
+// Each stat falls back to the base Pokémon's when absent
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CustomStats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attack: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defense: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub special_attack: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub special_defense: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<i64>,
+}
+
+// Fan games may give Pokémon a third type, so both two and three element lists are accepted.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum CustomTypes {
+    Dual(String, Option<String>),
+    Triple(String, Option<String>, Option<String>),
+}
+impl CustomTypes {
+    // None for an empty list, which keeps the base Pokémon's types
+    pub fn from_list(types: &[String]) -> Result<Option<Self>> {
+        let types = match types {
+            [] => None,
+            [primary] => Some(CustomTypes::Dual(primary.clone(), None)),
+            [primary, secondary] => {
+                Some(CustomTypes::Dual(primary.clone(), Some(secondary.clone())))
+            }
+            [primary, secondary, tertiary] => Some(CustomTypes::Triple(
+                primary.clone(),
+                Some(secondary.clone()),
+                Some(tertiary.clone()),
+            )),
+            _ => bail!("A custom Pokémon can have at most three types"),
+        };
+        Ok(types)
+    }
+
+    pub fn get_types(&self) -> (String, Option<String>, Option<String>) {
+        match self {
+            CustomTypes::Dual(primary, secondary) => (primary.clone(), secondary.clone(), None),
+            CustomTypes::Triple(primary, secondary, tertiary) => {
+                (primary.clone(), secondary.clone(), tertiary.clone())
+            }
+        }
+    }
 }
@@ -1,20 +1,24 @@
-use super::{app_data_directory, AppFile};
-use crate::api::api_client;
+use super::{app_data_directory, AppFile, Config};
+use crate::api::{api_client, fetch_error};
 use crate::api::{
-    AbilityFetcher, EvolutionFetcher, FetchResource, GameFetcher, MoveFetcher, PokemonFetcher,
-    SpeciesFetcher, TypeFetcher,
+    AbilityFetcher, EncounterFetcher, EvolutionFetcher, FetchIdentifiers, FetchResource,
+    GameFetcher, MachineFetcher, MoveFetcher, PokemonFetcher, SpeciesFetcher, TypeFetcher,
 };
+use crate::error::DunsparsError;
 use crate::models::database::{InsertRow, MetaRow, SelectRow};
 use crate::VERSION;
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
 use rusqlite::{Connection, OpenFlags, Result as SqlResult};
 use rustemon::client::RustemonClient;
 use semver::Version;
 
+// Bump on any change to create_schema.sql so that databases built with the old schema are rejected
+const SCHEMA_VERSION: u32 = 1;
+
 pub struct DatabaseFile {
     path: PathBuf,
 }
@@ -23,7 +27,20 @@ impl DatabaseFile {
         Self { path }
     }
 
+    pub fn from_config(config: &Config) -> Self {
+        match &config.db_path {
+            Some(path) => Self::new(path.clone()),
+            None => Self::default(),
+        }
+    }
+
     pub fn connect(&self) -> Result<Connection> {
+        let db = self.open()?;
+        Self::version_check(db)
+    }
+
+    // Skips the version check, e.g. to report on an outdated database
+    pub fn open(&self) -> Result<Connection> {
         let mut flags = OpenFlags::default();
         flags.set(OpenFlags::SQLITE_OPEN_READ_WRITE, false);
         flags.set(OpenFlags::SQLITE_OPEN_CREATE, false);
@@ -31,10 +48,10 @@ impl DatabaseFile {
 
         let open = Connection::open_with_flags(&self.path, flags);
         if let Ok(db) = open {
-            return Self::version_check(db);
+            return Ok(db);
         }
 
-        bail!("Database not set up. Run `dunspars setup` first.")
+        bail!(DunsparsError::DatabaseMissing)
     }
 
     fn version_check(db: Connection) -> Result<Connection> {
@@ -42,27 +59,126 @@ impl DatabaseFile {
 
         if let Ok(db_version) = meta {
             if versions_within_minor_level(&db_version.value, VERSION).unwrap_or(false) {
-                return Ok(db);
+                return Self::schema_check(db);
             }
 
-            bail!(
-                "Database version mismatch. Program version: {0}; Database version: {1}. Run `dunspars setup` again.",
-                VERSION,
-                db_version.value
-            )
+            bail!(DunsparsError::VersionMismatch {
+                program: String::from(VERSION),
+                database: db_version.value,
+            })
         }
 
-        bail!("Database malformed. Run `dunspars setup` again.")
+        bail!(DunsparsError::DatabaseMalformed)
     }
 
-    pub async fn build_db(&self, writer: &mut impl std::io::Write) -> Result<()> {
+    // Databases built before schema versions were stored have none and count as outdated
+    fn schema_check(db: Connection) -> Result<Connection> {
+        let schema = MetaRow::select_by_name("schema", &db)
+            .ok()
+            .and_then(|schema| schema.value.parse::<u32>().ok());
+        if schema == Some(SCHEMA_VERSION) {
+            return Ok(db);
+        }
+
+        bail!(DunsparsError::SchemaMismatch {
+            program: SCHEMA_VERSION,
+            database: schema,
+        })
+    }
+
+    // The database is built in a temporary file so that a failed setup keeps the previous one
+    pub async fn build_db(
+        &self,
+        writer: &mut impl std::io::Write,
+        with_encounters: bool,
+        with_machines: bool,
+    ) -> Result<()> {
         self.build_dir()?;
-        if Self::path_exists(&self.path) {
-            fs::remove_file(&self.path)?;
+        let tmp_path = self.tmp_path();
+        if Self::path_exists(&tmp_path) {
+            fs::remove_file(&tmp_path)?;
+        }
+
+        let result = self
+            .populate_db(&tmp_path, writer, with_encounters, with_machines)
+            .await;
+        self.finish_build(&tmp_path, result)
+    }
+
+    // Lists how many entries setup would retrieve without retrieving them or touching the database
+    pub async fn dry_run(
+        &self,
+        writer: &mut impl std::io::Write,
+        with_encounters: bool,
+        with_machines: bool,
+    ) -> Result<()> {
+        let api = api_client();
+        let mut total = 0;
+
+        total += Self::count_identifiers::<GameFetcher>("games", &api, writer).await?;
+        total += Self::count_identifiers::<MoveFetcher>("moves", &api, writer).await?;
+        total += Self::count_identifiers::<TypeFetcher>("types", &api, writer).await?;
+        total += Self::count_identifiers::<AbilityFetcher>("abilities", &api, writer).await?;
+        total += Self::count_identifiers::<SpeciesFetcher>("species", &api, writer).await?;
+        total += Self::count_identifiers::<EvolutionFetcher>("evolution", &api, writer).await?;
+        total += Self::count_identifiers::<PokemonFetcher>("pokemon", &api, writer).await?;
+        if with_encounters {
+            total +=
+                Self::count_identifiers::<EncounterFetcher>("encounters", &api, writer).await?;
+        }
+        if with_machines {
+            total += Self::count_identifiers::<MachineFetcher>("machines", &api, writer).await?;
+        }
+
+        writeln!(writer, "total: {total}")?;
+        Ok(())
+    }
+
+    async fn count_identifiers<T: FetchIdentifiers>(
+        label: &str,
+        api: &RustemonClient,
+        writer: &mut impl std::io::Write,
+    ) -> Result<usize> {
+        // Counting these would mean retrieving every entry of another resource
+        if let Some(source) = T::DERIVED_FROM {
+            writeln!(writer, "{label}: unknown; listed by the {source} entries")?;
+            return Ok(0);
+        }
+
+        let count = T::fetch_all_identifiers(api)
+            .await
+            .map_err(fetch_error)?
+            .len();
+        writeln!(writer, "{label}: {count}")?;
+        Ok(count)
+    }
+
+    fn tmp_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".tmp");
+        PathBuf::from(path)
+    }
+
+    fn finish_build(&self, tmp_path: &Path, result: Result<()>) -> Result<()> {
+        if let Err(e) = result {
+            // Cleanup is best effort; the original error is more useful
+            let _ = fs::remove_file(tmp_path);
+            return Err(e);
         }
 
+        fs::rename(tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    async fn populate_db(
+        &self,
+        path: &Path,
+        writer: &mut impl std::io::Write,
+        with_encounters: bool,
+        with_machines: bool,
+    ) -> Result<()> {
         let api = api_client();
-        let mut db = Connection::open(&self.path)?;
+        let mut db = Connection::open(path)?;
 
         let start = std::time::Instant::now();
 
@@ -98,7 +214,19 @@ impl DatabaseFile {
         self.fetch_and_populate::<PokemonFetcher>(&api, &mut db)
             .await?;
 
-        self.populate_meta(&mut db)?;
+        if with_encounters {
+            writeln!(writer, "retrieving encounters")?;
+            self.fetch_and_populate::<EncounterFetcher>(&api, &mut db)
+                .await?;
+        }
+
+        if with_machines {
+            writeln!(writer, "retrieving machines")?;
+            self.fetch_and_populate::<MachineFetcher>(&api, &mut db)
+                .await?;
+        }
+
+        self.populate_meta(&mut db, with_encounters, with_machines)?;
 
         let duration = start.elapsed();
         writeln!(writer, "setup time: {}s", duration.as_secs())?;
@@ -128,11 +256,35 @@ impl DatabaseFile {
         transaction.commit()
     }
 
-    fn populate_meta(&self, db: &mut Connection) -> SqlResult<()> {
-        let meta = vec![MetaRow {
-            name: String::from("version"),
-            value: String::from(VERSION),
-        }];
+    fn populate_meta(
+        &self,
+        db: &mut Connection,
+        with_encounters: bool,
+        with_machines: bool,
+    ) -> SqlResult<()> {
+        let built_at: String = db.query_row("SELECT datetime('now')", [], |row| row.get(0))?;
+        let meta = vec![
+            MetaRow {
+                name: String::from("version"),
+                value: String::from(VERSION),
+            },
+            MetaRow {
+                name: String::from("schema"),
+                value: SCHEMA_VERSION.to_string(),
+            },
+            MetaRow {
+                name: String::from("encounters"),
+                value: with_encounters.to_string(),
+            },
+            MetaRow {
+                name: String::from("machines"),
+                value: with_machines.to_string(),
+            },
+            MetaRow {
+                name: String::from("built_at"),
+                value: built_at,
+            },
+        ];
         self.populate_table(meta, db)
     }
 }
@@ -176,4 +328,68 @@ mod tests {
         let parse_error = versions_within_minor_level("1.2.3", "1.23");
         assert!(parse_error.is_err());
     }
+
+    #[test]
+    fn schema_version_is_checked() {
+        let db_with_schema = |schema: Option<&str>| {
+            let db = Connection::open_in_memory().unwrap();
+            db.execute_batch("CREATE TABLE meta ([name] TEXT PRIMARY KEY, [value] TEXT NOT NULL);")
+                .unwrap();
+            db.execute("INSERT INTO meta VALUES ('version', ?1)", [VERSION])
+                .unwrap();
+            if let Some(schema) = schema {
+                db.execute("INSERT INTO meta VALUES ('schema', ?1)", [schema])
+                    .unwrap();
+            }
+            db
+        };
+
+        let current = db_with_schema(Some(&SCHEMA_VERSION.to_string()));
+        assert!(DatabaseFile::version_check(current).is_ok());
+
+        let err = DatabaseFile::version_check(db_with_schema(None)).unwrap_err();
+        assert_eq!(
+            Some(&DunsparsError::SchemaMismatch {
+                program: SCHEMA_VERSION,
+                database: None,
+            }),
+            err.downcast_ref::<DunsparsError>()
+        );
+
+        let err = DatabaseFile::version_check(db_with_schema(Some("0"))).unwrap_err();
+        assert_eq!(
+            Some(&DunsparsError::SchemaMismatch {
+                program: SCHEMA_VERSION,
+                database: Some(0),
+            }),
+            err.downcast_ref::<DunsparsError>()
+        );
+    }
+
+    #[test]
+    fn finish_build_keeps_previous_db() {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("dunspars-finish-build-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let file = DatabaseFile::new(dir.join("resource.db"));
+        let tmp_path = file.tmp_path();
+        assert_eq!(dir.join("resource.db.tmp"), tmp_path);
+        fs::write(&file.path, "old").unwrap();
+
+        // A failed build removes the partial database and leaves the old one alone
+        fs::write(&tmp_path, "partial").unwrap();
+        let err = file.finish_build(&tmp_path, Err(anyhow::anyhow!("network dropped")));
+        assert_eq!("network dropped", err.unwrap_err().to_string());
+        assert!(!tmp_path.exists());
+        assert_eq!("old", fs::read_to_string(&file.path).unwrap());
+
+        // A successful build replaces it
+        fs::write(&tmp_path, "new").unwrap();
+        file.finish_build(&tmp_path, Ok(())).unwrap();
+        assert!(!tmp_path.exists());
+        assert_eq!("new", fs::read_to_string(&file.path).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
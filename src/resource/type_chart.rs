@@ -0,0 +1,174 @@
+use super::{app_config_directory, AppFile, YamlFile};
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+
+pub struct TypeChartFile {
+    path: PathBuf,
+}
+impl TypeChartFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+impl AppFile for TypeChartFile {
+    fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+impl YamlFile for TypeChartFile {
+    type YamlData = TypeChartCollection;
+}
+impl Default for TypeChartFile {
+    fn default() -> Self {
+        Self::new(app_config_directory("type_chart.yaml"))
+    }
+}
+
+// Type charts of fan games, keyed by type; these take precedence over the database's
+#[derive(Default, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct TypeChartCollection(HashMap<String, CustomTypeChart>);
+impl TypeChartCollection {
+    pub fn find_type(&self, name: &str) -> Option<&CustomTypeChart> {
+        self.0.get(name)
+    }
+
+    // Every type named in the file, including those within the charts
+    pub fn type_names(&self) -> Vec<&String> {
+        let mut names = vec![];
+        for (name, chart) in self.0.iter() {
+            names.push(name);
+            for multipliers in [&chart.to, &chart.from].into_iter().flatten() {
+                names.extend(multipliers.keys());
+            }
+        }
+        names
+    }
+
+    // The multiplier of an attacking type against a defending one if the file sets it, from the
+    // attacker's `to` side or else the defender's `from` side
+    pub fn multiplier(&self, attacker: &str, defender: &str) -> Option<f32> {
+        let side = |name: &str, to: bool| {
+            let chart = self.find_type(name)?;
+            if to {
+                chart.to.as_ref()
+            } else {
+                chart.from.as_ref()
+            }
+        };
+
+        if let Some(to) = side(attacker, true) {
+            return Some(to.get(defender).copied().unwrap_or(1.0));
+        }
+        side(defender, false).map(|from| from.get(attacker).copied().unwrap_or(1.0))
+    }
+
+    // Both sides describe the same matchups, so a pair covered by both must agree
+    pub fn check_conflicts(&self) -> Result<()> {
+        let mut attackers = self
+            .0
+            .iter()
+            .filter_map(|(name, chart)| Some((name, chart.to.as_ref()?)))
+            .collect::<Vec<_>>();
+        attackers.sort_by_key(|(name, _)| *name);
+        let mut defenders = self
+            .0
+            .iter()
+            .filter_map(|(name, chart)| Some((name, chart.from.as_ref()?)))
+            .collect::<Vec<_>>();
+        defenders.sort_by_key(|(name, _)| *name);
+
+        for (attacker, to) in &attackers {
+            for (defender, from) in &defenders {
+                let to_multiplier = to.get(*defender).copied().unwrap_or(1.0);
+                let from_multiplier = from.get(*attacker).copied().unwrap_or(1.0);
+                if to_multiplier != from_multiplier {
+                    bail!(
+                        "{attacker} to {defender} is x{to_multiplier} but {defender} from {attacker} is x{from_multiplier}"
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Multipliers when attacking (to) and when attacked (from); a missing side keeps the database's,
+// and types left out of a side are neutral. One type's `to` also sets the others' `from`
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CustomTypeChart {
+    pub to: Option<HashMap<String, f32>>,
+    pub from: Option<HashMap<String, f32>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_type_chart() {
+        let collection: TypeChartCollection = serde_yaml::from_str(
+            "
+            fairy:
+              to:
+                dragon: 2
+            steel:
+              from:
+                fairy: 1
+            ",
+        )
+        .unwrap();
+
+        let fairy = collection.find_type("fairy").unwrap();
+        assert_eq!(Some(&2.0), fairy.to.as_ref().unwrap().get("dragon"));
+        assert!(fairy.from.is_none());
+        assert!(collection.find_type("fire").is_none());
+
+        let mut names = collection.type_names();
+        names.sort();
+        assert_eq!(vec!["dragon", "fairy", "fairy", "steel"], names);
+    }
+
+    #[test]
+    fn type_chart_multipliers() {
+        let collection: TypeChartCollection = serde_yaml::from_str(
+            "
+            ghost:
+              to:
+                psychic: 2
+            steel:
+              from:
+                fairy: 1
+            ",
+        )
+        .unwrap();
+
+        // The attacker's side also applies when defending, and types left out are neutral
+        assert_eq!(Some(2.0), collection.multiplier("ghost", "psychic"));
+        assert_eq!(Some(1.0), collection.multiplier("ghost", "normal"));
+        assert_eq!(Some(1.0), collection.multiplier("fairy", "steel"));
+        assert_eq!(Some(1.0), collection.multiplier("fire", "steel"));
+        assert_eq!(None, collection.multiplier("fire", "grass"));
+        collection.check_conflicts().unwrap();
+
+        let conflicting: TypeChartCollection = serde_yaml::from_str(
+            "
+            fairy:
+              to:
+                steel: 0.5
+            steel:
+              from:
+                fairy: 1
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            "fairy to steel is x0.5 but steel from fairy is x1",
+            conflicting.check_conflicts().unwrap_err().to_string()
+        );
+    }
+}
@@ -7,7 +7,8 @@ CREATE TABLE games (
     [id] INTEGER PRIMARY KEY,
     [name] TEXT NOT NULL,
     [order] INTEGER NOT NULL,
-    [generation] INTEGER NOT NULL
+    [generation] INTEGER NOT NULL,
+    [versions] TEXT NOT NULL
 );
 
 CREATE TABLE evolutions (
@@ -21,6 +22,8 @@ CREATE TABLE species (
     [is_baby] BOOLEAN NOT NULL,
     [is_legendary] BOOLEAN NOT NULL,
     [is_mythical] BOOLEAN NOT NULL,
+    [capture_rate] INTEGER NOT NULL,
+    [growth_rate] TEXT NOT NULL,
     [evolution_id] INTEGER,
     FOREIGN KEY([evolution_id]) REFERENCES evolutions([id])
 );
@@ -36,6 +39,10 @@ CREATE TABLE pokemon (
     [special_attack] INTEGER NOT NULL,
     [special_defense] INTEGER NOT NULL,
     [speed] INTEGER NOT NULL,
+    [base_experience] INTEGER,
+    [height] INTEGER NOT NULL,
+    [weight] INTEGER NOT NULL,
+    [sprite] TEXT,
     [species_id] INTEGER NOT NULL,
     FOREIGN KEY([species_id]) REFERENCES species([id])
 );
@@ -80,7 +87,8 @@ CREATE TABLE moves (
     [effect] TEXT NOT NULL,
     [type] TEXT NOT NULL,
     [damage_class] TEXT NOT NULL,
-    [generation] INTEGER NOT NULL
+    [generation] INTEGER NOT NULL,
+    [short_effect] TEXT NOT NULL
 );
 
 CREATE TABLE move_changes (
@@ -96,6 +104,20 @@ CREATE TABLE move_changes (
     FOREIGN KEY([move_id]) REFERENCES moves([id])
 );
 
+CREATE TABLE move_meta (
+    [id] INTEGER PRIMARY KEY,
+    [drain] INTEGER NOT NULL,
+    [healing] INTEGER NOT NULL,
+    [crit_rate] INTEGER NOT NULL,
+    [flinch_chance] INTEGER NOT NULL,
+    [stat_chance] INTEGER NOT NULL,
+    [min_hits] INTEGER,
+    [max_hits] INTEGER,
+    [stat_changes] TEXT NOT NULL,
+    [stat_changes_user] BOOLEAN NOT NULL,
+    FOREIGN KEY([id]) REFERENCES moves([id])
+);
+
 CREATE TABLE types (
     [id] INTEGER PRIMARY KEY,
     [name] TEXT NOT NULL,
@@ -125,5 +147,26 @@ CREATE TABLE abilities (
     [id] INTEGER PRIMARY KEY,
     [name] TEXT NOT NULL,
     [effect] TEXT NOT NULL,
-    [generation] ITNEGER NOT NULL
+    [generation] ITNEGER NOT NULL,
+    [short_effect] TEXT NOT NULL
+);
+
+CREATE TABLE encounters (
+    [id] INTEGER PRIMARY KEY,
+    [location] TEXT NOT NULL,
+    [method] TEXT NOT NULL,
+    [version] TEXT NOT NULL,
+    [game] TEXT NOT NULL,
+    [min_level] INTEGER NOT NULL,
+    [max_level] INTEGER NOT NULL,
+    [pokemon_id] INTEGER NOT NULL,
+    FOREIGN KEY([pokemon_id]) REFERENCES pokemon([id])
+);
+
+CREATE TABLE machines (
+    [id] INTEGER PRIMARY KEY,
+    [name] TEXT NOT NULL,
+    [game] TEXT NOT NULL,
+    [move_id] INTEGER NOT NULL,
+    FOREIGN KEY([move_id]) REFERENCES moves([id])
 );
\ No newline at end of file
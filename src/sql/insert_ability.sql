@@ -1 +1 @@
-INSERT INTO abilities ([id], [name], [effect], [generation]) VALUES (?1, ?2, ?3, ?4);
\ No newline at end of file
+INSERT INTO abilities ([id], [name], [effect], [generation], [short_effect]) VALUES (?1, ?2, ?3, ?4, ?5);
\ No newline at end of file
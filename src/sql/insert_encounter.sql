@@ -0,0 +1 @@
+INSERT INTO encounters ([location], [method], [version], [game], [min_level], [max_level], [pokemon_id]) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);
\ No newline at end of file
@@ -1 +1 @@
-INSERT INTO games ([id], [name], [order], [generation]) VALUES (?1, ?2, ?3, ?4);
\ No newline at end of file
+INSERT INTO games ([id], [name], [order], [generation], [versions]) VALUES (?1, ?2, ?3, ?4, ?5);
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO machines ([id], [name], [game], [move_id]) VALUES (?1, ?2, ?3, ?4);
\ No newline at end of file
@@ -8,5 +8,6 @@ INSERT INTO moves (
     [type],
     [effect],
     [effect_chance],
-    [generation]
-) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10);
\ No newline at end of file
+    [generation],
+    [short_effect]
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11);
\ No newline at end of file
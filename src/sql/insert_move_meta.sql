@@ -0,0 +1,12 @@
+INSERT INTO move_meta (
+    [id],
+    [drain],
+    [healing],
+    [crit_rate],
+    [flinch_chance],
+    [stat_chance],
+    [min_hits],
+    [max_hits],
+    [stat_changes],
+    [stat_changes_user]
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10);
\ No newline at end of file
@@ -1 +1 @@
-INSERT INTO pokemon ([id], [name], [primary_type], [secondary_type], [hp], [attack], [defense], [special_attack], [special_defense], [speed], [species_id]) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11);
+INSERT INTO pokemon ([id], [name], [primary_type], [secondary_type], [hp], [attack], [defense], [special_attack], [special_defense], [speed], [base_experience], [height], [weight], [sprite], [species_id]) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15);
\ No newline at end of file
@@ -1 +1 @@
-INSERT INTO species ([id], [name], [is_baby], [is_legendary], [is_mythical], [evolution_id]) VALUES (?1, ?2, ?3, ?4, ?5, ?6);
\ No newline at end of file
+INSERT INTO species ([id], [name], [is_baby], [is_legendary], [is_mythical], [capture_rate], [growth_rate], [evolution_id]) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);
\ No newline at end of file
@@ -0,0 +1,7 @@
+SELECT 
+    p.[name], a.[is_hidden]
+FROM pokemon_abilities AS a
+JOIN pokemon AS p
+    ON p.[id] = a.[pokemon_id]
+WHERE a.[ability_id] = ?1 AND (a.[is_hidden] OR NOT ?2)
+ORDER BY p.[id];
\ No newline at end of file
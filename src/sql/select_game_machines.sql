@@ -0,0 +1,7 @@
+SELECT 
+    mv.[name],
+    ma.[name]
+FROM machines AS ma
+JOIN moves AS mv
+    ON mv.[id] = ma.[move_id]
+WHERE ma.[game] = ?1;
\ No newline at end of file
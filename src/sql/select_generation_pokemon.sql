@@ -0,0 +1,11 @@
+SELECT
+    p.[name]
+FROM pokemon AS p
+JOIN species AS s
+    ON s.[id] = p.[species_id]
+WHERE EXISTS (
+        SELECT 1 FROM pokemon_moves AS m
+        WHERE m.[pokemon_id] = p.[id] AND m.[generation] = ?1
+    )
+    AND (?2 OR NOT (s.[is_legendary] OR s.[is_mythical]))
+ORDER BY p.[id];
\ No newline at end of file
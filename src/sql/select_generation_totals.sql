@@ -0,0 +1,10 @@
+SELECT
+    p.[hp] + p.[attack] + p.[defense] + p.[special_attack] + p.[speed]
+        -- Generation 1 had a single Special stat, as in Stats::get
+        + CASE WHEN ?1 = 1 THEN 0 ELSE p.[special_defense] END AS [total]
+FROM pokemon AS p
+WHERE EXISTS (
+    SELECT 1 FROM pokemon_moves AS m
+    WHERE m.[pokemon_id] = p.[id] AND m.[generation] = ?1
+)
+ORDER BY [total] DESC;
\ No newline at end of file
@@ -0,0 +1,10 @@
+SELECT
+    p.[name]
+FROM pokemon_moves AS m
+JOIN pokemon AS p
+    ON p.[id] = m.[pokemon_id]
+WHERE m.[move_id] = ?1
+    AND m.[generation] = ?2
+    AND (?3 IS NULL OR m.[learn_method] = ?3)
+GROUP BY p.[id]
+ORDER BY p.[id];
\ No newline at end of file
@@ -0,0 +1,12 @@
+SELECT
+    g.[generation],
+    g.[name]
+FROM games AS g
+WHERE g.[generation] IN (
+    SELECT DISTINCT m.[generation]
+    FROM pokemon_moves AS m
+    JOIN pokemon AS p
+        ON p.[id] = m.[pokemon_id]
+    WHERE p.[name] = ?1
+)
+ORDER BY g.[generation], g.[order];
\ No newline at end of file
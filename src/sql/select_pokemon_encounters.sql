@@ -0,0 +1,7 @@
+SELECT 
+    e.*
+FROM encounters AS e
+JOIN pokemon AS p
+    ON p.[id] = e.[pokemon_id]
+WHERE p.[name] = ?1 AND e.[game] = ?2
+ORDER BY e.[location], e.[version];
\ No newline at end of file
@@ -0,0 +1,8 @@
+SELECT DISTINCT
+    p.[generation]
+FROM pokemon_moves AS p
+JOIN moves AS m
+    ON m.[id] = p.[move_id]
+WHERE p.[pokemon_id] = ?1
+    AND m.[name] = ?2
+ORDER BY p.[generation];